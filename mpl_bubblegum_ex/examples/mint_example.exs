@@ -9,16 +9,16 @@ payer_secret = Base.decode64!(payer_json["secret"])
 
 # Load tree creator keypair from file (generated by solana-keygen)
 tree_creator_json = Jason.decode!(File.read!("tree_creator.json"))
+tree_creator_secret = Base.decode64!(tree_creator_json["secret"])
 {:ok, tree_creator} = Pubkey.from_base58(tree_creator_json["public"])
 
-# Load tree config keypair from file (generated by solana-keygen)
-tree_config_json = Jason.decode!(File.read!("tree_config.json"))
-{:ok, tree_config} = Pubkey.from_base58(tree_config_json["public"])
-
 # Load merkle tree keypair from file (generated by solana-keygen)
 merkle_tree_json = Jason.decode!(File.read!("merkle_tree.json"))
 {:ok, merkle_tree} = Pubkey.from_base58(merkle_tree_json["public"])
 
+# The tree config is a PDA of the merkle tree
+{:ok, tree_config, _bump} = MplBubblegum.derive_tree_config_pda(merkle_tree)
+
 # Use payer as the owner for simplicity
 {:ok, owner} = Pubkey.from_base58(payer_json["public"])
 
@@ -47,19 +47,23 @@ params = %{
   merkle_tree: merkle_tree,
   payer: payer,
   tree_creator_or_delegate: tree_creator,
-  metadata: metadata
+  metadata: metadata,
+  # First mint into a freshly created tree
+  nonce: 0
 }
 
 IO.puts("Minting NFT with pubkeys:")
 IO.puts("- Payer/Owner: #{payer_json["public"]}")
 IO.puts("- Tree Creator: #{tree_creator_json["public"]}")
-IO.puts("- Tree Config: #{tree_config_json["public"]}")
+IO.puts("- Tree Config: #{Pubkey.to_base58(tree_config)}")
 IO.puts("- Merkle Tree: #{merkle_tree_json["public"]}")
 
 case MplBubblegum.mint_v1(params) do
-  {:ok, transaction} ->
-    IO.puts("Transaction created (size: #{byte_size(transaction)} bytes). Signing and submitting...")
-    case MplBubblegum.sign_and_submit_transaction(transaction, payer_secret) do
+  {:ok, transaction, asset_id} ->
+    transaction_binary = :binary.list_to_bin(transaction)
+    IO.puts("Transaction created (size: #{byte_size(transaction_binary)} bytes) for asset #{MplBubblegum.Types.Pubkey.to_base58(asset_id)}. Signing and submitting...")
+    # The tree creator must sign as the tree's delegate
+    case MplBubblegum.sign_and_submit_transaction(transaction_binary, [payer_secret, tree_creator_secret]) do
       {:ok, signature} ->
         IO.puts("Transaction submitted with signature: #{signature}")
         check_status(signature)
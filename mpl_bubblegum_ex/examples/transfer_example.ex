@@ -11,14 +11,13 @@ payer_secret = Base.decode64!(payer_json["secret"])
 tree_creator_json = Jason.decode!(File.read!("tree_creator.json"))
 {:ok, tree_creator} = Pubkey.from_base58(tree_creator_json["public"])
 
-# Load tree config keypair from file (generated by solana-keygen)
-tree_config_json = Jason.decode!(File.read!("tree_config.json"))
-{:ok, tree_config} = Pubkey.from_base58(tree_config_json["public"])
-
 # Load merkle tree keypair from file (generated by solana-keygen)
 merkle_tree_json = Jason.decode!(File.read!("merkle_tree.json"))
 {:ok, merkle_tree} = Pubkey.from_base58(merkle_tree_json["public"])
 
+# The tree config is a PDA of the merkle tree
+{:ok, tree_config, _bump} = MplBubblegum.derive_tree_config_pda(merkle_tree)
+
 # Load new owner keypair from file (generated by solana-keygen)
 new_owner_json = Jason.decode!(File.read!("new_owner.json"))
 {:ok, new_owner} = Pubkey.from_base58(new_owner_json["public"])
@@ -33,6 +32,7 @@ index = 0                                  # Placeholder index
 # Transfer transaction
 params = %{
   tree_config: tree_config,
+  payer: owner,
   leaf_owner: owner,
   leaf_delegate: owner,
   new_leaf_owner: new_owner,
@@ -45,16 +45,17 @@ params = %{
 }
 
 IO.puts("Transferring NFT with pubkeys:")
-IO.puts("- Owner: #{payer_json["public"]}")
+IO.puts("- Owner/Payer: #{payer_json["public"]}")
 IO.puts("- Tree Creator: #{tree_creator_json["public"]}")
-IO.puts("- Tree Config: #{tree_config_json["public"]}")
+IO.puts("- Tree Config: #{Pubkey.to_base58(tree_config)}")
 IO.puts("- Merkle Tree: #{merkle_tree_json["public"]}")
 IO.puts("- New Owner: #{new_owner_json["public"]}")
 
 case MplBubblegum.transfer(params) do
   {:ok, transaction} ->
-    IO.puts("Transaction created (size: #{byte_size(transaction)} bytes). Signing and submitting...")
-    case MplBubblegum.sign_and_submit_transaction(transaction, payer_secret) do
+    transaction_binary = :binary.list_to_bin(transaction)
+    IO.puts("Transaction created (size: #{byte_size(transaction_binary)} bytes). Signing and submitting...")
+    case MplBubblegum.sign_and_submit_transaction(transaction_binary, [payer_secret]) do
       {:ok, signature} ->
         IO.puts("Transaction submitted with signature: #{signature}")
         check_status(signature)
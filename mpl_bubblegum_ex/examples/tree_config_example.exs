@@ -13,18 +13,16 @@ payer_secret = payer_json["secret"]
 tree_creator_secret = tree_creator_json["secret"]
 {:ok, tree_creator} = Pubkey.from_base58(tree_creator_json["public"])
 
-# Load tree config keypair
-{:ok, tree_config_json} = KeypairLoader.load_keypair("tree_config.json")
-tree_config_secret = tree_config_json["secret"]
-{:ok, tree_config} = Pubkey.from_base58(tree_config_json["public"])
-
 # Load merkle tree keypair
 {:ok, merkle_tree_json} = KeypairLoader.load_keypair("merkle_tree.json")
 merkle_tree_secret = merkle_tree_json["secret"]
 {:ok, merkle_tree} = Pubkey.from_base58(merkle_tree_json["public"])
 
+# The tree config is a PDA of the merkle tree, allocated by bubblegum
+{:ok, tree_config, _bump} = MplBubblegum.derive_tree_config_pda(merkle_tree)
+
 # Fund accounts (assuming local test validator)
-for account <- [payer_json["public"], tree_creator_json["public"], tree_config_json["public"], merkle_tree_json["public"]] do
+for account <- [payer_json["public"], tree_creator_json["public"]] do
   {output, status} = System.cmd("solana", ["airdrop", "10", account, "--url", "http://127.0.0.1:8899"])
   if status != 0, do: IO.puts("Airdrop failed for #{account}: #{output}")
   Process.sleep(1000) # Wait between airdrops to avoid rate limiting
@@ -44,14 +42,14 @@ params = %{
 IO.puts("Creating tree config with pubkeys:")
 IO.puts("- Payer: #{payer_json["public"]}")
 IO.puts("- Tree Creator: #{tree_creator_json["public"]}")
-IO.puts("- Tree Config: #{tree_config_json["public"]}")
+IO.puts("- Tree Config: #{Pubkey.to_base58(tree_config)}")
 IO.puts("- Merkle Tree: #{merkle_tree_json["public"]}")
 
 case MplBubblegum.create_tree_config(params) do
   {:ok, transaction} ->
     transaction_binary = :binary.list_to_bin(transaction)
     IO.puts("Transaction created (size: #{byte_size(transaction_binary)} bytes). Signing and submitting...")
-    case MplBubblegum.sign_and_submit_transaction(transaction_binary, [payer_secret, tree_creator_secret, tree_config_secret, merkle_tree_secret]) do
+    case MplBubblegum.sign_and_submit_transaction(transaction_binary, [payer_secret, tree_creator_secret, merkle_tree_secret]) do
       {:ok, signature} ->
         IO.puts("Transaction submitted with signature: #{signature}")
         Process.sleep(2000)
@@ -4,54 +4,134 @@ defmodule MplBubblegum do
   
   This module provides functions to interact with the Solana blockchain
   for creating and managing compressed NFTs using the Metaplex Bubblegum program.
+
+  ## Errors
+
+  Errors raised by the native code are returned as `{:error, {kind, message}}`,
+  where `kind` is one of `:invalid_parameter`, `:conversion`, `:deserialization`,
+  `:rpc`, `:das`, `:bubblegum`, `:borsh` or `:solana_program`. Parameter validation done in
+  Elixir returns `{:error, message}`. Arguments the native code cannot decode at all, such as
+  a pubkey that is not 32 bytes, raise an `ErlangError` whose reason is the error message.
   """
 
   alias MplBubblegum.Native
   alias MplBubblegum.Types.Pubkey
+  alias MplBubblegum.Types.AccountInfo
+  alias MplBubblegum.Types.AssetProof
   alias MplBubblegum.Types.Hash
+  alias MplBubblegum.Types.TransactionOptions
 
   @doc """
   Creates a new compressed NFT tree configuration.
 
+  The transaction creates the merkle tree account, which signs as a new account.
+  The tree config account is allocated by bubblegum itself, so it is not a signer.
+
   ## Parameters
 
-  * `tree_config` - The public key for the tree configuration account
+  * `tree_config` - The tree config PDA of the merkle tree, as returned by `derive_tree_config_pda/1`
   * `merkle_tree` - The public key for the merkle tree account
-  * `payer` - The public key of the payer
-  * `tree_creator` - The public key of the tree creator
+  * `payer` - The public key of the payer, which funds the new accounts
+  * `tree_creator` - The public key of the tree creator, recorded as the tree's authority;
+    it signs alongside the payer when the two differ
   * `max_depth` - The maximum depth of the merkle tree
   * `max_buffer_size` - The maximum buffer size of the merkle tree
   * `public` - Whether the tree is public or not (optional)
+  * `extra_accounts` - Additional `{pubkey, is_signer, is_writable}` accounts appended to the instruction (optional)
+  * `compute_unit_limit` - Compute unit limit for the transaction (optional)
+  * `compute_unit_price` - Priority fee in micro-lamports per compute unit (optional)
+  * `signers` - All pubkeys that will eventually sign the transaction (optional)
+  * `bubblegum_program_id`, `compression_program_id`, `noop_program_id` - Program ID overrides for forked deployments (optional)
+  * `verify_funding` - An RPC endpoint URL; when given, the payer's balance is checked against
+    the rent of the new accounts plus the transaction fee before the transaction is returned (optional)
 
   ## Returns
 
   * `{:ok, transaction}` - The serialized transaction
+  * `{:error, {:invalid_parameter, "payer balance N < required M"}}` - If `verify_funding` is
+    given and the payer cannot fund the new accounts and the fee
   * `{:error, reason}` - If an error occurs
   """
-  def create_tree_config(%{
-        tree_config: tree_config,
-        merkle_tree: merkle_tree,
-        payer: payer,
-        tree_creator: tree_creator,
-        max_depth: max_depth,
-        max_buffer_size: max_buffer_size,
-        public: public
-      }) do
-    # Call the Rust NIF function
-    Native.create_tree_config(
+  def create_tree_config(%{verify_funding: rpc_url} = params) when is_binary(rpc_url) do
+    with {:ok, transaction} <- tree_config_transaction(&Native.create_tree_config/9, params),
+         {:ok, _required} <- check_tree_funding(rpc_url, params, transaction) do
+      {:ok, transaction}
+    end
+  end
+
+  def create_tree_config(params), do: tree_config_transaction(&Native.create_tree_config/9, params)
+
+  @doc """
+  Checks that a payer can fund the tree config and merkle tree accounts that
+  `create_tree_config/1` creates and pay the fee of its transaction.
+
+  The fee is the signature fee of 5000 lamports per required signature plus the
+  priority fee of `compute_unit_price` over the transaction's compute unit limit.
+
+  ## Parameters
+
+  * `rpc_url` - The RPC endpoint URL
+  * `params` - The parameters of `create_tree_config/1`
+
+  ## Returns
+
+  * `{:ok, lamports}` - The rent and fee the payer needs
+  * `{:error, {:invalid_parameter, "payer balance N < required M"}}` - If the payer cannot afford it
+  * `{:error, reason}` - If an error occurs
+  """
+  def check_tree_funding(rpc_url, params) when is_binary(rpc_url) do
+    with {:ok, transaction} <- tree_config_transaction(&Native.create_tree_config/9, params) do
+      check_tree_funding(rpc_url, params, transaction)
+    end
+  end
+
+  defp check_tree_funding(rpc_url, %{payer: payer, max_depth: max_depth, max_buffer_size: max_buffer_size}, transaction) do
+    Native.check_tree_funding(rpc_url, payer, to_binary(transaction), max_depth, max_buffer_size)
+  end
+
+  @doc """
+  Creates a transaction with only the tree configuration instruction, leaving out
+  the creation of the merkle tree account.
+
+  Use this when the accounts are created and funded separately, e.g. by a tool
+  that grinds vanity addresses. Takes the same parameters as `create_tree_config/1`.
+
+  ## Returns
+
+  * `{:ok, transaction}` - The serialized transaction
+  * `{:error, reason}` - If an error occurs
+  """
+  def create_tree_config_instruction_only(params),
+    do: tree_config_transaction(&Native.create_tree_config_instruction_only/9, params)
+
+  defp tree_config_transaction(nif, %{
+         tree_config: tree_config,
+         merkle_tree: merkle_tree,
+         payer: payer,
+         tree_creator: tree_creator,
+         max_depth: max_depth,
+         max_buffer_size: max_buffer_size,
+         public: public
+       } = params) do
+    nif.(
       tree_config,
       merkle_tree,
       payer,
       tree_creator,
       max_depth,
       max_buffer_size,
-      public
+      public,
+      Map.get(params, :extra_accounts),
+      TransactionOptions.from_params(params)
     )
   end
 
   @doc """
   Mints a new compressed NFT.
 
+  A collection in the metadata must be unverified; use `mint_to_collection_v1/1`
+  to mint into a verified collection.
+
   ## Parameters
 
   * `tree_config` - The public key for the tree configuration account
@@ -61,10 +141,25 @@ defmodule MplBubblegum do
   * `payer` - The public key of the payer
   * `tree_creator_or_delegate` - The public key of the tree creator or delegate
   * `metadata` - The metadata for the NFT
+  * `nonce` - The leaf nonce the mint will receive, i.e. the tree's `num_minted`. It must still
+    match the tree's state when the transaction lands, otherwise the returned asset ID is wrong
+  * `extra_accounts` - Additional `{pubkey, is_signer, is_writable}` accounts appended to the instruction (optional)
+  * `extra_instructions` - Bincode-serialized instructions run after the mint in the same
+    transaction, e.g. a mint fee transfer or a marketplace listing (optional)
+  * `compute_unit_limit` - Compute unit limit for the transaction (optional)
+  * `compute_unit_price` - Priority fee in micro-lamports per compute unit (optional)
+  * `signers` - All pubkeys that will eventually sign the transaction (optional)
+  * `bubblegum_program_id`, `compression_program_id`, `noop_program_id` - Program ID overrides for forked deployments (optional)
+  * `memo` - A memo recorded ahead of the instruction, e.g. an order ID (optional)
+  * `nonce_account`, `nonce_authority` - A durable nonce account to advance first, so the
+    transaction can be signed offline; sign it with `sign_transaction/3` against
+    `get_nonce_blockhash/2`. The authority defaults to the fee payer (optional)
+  * `placeholder_fee_payer` - Leave the fee payer as the all-zero pubkey for a relayer to set;
+    the transaction must get its real fee payer before it is signed (optional, defaults to `false`)
 
   ## Returns
 
-  * `{:ok, transaction}` - The serialized transaction
+  * `{:ok, transaction, asset_id}` - The serialized transaction and the asset ID of the minted NFT
   * `{:error, reason}` - If an error occurs
   """
   def mint_v1(%{
@@ -74,8 +169,9 @@ defmodule MplBubblegum do
         merkle_tree: merkle_tree,
         payer: payer,
         tree_creator_or_delegate: tree_creator_or_delegate,
-        metadata: metadata
-      }) do
+        metadata: metadata,
+        nonce: nonce
+      } = params) do
     # Call the Rust NIF function
     Native.mint_v1(
       tree_config,
@@ -84,7 +180,105 @@ defmodule MplBubblegum do
       merkle_tree,
       payer,
       tree_creator_or_delegate,
-      metadata
+      metadata,
+      nonce,
+      Map.get(params, :extra_accounts),
+      Map.get(params, :extra_instructions),
+      TransactionOptions.from_params(params)
+    )
+  end
+
+  @doc """
+  Builds a single transaction that mints several compressed NFTs.
+
+  Batching saves fees and confirmation time, but every mint must fit in one
+  1232-byte transaction; in practice that is only a handful of mints.
+
+  ## Parameters
+
+  * `payer` - The public key paying for all mints and the transaction fee
+  * `mints` - A list of `MintRequest` structs with the accounts and metadata of each mint
+  * `compute_unit_limit` - Compute unit limit for the transaction (optional)
+  * `compute_unit_price` - Priority fee in micro-lamports per compute unit (optional)
+  * `signers` - All pubkeys that will eventually sign the transaction (optional)
+  * `bubblegum_program_id`, `compression_program_id`, `noop_program_id` - Program ID overrides for forked deployments (optional)
+  * `memo` - A memo recorded ahead of the instruction, e.g. an order ID (optional)
+  * `nonce_account`, `nonce_authority` - A durable nonce account to advance first, so the
+    transaction can be signed offline; sign it with `sign_transaction/3` against
+    `get_nonce_blockhash/2`. The authority defaults to the fee payer (optional)
+
+  ## Returns
+
+  * `{:ok, transaction}` - The serialized transaction
+  * `{:error, reason}` - If an error occurs or the batch exceeds the transaction size limit
+  """
+  def build_batch_mint(%{payer: payer, mints: mints} = params) when is_list(mints) do
+    Native.build_batch_mint(payer, mints, TransactionOptions.from_params(params))
+  end
+
+  @doc """
+  Mints a new compressed NFT into a verified collection.
+
+  The metadata `collection` must be set to the collection mint; the instruction
+  marks it as verified on-chain.
+
+  ## Parameters
+
+  * `tree_config` - The public key for the tree configuration account
+  * `leaf_owner` - The public key of the leaf owner
+  * `leaf_delegate` - The public key of the leaf delegate
+  * `merkle_tree` - The public key for the merkle tree account
+  * `payer` - The public key of the payer
+  * `tree_creator_or_delegate` - The public key of the tree creator or delegate
+  * `collection_authority` - The public key of the collection update authority
+  * `collection_authority_record_pda` - The collection authority record, if delegated (optional)
+  * `collection_mint` - The mint of the collection NFT
+  * `collection_metadata` - The metadata account of the collection NFT
+  * `collection_edition` - The master edition account of the collection NFT
+  * `metadata` - The metadata for the NFT
+  * `extra_accounts` - Additional `{pubkey, is_signer, is_writable}` accounts appended to the instruction (optional)
+  * `compute_unit_limit` - Compute unit limit for the transaction (optional)
+  * `compute_unit_price` - Priority fee in micro-lamports per compute unit (optional)
+  * `signers` - All pubkeys that will eventually sign the transaction (optional)
+  * `bubblegum_program_id`, `compression_program_id`, `noop_program_id` - Program ID overrides for forked deployments (optional)
+  * `memo` - A memo recorded ahead of the instruction, e.g. an order ID (optional)
+  * `nonce_account`, `nonce_authority` - A durable nonce account to advance first, so the
+    transaction can be signed offline; sign it with `sign_transaction/3` against
+    `get_nonce_blockhash/2`. The authority defaults to the fee payer (optional)
+
+  ## Returns
+
+  * `{:ok, transaction}` - The serialized transaction
+  * `{:error, reason}` - If an error occurs
+  """
+  def mint_to_collection_v1(%{
+        tree_config: tree_config,
+        leaf_owner: leaf_owner,
+        leaf_delegate: leaf_delegate,
+        merkle_tree: merkle_tree,
+        payer: payer,
+        tree_creator_or_delegate: tree_creator_or_delegate,
+        collection_authority: collection_authority,
+        collection_mint: collection_mint,
+        collection_metadata: collection_metadata,
+        collection_edition: collection_edition,
+        metadata: metadata
+      } = params) do
+    Native.mint_to_collection_v1(
+      tree_config,
+      leaf_owner,
+      leaf_delegate,
+      merkle_tree,
+      payer,
+      tree_creator_or_delegate,
+      collection_authority,
+      Map.get(params, :collection_authority_record_pda),
+      collection_mint,
+      collection_metadata,
+      collection_edition,
+      metadata,
+      Map.get(params, :extra_accounts),
+      TransactionOptions.from_params(params)
     )
   end
 
@@ -98,187 +292,2095 @@ defmodule MplBubblegum do
   * `leaf_delegate` - The public key of the leaf delegate
   * `new_leaf_owner` - The public key of the new leaf owner
   * `merkle_tree` - The public key for the merkle tree account
+  * `payer` - The public key of the fee payer
   * `root` - The root hash of the merkle tree
   * `data_hash` - The data hash of the leaf
   * `creator_hash` - The creator hash of the leaf
   * `nonce` - The nonce of the leaf
   * `index` - The index of the leaf
+  * `leaf_owner_is_signer` - Whether the leaf owner signs the transfer (optional, defaults to `true`)
+  * `leaf_delegate_is_signer` - Whether the leaf delegate signs the transfer (optional, defaults to `false`)
+  * `proof` - The 32-byte proof nodes appended as remaining accounts, as binaries or the base58
+    strings returned by `get_asset_proof/2` (optional)
+  * `canopy_depth` - The tree's canopy depth; the top proof nodes it covers are left out (optional, defaults to 0)
+  * `extra_accounts` - Additional `{pubkey, is_signer, is_writable}` accounts appended to the instruction (optional)
+  * `max_depth` - The tree's max depth; when given, `index` must be below `2^max_depth` (optional)
+  * `compute_unit_limit` - Compute unit limit for the transaction (optional)
+  * `compute_unit_price` - Priority fee in micro-lamports per compute unit (optional)
+  * `signers` - All pubkeys that will eventually sign the transaction (optional)
+  * `bubblegum_program_id`, `compression_program_id`, `noop_program_id` - Program ID overrides for forked deployments (optional)
+  * `memo` - A memo recorded ahead of the instruction, e.g. an order ID (optional)
+  * `nonce_account`, `nonce_authority` - A durable nonce account to advance first, so the
+    transaction can be signed offline; sign it with `sign_transaction/3` against
+    `get_nonce_blockhash/2`. The authority defaults to the fee payer (optional)
+  * `placeholder_fee_payer` - Leave the fee payer as the all-zero pubkey for a relayer to set;
+    the transaction must get its real fee payer before it is signed (optional, defaults to `false`)
+  * `verify_owner` - A DAS endpoint URL; when given, the asset's current owner is fetched with
+    `getAsset` and must be `leaf_owner` (optional)
+  * `asset_id` - The asset checked by `verify_owner` (optional, defaults to the asset ID of
+    `merkle_tree` and `nonce`)
 
   ## Returns
 
   * `{:ok, transaction}` - The serialized transaction
+  * `{:error, {:invalid_parameter, reason}}` - If `verify_owner` is given and `leaf_owner` no longer
+    owns the asset
   * `{:error, reason}` - If an error occurs
   """
 
+  def transfer(%{verify_owner: das_url} = params) when is_binary(das_url) do
+    with :ok <- check_leaf_owner(das_url, params) do
+      params |> Map.delete(:verify_owner) |> transfer()
+    end
+  end
+
   def transfer(params) do
-  with {:ok, tree_config} <- get_pubkey(params, :tree_config),
-       {:ok, leaf_owner} <- get_pubkey(params, :leaf_owner),
-       {:ok, leaf_delegate} <- get_pubkey(params, :leaf_delegate),
-       {:ok, new_leaf_owner} <- get_pubkey(params, :new_leaf_owner),
-       {:ok, merkle_tree} <- get_pubkey(params, :merkle_tree),
-       {:ok, root} <- get_hash(params, :root),
-       {:ok, data_hash} <- get_hash(params, :data_hash),
-       {:ok, creator_hash} <- get_hash(params, :creator_hash),
-       {:ok, nonce} <- get_integer(params, :nonce),
-       {:ok, index} <- get_integer(params, :index) do
-    try do
-      Native.transfer(
-        tree_config,
+    with {:ok, args} <- transfer_args(params) do
+      # The max depth goes right before the transaction options
+      args = List.insert_at(args, -2, Map.get(params, :max_depth))
+
+      try do
+        apply(Native, :transfer, args)
+      rescue
+        ArgumentError -> {:error, "Native transfer failed"}
+      end
+    end
+  end
+
+  @doc """
+  Builds a transfer that several parties sign, e.g. a delegate authorizing an
+  escrow release while a relayer pays the fee.
+
+  The leaf owner and leaf delegate sign if they are in `signers`, and the message
+  requires a signature from every signer, so each party can add theirs with
+  `sign_transaction/3`.
+
+  ## Parameters
+
+  * `params` - The same parameters as `transfer/1`, except that `signers` is required
+    and replaces `leaf_owner_is_signer` and `leaf_delegate_is_signer`
+
+  ## Returns
+
+  * `{:ok, transaction}` - The serialized, unsigned transaction
+  * `{:error, reason}` - If an error occurs
+  """
+  def transfer_with_signers(%{signers: signers} = params) when is_list(signers) do
+    with {:ok, args} <- transfer_args(params) do
+      # Swap the leaf_owner_is_signer and leaf_delegate_is_signer flags for the signers
+      {head, [_, _ | tail]} = Enum.split(args, 11)
+      apply(Native, :transfer_with_signers, head ++ [signers | tail])
+    end
+  end
+
+  @doc """
+  Builds an owner-signed transfer from the proof returned by `get_asset_proof/2`.
+
+  The merkle tree, tree config, root, leaf index and nonce all come from the
+  proof, whose base58 nodes are decoded and cut down to `canopy_depth`. The
+  proof's leaf must match the given owner, delegate and hashes, so a transfer
+  built from stale DAS data fails here rather than on-chain.
+
+  ## Parameters
+
+  * `asset_proof` - The `MplBubblegum.Types.AssetProof` returned by `get_asset_proof/2`
+  * `params` - A map with:
+    * `leaf_owner` - The public key of the leaf owner, who signs the transfer
+    * `leaf_delegate` - The public key of the leaf delegate
+    * `new_leaf_owner` - The public key of the new leaf owner
+    * `payer` - The public key of the fee payer
+    * `data_hash`, `creator_hash` - The hashes of the leaf, as 32-byte binaries or the
+      base58 strings in the `compression` field of `get_asset/2`
+    * `canopy_depth` - The tree's canopy depth (optional, defaults to 0)
+    * The transaction options accepted by `transfer/1` (optional)
+
+  ## Returns
+
+  * `{:ok, transaction}` - The serialized transaction
+  * `{:error, reason}` - If an error occurs
+  """
+  def build_transfer_from_proof(%AssetProof{} = asset_proof, params) do
+    with {:ok, leaf_owner} <- get_pubkey(params, :leaf_owner),
+         {:ok, leaf_delegate} <- get_pubkey(params, :leaf_delegate),
+         {:ok, new_leaf_owner} <- get_pubkey(params, :new_leaf_owner),
+         {:ok, payer} <- get_pubkey(params, :payer),
+         {:ok, data_hash} <- get_leaf_hash(params, :data_hash),
+         {:ok, creator_hash} <- get_leaf_hash(params, :creator_hash) do
+      Native.build_transfer_from_proof(
+        asset_proof,
         leaf_owner,
         leaf_delegate,
         new_leaf_owner,
-        merkle_tree,
-        root.bytes,
-        data_hash.bytes,
-        creator_hash.bytes,
-        nonce,
-        index
+        payer,
+        data_hash,
+        creator_hash,
+        Map.get(params, :canopy_depth),
+        TransactionOptions.from_params(params)
       )
-    rescue
-      ArgumentError -> {:error, "Native transfer failed"}
-    end
-  else
-    {:error, reason} -> {:error, reason}
     end
   end
 
   @doc """
-  Signs and submits a transaction to the Solana network.
+  Delegates a compressed NFT and transfers it in a single transaction, e.g. to
+  settle a marketplace sale atomically by making the buyer the delegate and
+  the new owner at once.
+
+  The delegation changes the leaf, so the transfer is proven against the root
+  that follows from it rather than the one in `asset_proof`. That root is
+  recomputed from the full proof, which must hash the proof's leaf up to its
+  root. The leaf owner signs and pays for the transaction.
 
   ## Parameters
 
-  * `transaction` - The serialized transaction binary (returned from create_tree_config, mint_v1, or transfer)
-  * `secret_keys` - A list of secret keys (binary or base64-encoded strings) for all required signers
+  * `asset_proof` - The `MplBubblegum.Types.AssetProof` returned by `get_asset_proof/2`
+  * `params` - A map with:
+    * `leaf_owner` - The public key of the leaf owner
+    * `previous_leaf_delegate` - The public key of the current leaf delegate
+    * `new_leaf_delegate` - The public key of the new leaf delegate
+    * `new_leaf_owner` - The public key of the new leaf owner
+    * `data_hash`, `creator_hash` - The hashes of the leaf, as 32-byte binaries or the
+      base58 strings in the `compression` field of `get_asset/2`
+    * `canopy_depth` - The tree's canopy depth (optional, defaults to 0)
+    * The transaction options accepted by `transfer/1` (optional)
 
   ## Returns
 
-  * `{:ok, signature}` - The transaction signature if successful
+  * `{:ok, transaction}` - The serialized, unsigned transaction
   * `{:error, reason}` - If an error occurs
   """
-  def sign_and_submit_transaction(transaction, secret_keys) when is_binary(transaction) and is_list(secret_keys) do
-    with {:ok, secret_key_binaries} <- normalize_secret_keys(secret_keys) do
-      Native.sign_and_submit_transaction(transaction, secret_key_binaries)
+  def delegate_and_transfer(%AssetProof{} = asset_proof, params) do
+    with {:ok, leaf_owner} <- get_pubkey(params, :leaf_owner),
+         {:ok, previous_leaf_delegate} <- get_pubkey(params, :previous_leaf_delegate),
+         {:ok, new_leaf_delegate} <- get_pubkey(params, :new_leaf_delegate),
+         {:ok, new_leaf_owner} <- get_pubkey(params, :new_leaf_owner),
+         {:ok, data_hash} <- get_leaf_hash(params, :data_hash),
+         {:ok, creator_hash} <- get_leaf_hash(params, :creator_hash) do
+      Native.delegate_and_transfer(
+        asset_proof,
+        leaf_owner,
+        previous_leaf_delegate,
+        new_leaf_delegate,
+        new_leaf_owner,
+        data_hash,
+        creator_hash,
+        Map.get(params, :canopy_depth),
+        TransactionOptions.from_params(params)
+      )
+    end
+  end
+
+  # Leaf hashes from the DAS API are base58 strings rather than raw 32-byte binaries
+  defp get_leaf_hash(params, key) do
+    case Map.get(params, key) do
+      value when is_binary(value) and byte_size(value) != 32 ->
+        {:ok, %Hash{bytes: Base58.decode(value)}}
+
+      _ ->
+        get_hash(params, key)
     end
   end
 
   @doc """
-  Gets the status of a transaction on the Solana network.
+  Builds a versioned (v0) transfer transaction that resolves accounts through
+  an address lookup table.
+
+  Legacy transactions cannot fit the proof of a deep tree; putting the proof
+  nodes in a lookup table keeps each of them to a single byte in the message.
+  The lookup table is fetched from `rpc_url`. The result can be signed and
+  sent like a legacy transaction, e.g. with `sign_and_submit_transaction/3`.
 
   ## Parameters
 
-  * `signature` - The transaction signature (string)
+  * `rpc_url` - The RPC endpoint URL used to fetch the lookup table
+  * `params` - The same parameters as `transfer/1`, plus:
+    * `lookup_table` - The address lookup table holding the proof nodes (optional)
 
   ## Returns
 
-  * `{:ok, status}` - The status ("confirmed", "failed: <reason>", or "not_found")
+  * `{:ok, transaction}` - The serialized, unsigned versioned transaction
   * `{:error, reason}` - If an error occurs
   """
-  def get_transaction_status(signature) when is_binary(signature) do
-    Native.get_transaction_status(signature)
+  def build_transfer_v0(rpc_url, params) when is_binary(rpc_url) do
+    with {:ok, args} <- transfer_args(params) do
+      apply(Native, :build_transfer_v0, [rpc_url | args] ++ [Map.get(params, :lookup_table)])
+    end
   end
 
-  # Update helper function to handle a list of secret keys
-  defp normalize_secret_keys(secret_keys) do
-    Enum.reduce_while(secret_keys, {:ok, []}, fn key, {:ok, acc} ->
-      case normalize_secret_key(key) do
-        {:ok, binary} -> {:cont, {:ok, [binary | acc]}}
-        {:error, reason} -> {:halt, {:error, reason}}
-      end
-    end)
-    |> case do
-         {:ok, list} -> {:ok, Enum.reverse(list)}
-         {:error, reason} -> {:error, reason}
-       end
+  defp check_leaf_owner(das_url, params) do
+    with {:ok, leaf_owner} <- get_pubkey(params, :leaf_owner),
+         {:ok, asset_id} <- transfer_asset_id(params) do
+      Native.check_asset_owner(das_url, asset_id, leaf_owner)
+    end
   end
 
-   @doc """
-  Gets account information from the Solana network.
+  defp transfer_asset_id(%{asset_id: asset_id} = params) when not is_nil(asset_id),
+    do: get_pubkey(params, :asset_id)
+
+  defp transfer_asset_id(params) do
+    with {:ok, merkle_tree} <- get_pubkey(params, :merkle_tree),
+         {:ok, nonce} <- get_integer(params, :nonce) do
+      get_asset_id(merkle_tree, nonce)
+    end
+  end
+
+  defp transfer_args(params) do
+    with {:ok, tree_config} <- get_pubkey(params, :tree_config),
+         {:ok, leaf_owner} <- get_pubkey(params, :leaf_owner),
+         {:ok, leaf_delegate} <- get_pubkey(params, :leaf_delegate),
+         {:ok, new_leaf_owner} <- get_pubkey(params, :new_leaf_owner),
+         {:ok, merkle_tree} <- get_pubkey(params, :merkle_tree),
+         {:ok, payer} <- get_pubkey(params, :payer),
+         {:ok, root} <- get_hash(params, :root),
+         {:ok, data_hash} <- get_hash(params, :data_hash),
+         {:ok, creator_hash} <- get_hash(params, :creator_hash),
+         {:ok, nonce} <- get_integer(params, :nonce),
+         {:ok, index} <- get_integer(params, :index) do
+      {:ok,
+       [
+         tree_config,
+         leaf_owner,
+         leaf_delegate,
+         new_leaf_owner,
+         merkle_tree,
+         payer,
+         byte_list(root.bytes),
+         byte_list(data_hash.bytes),
+         byte_list(creator_hash.bytes),
+         nonce,
+         index,
+         Map.get(params, :leaf_owner_is_signer, true),
+         Map.get(params, :leaf_delegate_is_signer, false),
+         params |> Map.get(:proof, []) |> Enum.map(&proof_node/1),
+         Map.get(params, :canopy_depth),
+         Map.get(params, :extra_accounts),
+         TransactionOptions.from_params(params)
+       ]}
+    end
+  end
+
+  @doc """
+  Loads a keypair from the Solana CLI JSON format and returns its public key.
 
   ## Parameters
 
-  * `pubkey` - The public key of the account (Pubkey struct or base58 string)
+  * `json` - A JSON array of 64 integers, as written to disk by `solana-keygen`
 
   ## Returns
 
-  * `{:ok, account_info}` - A map containing account details (lamports, owner, executable, rent_epoch, data_len)
-  * `{:error, reason}` - If an error occurs
+  * `{:ok, pubkey}` - The public key of the keypair
+  * `{:error, reason}` - If the JSON is malformed or not exactly 64 bytes
   """
-  def get_account_info(pubkey) do
-    with {:ok, pubkey_struct} <- normalize_pubkey(pubkey),
-         {:ok, account_map} <- Native.get_account_info(pubkey_struct),
-         {:ok, account_info} <- MplBubblegum.Types.AccountInfo.from_map(account_map) do
-      {:ok, account_info}
-    else
-      {:error, reason} -> {:error, reason}
+  def keypair_from_json(json) when is_binary(json) do
+    Native.keypair_from_json(json)
+  end
+
+  @doc """
+  Loads a keypair from a base58-encoded secret key, the format wallets such as
+  Phantom export private keys in.
+
+  ## Parameters
+
+  * `secret_base58` - The base58 encoding of the 64-byte secret key
+
+  ## Returns
+
+  * `{:ok, pubkey, secret_key}` - The public key and the 64-byte secret key binary,
+    ready for `sign_transaction/3` and `sign_and_submit_transaction/3`
+  * `{:error, reason}` - If the string is not valid base58 or not 64 bytes
+  """
+  def keypair_from_base58(secret_base58) when is_binary(secret_base58) do
+    with {:ok, pubkey, secret_key} <- Native.keypair_from_base58(secret_base58) do
+      {:ok, pubkey, to_binary(secret_key)}
     end
   end
 
-  # Helper functions for parameter validation
+  @doc """
+  Signs an arbitrary message, e.g. to prove wallet ownership off-chain.
 
-  defp normalize_secret_key(secret_key) when is_binary(secret_key) do
-    case byte_size(secret_key) do
-      64 -> {:ok, secret_key}  # Already in binary format
-      _ ->
-        case Base.decode64(secret_key) do
-          {:ok, binary} when byte_size(binary) == 64 -> {:ok, binary}
-          _ -> {:error, "Invalid secret key format; must be 64 bytes or base64-encoded 64-byte string"}
-        end
+  ## Parameters
+
+  * `secret_key` - The secret key (binary, base64-encoded string, or Solana CLI JSON array)
+  * `message` - The message bytes (binary or list of bytes)
+
+  ## Returns
+
+  * `{:ok, signature}` - The 64-byte ed25519 signature as a binary
+  * `{:error, reason}` - If the secret key is invalid
+  """
+  def sign_message(secret_key, message) do
+    with {:ok, secret_key_binary} <- normalize_secret_key(secret_key),
+         {:ok, signature} <- Native.sign_message(secret_key_binary, to_binary(message)) do
+      {:ok, to_binary(signature)}
     end
   end
 
-  defp normalize_pubkey(%Pubkey{} = pubkey), do: {:ok, pubkey}
-  defp normalize_pubkey(pubkey) when is_binary(pubkey) do
-    Pubkey.from_base58(pubkey)
+  @doc """
+  Verifies an ed25519 signature over a message.
+
+  ## Parameters
+
+  * `pubkey` - The signer's public key (Pubkey struct or base58 string)
+  * `message` - The signed message bytes (binary or list of bytes)
+  * `signature` - The 64-byte signature (binary or list of bytes)
+
+  ## Returns
+
+  * `{:ok, valid}` - Whether the signature is valid for the message and public key
+  * `{:error, reason}` - If the public key or signature is malformed
+  """
+  def verify_signature(pubkey, message, signature) do
+    with {:ok, pubkey_struct} <- normalize_pubkey(pubkey) do
+      Native.verify_signature(pubkey_struct, to_binary(message), to_binary(signature))
+    end
   end
-  defp normalize_pubkey(_), do: {:error, "Invalid public key format"}
 
   @doc """
-  Hashes the metadata of an NFT.
+  Checks whether a public key is a point on the ed25519 curve. Wallet addresses
+  are on the curve, while PDAs are off it and have no private key to sign with.
 
   ## Parameters
 
-  * `metadata` - The metadata to hash
+  * `pubkey` - The public key to check (Pubkey struct or base58 string)
 
   ## Returns
 
-  * `{:ok, hash}` - The hash of the metadata
-  * `{:error, reason}` - If an error occurs
+  * `{:ok, on_curve}` - Whether the public key is on the curve
+  * `{:error, reason}` - If the public key is malformed
   """
-  def hash_metadata(metadata) do
-    Native.hash_metadata(metadata)
+  def pubkey_is_on_curve(pubkey) do
+    with {:ok, pubkey_struct} <- normalize_pubkey(pubkey) do
+      Native.pubkey_is_on_curve(pubkey_struct)
+    end
   end
 
   @doc """
-  Hashes the creators of an NFT.
+  Sets a new delegate on a compressed NFT.
+
+  The leaf owner signs and pays for the transaction.
 
   ## Parameters
 
-  * `creators` - The creators to hash
+  * `tree_config` - The public key for the tree configuration account
+  * `leaf_owner` - The public key of the leaf owner
+  * `previous_leaf_delegate` - The public key of the current leaf delegate
+  * `new_leaf_delegate` - The public key of the new leaf delegate
+  * `merkle_tree` - The public key for the merkle tree account
+  * `root` - The root hash of the merkle tree
+  * `data_hash` - The data hash of the leaf
+  * `creator_hash` - The creator hash of the leaf
+  * `nonce` - The nonce of the leaf
+  * `index` - The index of the leaf
+  * `proof` - The 32-byte proof nodes appended as remaining accounts, as binaries or the base58
+    strings returned by `get_asset_proof/2` (optional)
+  * `canopy_depth` - The tree's canopy depth; the top proof nodes it covers are left out (optional, defaults to 0)
 
   ## Returns
 
-  * `{:ok, hash}` - The hash of the creators
+  * `{:ok, transaction}` - The serialized transaction
   * `{:error, reason}` - If an error occurs
   """
-  def hash_creators(creators) do
-    Native.hash_creators(creators)
+  def delegate(%{
+        tree_config: tree_config,
+        leaf_owner: leaf_owner,
+        previous_leaf_delegate: previous_leaf_delegate,
+        new_leaf_delegate: new_leaf_delegate,
+        merkle_tree: merkle_tree,
+        root: root,
+        data_hash: data_hash,
+        creator_hash: creator_hash,
+        nonce: nonce,
+        index: index
+      } = params) do
+    Native.delegate(
+      tree_config,
+      leaf_owner,
+      previous_leaf_delegate,
+      new_leaf_delegate,
+      merkle_tree,
+      byte_list(root),
+      byte_list(data_hash),
+      byte_list(creator_hash),
+      nonce,
+      index,
+      params |> Map.get(:proof, []) |> Enum.map(&proof_node/1),
+      Map.get(params, :canopy_depth),
+      TransactionOptions.from_params(params)
+    )
   end
 
   @doc """
-  Gets the asset ID for a leaf.
+  Makes an escrow the delegate of a compressed NFT, so it can transfer the NFT
+  when a listing sells. The NFT must not have another delegate.
+
+  The leaf owner signs and pays for the transaction.
 
   ## Parameters
 
-  * `tree` - The public key of the merkle tree
+  * `tree_config` - The public key for the tree configuration account
+  * `leaf_owner` - The public key of the leaf owner
+  * `escrow` - The public key of the escrow, e.g. a marketplace program's PDA
+  * `merkle_tree` - The public key for the merkle tree account
+  * `root` - The root hash of the merkle tree
+  * `data_hash` - The data hash of the leaf
+  * `creator_hash` - The creator hash of the leaf
   * `nonce` - The nonce of the leaf
+  * `index` - The index of the leaf
+  * `proof` - The 32-byte proof nodes appended as remaining accounts, as binaries or the base58
+    strings returned by `get_asset_proof/2` (optional)
+  * `canopy_depth` - The tree's canopy depth; the top proof nodes it covers are left out (optional, defaults to 0)
 
   ## Returns
 
-  * `{:ok, asset_id}` - The asset ID
+  * `{:ok, transaction}` - The serialized transaction
   * `{:error, reason}` - If an error occurs
   """
-  def get_asset_id(tree, nonce) do
-    Native.get_asset_id(tree, nonce)
+  def escrow_delegate(params), do: escrow_transaction(:escrow_delegate, params)
+
+  @doc """
+  Takes the delegation back from an escrow, e.g. when a listing is cancelled,
+  leaving the leaf owner as its own delegate.
+
+  The leaf owner signs and pays for the transaction.
+
+  ## Parameters
+
+  * `tree_config` - The public key for the tree configuration account
+  * `leaf_owner` - The public key of the leaf owner
+  * `escrow` - The public key of the escrow, e.g. a marketplace program's PDA
+  * `merkle_tree` - The public key for the merkle tree account
+  * `root` - The root hash of the merkle tree
+  * `data_hash` - The data hash of the leaf
+  * `creator_hash` - The creator hash of the leaf
+  * `nonce` - The nonce of the leaf
+  * `index` - The index of the leaf
+  * `proof` - The 32-byte proof nodes appended as remaining accounts, as binaries or the base58
+    strings returned by `get_asset_proof/2` (optional)
+  * `canopy_depth` - The tree's canopy depth; the top proof nodes it covers are left out (optional, defaults to 0)
+
+  ## Returns
+
+  * `{:ok, transaction}` - The serialized transaction
+  * `{:error, reason}` - If an error occurs
+  """
+  def release_delegate(params), do: escrow_transaction(:release_delegate, params)
+
+  defp escrow_transaction(nif, %{
+         tree_config: tree_config,
+         leaf_owner: leaf_owner,
+         escrow: escrow,
+         merkle_tree: merkle_tree,
+         root: root,
+         data_hash: data_hash,
+         creator_hash: creator_hash,
+         nonce: nonce,
+         index: index
+       } = params) do
+    apply(Native, nif, [
+      tree_config,
+      leaf_owner,
+      escrow,
+      merkle_tree,
+      byte_list(root),
+      byte_list(data_hash),
+      byte_list(creator_hash),
+      nonce,
+      index,
+      params |> Map.get(:proof, []) |> Enum.map(&proof_node/1),
+      Map.get(params, :canopy_depth),
+      TransactionOptions.from_params(params)
+    ])
+  end
+
+  @doc """
+  Burns a compressed NFT.
+
+  Either the leaf owner or the leaf delegate authorizes the burn, and the payer
+  pays the fee.
+
+  ## Parameters
+
+  * `tree_config` - The public key for the tree configuration account
+  * `leaf_owner` - The public key of the leaf owner
+  * `leaf_delegate` - The public key of the leaf delegate
+  * `merkle_tree` - The public key for the merkle tree account
+  * `payer` - The public key of the fee payer
+  * `root` - The root hash of the merkle tree
+  * `data_hash` - The data hash of the leaf
+  * `creator_hash` - The creator hash of the leaf
+  * `nonce` - The nonce of the leaf
+  * `index` - The index of the leaf
+  * `leaf_owner_is_signer` - Whether the leaf owner signs the burn (optional, defaults to `true`)
+  * `leaf_delegate_is_signer` - Whether the leaf delegate signs the burn (optional, defaults to `false`)
+  * `proof` - The 32-byte proof nodes appended as remaining accounts, as binaries or the base58
+    strings returned by `get_asset_proof/2` (optional)
+  * `canopy_depth` - The tree's canopy depth; the top proof nodes it covers are left out (optional, defaults to 0)
+  * `extra_accounts` - Additional `{pubkey, is_signer, is_writable}` accounts appended to the instruction (optional)
+
+  ## Returns
+
+  * `{:ok, transaction}` - The serialized transaction
+  * `{:error, reason}` - If an error occurs
+  """
+  def burn(%{
+        tree_config: tree_config,
+        leaf_owner: leaf_owner,
+        leaf_delegate: leaf_delegate,
+        merkle_tree: merkle_tree,
+        payer: payer,
+        root: root,
+        data_hash: data_hash,
+        creator_hash: creator_hash,
+        nonce: nonce,
+        index: index
+      } = params) do
+    Native.burn(
+      tree_config,
+      leaf_owner,
+      leaf_delegate,
+      merkle_tree,
+      payer,
+      byte_list(root),
+      byte_list(data_hash),
+      byte_list(creator_hash),
+      nonce,
+      index,
+      Map.get(params, :leaf_owner_is_signer, true),
+      Map.get(params, :leaf_delegate_is_signer, false),
+      params |> Map.get(:proof, []) |> Enum.map(&proof_node/1),
+      Map.get(params, :canopy_depth),
+      Map.get(params, :extra_accounts),
+      TransactionOptions.from_params(params)
+    )
+  end
+
+  @doc """
+  Verifies the collection of an existing compressed NFT.
+
+  ## Parameters
+
+  * `tree_config` - The public key for the tree configuration account
+  * `leaf_owner` - The public key of the leaf owner
+  * `leaf_delegate` - The public key of the leaf delegate
+  * `merkle_tree` - The public key for the merkle tree account
+  * `payer` - The public key of the payer
+  * `tree_creator_or_delegate` - The public key of the tree creator or delegate
+  * `collection_authority` - The public key of the collection update authority
+  * `collection_authority_record_pda` - The collection authority record, if delegated (optional)
+  * `collection_mint` - The mint of the collection NFT
+  * `collection_metadata` - The metadata account of the collection NFT
+  * `collection_edition` - The master edition account of the collection NFT
+  * `root` - The root hash of the merkle tree
+  * `data_hash` - The data hash of the leaf
+  * `creator_hash` - The creator hash of the leaf
+  * `nonce` - The nonce of the leaf
+  * `index` - The index of the leaf
+  * `metadata` - The current metadata of the NFT
+  * `proof` - The 32-byte proof nodes appended as remaining accounts, as binaries or the base58
+    strings returned by `get_asset_proof/2` (optional)
+
+  ## Returns
+
+  * `{:ok, transaction}` - The serialized transaction
+  * `{:error, reason}` - If an error occurs
+  """
+  def verify_collection(params), do: collection_verification(&Native.verify_collection/19, params)
+
+  @doc """
+  Unverifies the collection of an existing compressed NFT.
+
+  ## Parameters
+
+  * `tree_config` - The public key for the tree configuration account
+  * `leaf_owner` - The public key of the leaf owner
+  * `leaf_delegate` - The public key of the leaf delegate
+  * `merkle_tree` - The public key for the merkle tree account
+  * `payer` - The public key of the payer
+  * `tree_creator_or_delegate` - The public key of the tree creator or delegate
+  * `collection_authority` - The public key of the collection update authority
+  * `collection_authority_record_pda` - The collection authority record, if delegated (optional)
+  * `collection_mint` - The mint of the collection NFT
+  * `collection_metadata` - The metadata account of the collection NFT
+  * `collection_edition` - The master edition account of the collection NFT
+  * `root` - The root hash of the merkle tree
+  * `data_hash` - The data hash of the leaf
+  * `creator_hash` - The creator hash of the leaf
+  * `nonce` - The nonce of the leaf
+  * `index` - The index of the leaf
+  * `metadata` - The current metadata of the NFT
+  * `proof` - The 32-byte proof nodes appended as remaining accounts, as binaries or the base58
+    strings returned by `get_asset_proof/2` (optional)
+
+  ## Returns
+
+  * `{:ok, transaction}` - The serialized transaction
+  * `{:error, reason}` - If an error occurs
+  """
+  def unverify_collection(params), do: collection_verification(&Native.unverify_collection/19, params)
+
+  @doc """
+  Sets the collection of an existing compressed NFT and verifies it in one step,
+  e.g. to fix an NFT minted into the wrong collection or none at all.
+
+  The tree creator or delegate must sign, since the leaf's metadata changes.
+
+  ## Parameters
+
+  * `tree_config` - The public key for the tree configuration account
+  * `leaf_owner` - The public key of the leaf owner
+  * `leaf_delegate` - The public key of the leaf delegate
+  * `merkle_tree` - The public key for the merkle tree account
+  * `payer` - The public key of the payer
+  * `tree_creator_or_delegate` - The public key of the tree creator or delegate
+  * `collection_authority` - The public key of the new collection's update authority
+  * `collection_authority_record_pda` - The collection authority record, if delegated (optional)
+  * `new_collection` - The mint of the collection NFT to set
+  * `collection_metadata` - The metadata account of the new collection NFT
+  * `collection_edition` - The master edition account of the new collection NFT
+  * `root` - The root hash of the merkle tree
+  * `data_hash` - The data hash of the leaf
+  * `creator_hash` - The creator hash of the leaf
+  * `nonce` - The nonce of the leaf
+  * `index` - The index of the leaf
+  * `metadata` - The current metadata of the NFT
+  * `proof` - The 32-byte proof nodes appended as remaining accounts, as binaries or the base58
+    strings returned by `get_asset_proof/2` (optional)
+
+  ## Returns
+
+  * `{:ok, transaction}` - The serialized transaction
+  * `{:error, reason}` - If an error occurs
+  """
+  def set_and_verify_collection(%{new_collection: new_collection} = params) do
+    collection_verification(
+      &Native.set_and_verify_collection/19,
+      Map.put(params, :collection_mint, new_collection)
+    )
+  end
+
+  defp collection_verification(nif, %{
+         tree_config: tree_config,
+         leaf_owner: leaf_owner,
+         leaf_delegate: leaf_delegate,
+         merkle_tree: merkle_tree,
+         payer: payer,
+         tree_creator_or_delegate: tree_creator_or_delegate,
+         collection_authority: collection_authority,
+         collection_mint: collection_mint,
+         collection_metadata: collection_metadata,
+         collection_edition: collection_edition,
+         root: root,
+         data_hash: data_hash,
+         creator_hash: creator_hash,
+         nonce: nonce,
+         index: index,
+         metadata: metadata
+       } = params) do
+    nif.(
+      tree_config,
+      leaf_owner,
+      leaf_delegate,
+      merkle_tree,
+      payer,
+      tree_creator_or_delegate,
+      collection_authority,
+      Map.get(params, :collection_authority_record_pda),
+      collection_mint,
+      collection_metadata,
+      collection_edition,
+      byte_list(root),
+      byte_list(data_hash),
+      byte_list(creator_hash),
+      nonce,
+      index,
+      metadata,
+      params |> Map.get(:proof, []) |> Enum.map(&proof_node/1),
+      TransactionOptions.from_params(params)
+    )
+  end
+
+  @doc """
+  Updates the metadata of an existing compressed NFT.
+
+  ## Parameters
+
+  * `tree_config` - The public key for the tree configuration account
+  * `authority` - The collection authority if the NFT is in a verified collection, otherwise the tree creator or delegate
+  * `collection_mint` - The mint of the verified collection (optional)
+  * `collection_metadata` - The metadata account of the verified collection (optional)
+  * `collection_authority_record_pda` - The collection authority record, if delegated (optional)
+  * `leaf_owner` - The public key of the leaf owner
+  * `leaf_delegate` - The public key of the leaf delegate
+  * `payer` - The public key of the payer
+  * `merkle_tree` - The public key for the merkle tree account
+  * `root` - The root hash of the merkle tree
+  * `nonce` - The nonce of the leaf
+  * `index` - The index of the leaf
+  * `current_metadata` - The current metadata of the NFT
+  * `update_args` - An `MplBubblegum.Types.UpdateArgs` with the fields to change
+  * `proof` - The 32-byte proof nodes appended as remaining accounts, as binaries or the base58
+    strings returned by `get_asset_proof/2` (optional)
+
+  ## Returns
+
+  * `{:ok, transaction}` - The serialized transaction
+  * `{:error, reason}` - If an error occurs
+  """
+  def update_metadata(%{
+        tree_config: tree_config,
+        authority: authority,
+        leaf_owner: leaf_owner,
+        leaf_delegate: leaf_delegate,
+        payer: payer,
+        merkle_tree: merkle_tree,
+        root: root,
+        nonce: nonce,
+        index: index,
+        current_metadata: current_metadata,
+        update_args: update_args
+      } = params) do
+    Native.update_metadata(
+      tree_config,
+      authority,
+      Map.get(params, :collection_mint),
+      Map.get(params, :collection_metadata),
+      Map.get(params, :collection_authority_record_pda),
+      leaf_owner,
+      leaf_delegate,
+      payer,
+      merkle_tree,
+      byte_list(root),
+      nonce,
+      index,
+      current_metadata,
+      update_args,
+      params |> Map.get(:proof, []) |> Enum.map(&proof_node/1),
+      TransactionOptions.from_params(params)
+    )
+  end
+
+  @doc """
+  Redeems a compressed NFT leaf into a voucher, the first step of decompression.
+
+  The leaf owner signs and pays for the transaction.
+
+  ## Parameters
+
+  * `tree_config` - The public key for the tree configuration account
+  * `leaf_owner` - The public key of the leaf owner
+  * `leaf_delegate` - The public key of the leaf delegate
+  * `merkle_tree` - The public key for the merkle tree account
+  * `voucher` - The voucher PDA for the leaf
+  * `root` - The root hash of the merkle tree
+  * `data_hash` - The data hash of the leaf
+  * `creator_hash` - The creator hash of the leaf
+  * `nonce` - The nonce of the leaf
+  * `index` - The index of the leaf
+  * `proof` - The 32-byte proof nodes appended as remaining accounts, as binaries or the base58
+    strings returned by `get_asset_proof/2` (optional)
+
+  ## Returns
+
+  * `{:ok, transaction}` - The serialized transaction
+  * `{:error, reason}` - If an error occurs
+  """
+  def redeem(%{
+        tree_config: tree_config,
+        leaf_owner: leaf_owner,
+        leaf_delegate: leaf_delegate,
+        merkle_tree: merkle_tree,
+        voucher: voucher,
+        root: root,
+        data_hash: data_hash,
+        creator_hash: creator_hash,
+        nonce: nonce,
+        index: index
+      } = params) do
+    Native.redeem(
+      tree_config,
+      leaf_owner,
+      leaf_delegate,
+      merkle_tree,
+      voucher,
+      byte_list(root),
+      byte_list(data_hash),
+      byte_list(creator_hash),
+      nonce,
+      index,
+      params |> Map.get(:proof, []) |> Enum.map(&proof_node/1),
+      TransactionOptions.from_params(params)
+    )
+  end
+
+  @doc """
+  Cancels a redeem, returning the voucher's leaf to the merkle tree.
+
+  The leaf owner signs and pays for the transaction.
+
+  ## Parameters
+
+  * `tree_config` - The public key for the tree configuration account
+  * `leaf_owner` - The public key of the leaf owner
+  * `merkle_tree` - The public key for the merkle tree account
+  * `voucher` - The voucher PDA created by `redeem/1`
+  * `root` - The root hash of the merkle tree
+  * `proof` - The 32-byte proof nodes appended as remaining accounts, as binaries or the base58
+    strings returned by `get_asset_proof/2` (optional)
+
+  ## Returns
+
+  * `{:ok, transaction}` - The serialized transaction
+  * `{:error, reason}` - If an error occurs
+  """
+  def cancel_redeem(%{
+        tree_config: tree_config,
+        leaf_owner: leaf_owner,
+        merkle_tree: merkle_tree,
+        voucher: voucher,
+        root: root
+      } = params) do
+    Native.cancel_redeem(
+      tree_config,
+      leaf_owner,
+      merkle_tree,
+      voucher,
+      byte_list(root),
+      params |> Map.get(:proof, []) |> Enum.map(&proof_node/1),
+      TransactionOptions.from_params(params)
+    )
+  end
+
+  @doc """
+  Decompresses a redeemed leaf into a regular Token Metadata NFT.
+
+  The leaf owner signs and pays for the transaction.
+
+  ## Parameters
+
+  * `voucher` - The voucher PDA created by `redeem/1`
+  * `leaf_owner` - The public key of the leaf owner
+  * `token_account` - The leaf owner's associated token account for the mint
+  * `mint` - The mint PDA of the decompressed NFT
+  * `mint_authority` - The mint authority PDA of the decompressed NFT
+  * `metadata_account` - The Token Metadata metadata account of the mint
+  * `master_edition` - The Token Metadata master edition account of the mint
+  * `associated_token_program` - The associated token program (optional)
+  * `system_program` - The system program (optional)
+  * `token_metadata_program` - The Token Metadata program (optional)
+  * `metadata` - The metadata of the NFT; its `token_program_version` selects SPL Token or
+    Token-2022 as the owner of `token_account` and `mint`
+
+  ## Returns
+
+  * `{:ok, transaction}` - The serialized transaction
+  * `{:error, reason}` - If an error occurs
+  """
+  def decompress_v1(%{
+        voucher: voucher,
+        leaf_owner: leaf_owner,
+        token_account: token_account,
+        mint: mint,
+        mint_authority: mint_authority,
+        metadata_account: metadata_account,
+        master_edition: master_edition,
+        metadata: metadata
+      } = params) do
+    Native.decompress_v1(
+      voucher,
+      leaf_owner,
+      token_account,
+      mint,
+      mint_authority,
+      metadata_account,
+      master_edition,
+      Map.get(params, :associated_token_program),
+      Map.get(params, :system_program),
+      Map.get(params, :token_metadata_program),
+      metadata,
+      TransactionOptions.from_params(params)
+    )
+  end
+
+  @doc """
+  Enables or disables decompression of the leaves of a merkle tree.
+
+  The tree creator signs and pays for the transaction.
+
+  ## Parameters
+
+  * `tree_config` - The public key for the tree configuration account
+  * `tree_creator` - The public key of the tree creator
+  * `merkle_tree` - The public key for the merkle tree account, which `tree_config` must belong to
+  * `decompressable` - Whether leaves of the tree can be decompressed
+
+  ## Returns
+
+  * `{:ok, transaction}` - The serialized transaction
+  * `{:error, reason}` - If an error occurs
+  """
+  def set_decompressible_state(%{
+        tree_config: tree_config,
+        tree_creator: tree_creator,
+        merkle_tree: merkle_tree,
+        decompressable: decompressable
+      } = params)
+      when is_boolean(decompressable) do
+    Native.set_decompressible_state(
+      tree_config,
+      tree_creator,
+      merkle_tree,
+      decompressable,
+      TransactionOptions.from_params(params)
+    )
+  end
+
+  @doc """
+  Builds an unsigned transaction and returns it with the SHA-256 digest of its message.
+
+  The digest is an immutable fingerprint of exactly what will be signed, suitable
+  for recording in an audit log before the transaction is approved.
+
+  ## Parameters
+
+  * `instruction` - The builder to use (`:create_tree_config`, `:mint_v1`, or `:transfer`)
+  * `params` - The parameters for that builder
+
+  ## Returns
+
+  * `{:ok, transaction, digest}` - The serialized transaction and its message digest as a Hash
+  * `{:error, reason}` - If an error occurs
+  """
+  def unsigned_with_digest(instruction, params) when is_map(params) do
+    result =
+      case instruction do
+        :create_tree_config ->
+          create_tree_config(params)
+
+        :mint_v1 ->
+          with {:ok, transaction, _asset_id} <- mint_v1(params), do: {:ok, transaction}
+
+        :transfer ->
+          transfer(params)
+
+        _ ->
+          {:error, "Unsupported instruction: #{inspect(instruction)}"}
+      end
+
+    with {:ok, transaction} <- result do
+      Native.unsigned_with_digest(to_binary(transaction))
+    end
+  end
+
+  @doc """
+  Decodes a serialized transaction for inspection, e.g. when debugging a failed mint.
+
+  ## Parameters
+
+  * `transaction` - The serialized transaction (binary or list of bytes)
+
+  ## Returns
+
+  * `{:ok, decoded}` - A `DecodedTransaction` with the fee payer, recent blockhash, number of
+    signatures and each instruction's program ID, accounts and base64-encoded data
+  * `{:error, reason}` - If the bytes are not a valid transaction
+  """
+  def decode_transaction(transaction) do
+    Native.decode_transaction(to_binary(transaction))
+  end
+
+  @doc """
+  Decodes the arguments of a bubblegum instruction, e.g. the data of an
+  instruction returned by `decode_transaction/1` whose program is
+  `bubblegum_program_id/0`.
+
+  ## Parameters
+
+  * `data` - The instruction data (binary or list of bytes); decode the base64 data of a
+    `DecodedTransaction` instruction with `Base.decode64!/1` first
+
+  ## Returns
+
+  * `{:ok, {name, args}}` - The instruction name as an atom, such as `:mint_v1` or `:transfer`, and
+    its arguments as a map with string keys; hashes and pubkeys are base58 strings and metadata
+    follows the fields of `MplBubblegum.Types.Metadata`
+  * `{:error, :unknown_discriminator}` - If the data is not a known bubblegum instruction
+  * `{:error, reason}` - If the arguments are malformed
+  """
+  def decode_bubblegum_instruction(data) do
+    Native.decode_bubblegum_instruction(to_binary(data))
+  end
+
+  @doc """
+  Encodes a serialized transaction as base64, the format browser wallet
+  adapters expect when asked to sign a transaction.
+
+  ## Parameters
+
+  * `transaction` - The serialized legacy or versioned transaction (binary or list of bytes)
+
+  ## Returns
+
+  * `{:ok, base64}` - The base64-encoded transaction
+  * `{:error, reason}` - If the bytes are not a valid transaction
+  """
+  def transaction_to_base64(transaction) do
+    Native.transaction_to_base64(to_binary(transaction))
+  end
+
+  @doc """
+  Decodes a base64 transaction, such as one signed by a browser wallet, back
+  into the serialized form the other functions take.
+
+  ## Parameters
+
+  * `base64` - The base64-encoded transaction
+
+  ## Returns
+
+  * `{:ok, transaction}` - The serialized transaction
+  * `{:error, reason}` - If the string is not base64 or not a valid transaction
+  """
+  def transaction_from_base64(base64) when is_binary(base64) do
+    Native.transaction_from_base64(base64)
+  end
+
+  @doc """
+  Signs and submits a transaction to the Solana network.
+
+  The blockhash is cached per RPC endpoint for 30 seconds, so bursts of submissions
+  share one `getLatestBlockhash` call. Retries always fetch a fresh blockhash.
+
+  ## Parameters
+
+  * `transaction` - The serialized transaction binary (returned from create_tree_config, mint_v1, or transfer)
+  * `secret_keys` - A list of secret keys (binary, base64-encoded strings, or Solana CLI JSON arrays) for all required signers
+  * `opts` - Keyword list of submission options:
+    * `:rpc_url` - The RPC endpoint URL (defaults to `"http://127.0.0.1:8899"`)
+    * `:commitment` - The commitment to confirm at: `"processed"`, `"confirmed"` or `"finalized"` (defaults to `"confirmed"`)
+    * `:max_retries` - How many times to re-sign with a fresh blockhash and resubmit, backing off exponentially (defaults to `0`).
+      Only a send that could not connect, a preflight `BlockhashNotFound` or an expired blockhash is
+      retried. When a send fails without a clear answer, the original signature is watched until its
+      blockhash expires before re-signing. A transaction rejected by preflight, failed on-chain or whose
+      status could not be read is returned as an error, naming its signature once it may have landed
+    * `:skip_preflight` - Whether to skip the preflight simulation (defaults to `false`)
+    * `:preflight_commitment` - The commitment to run the preflight simulation at, e.g. `"processed"`
+      for fast feedback while confirming at `"finalized"` (defaults to `:commitment`)
+
+  ## Returns
+
+  * `{:ok, signature}` - The transaction signature once confirmed
+  * `{:error, reason}` - The first error that cannot be retried, or the last error if every attempt failed
+  """
+  def sign_and_submit_transaction(transaction, secret_keys, opts \\ [])
+      when is_binary(transaction) and is_list(secret_keys) and is_list(opts) do
+    with {:ok, secret_key_binaries} <- normalize_secret_keys(secret_keys) do
+      Native.sign_and_submit_transaction(
+        Keyword.get(opts, :rpc_url, "http://127.0.0.1:8899"),
+        transaction,
+        secret_key_binaries,
+        Keyword.get(opts, :commitment, "confirmed"),
+        Keyword.get(opts, :max_retries, 0),
+        Keyword.get(opts, :skip_preflight, false),
+        Keyword.get(opts, :preflight_commitment)
+      )
+    end
+  end
+
+  @doc """
+  Clears the cached blockhashes used by `sign_and_submit_transaction/3`, e.g. between
+  tests against a restarted local validator.
+  """
+  def invalidate_blockhash_cache, do: Native.invalidate_blockhash_cache()
+
+  @doc """
+  Submits an already signed transaction without re-signing it or fetching a blockhash.
+
+  ## Parameters
+
+  * `rpc_url` - The RPC endpoint URL
+  * `transaction` - The serialized, fully signed transaction (binary or list of bytes)
+  * `opts` - Keyword options:
+    * `:skip_preflight` - Whether to skip the preflight simulation (default `false`)
+
+  ## Returns
+
+  * `{:ok, signature}` - The transaction signature
+  * `{:error, reason}` - If the transaction is not fully signed or submission fails
+  """
+  def send_raw_transaction(rpc_url, transaction, opts \\ []) when is_binary(rpc_url) do
+    Native.send_raw_transaction(
+      rpc_url,
+      to_binary(transaction),
+      Keyword.get(opts, :skip_preflight, false)
+    )
+  end
+
+  @doc """
+  Submits many signed transactions concurrently, with at most 20 in flight.
+
+  Transactions are only sent, not confirmed; check the returned signatures with
+  `get_signature_statuses/2`.
+
+  ## Parameters
+
+  * `transactions` - The fully signed, serialized legacy or v0 transactions
+  * `transactions` - The fully signed, serialized transactions
+
+  ## Returns
+
+  * `{:ok, results}` - An `{:ok, signature}` or `{:error, reason}` per transaction, in input order
+  """
+  def submit_transactions_batch(rpc_url, transactions)
+      when is_binary(rpc_url) and is_list(transactions) do
+    Native.submit_transactions_batch(rpc_url, Enum.map(transactions, &to_binary/1))
+  end
+
+  @doc """
+  Signs a transaction again against a fresh blockhash and submits it. Use it
+  when a transaction sat long enough for its blockhash to expire; signatures
+  made against the old blockhash are discarded.
+
+  ## Parameters
+
+  * `rpc_url` - The RPC endpoint URL
+  * `transaction` - The serialized, possibly signed, transaction (binary or list of bytes)
+  * `secret_keys` - A list of secret keys (binary or base64-encoded strings) for all required signers
+
+  ## Returns
+
+  * `{:ok, signature}` - The signature of the submitted transaction
+  * `{:error, reason}` - If an error occurs
+  """
+  def refresh_and_submit(rpc_url, transaction, secret_keys)
+      when is_binary(rpc_url) and is_list(secret_keys) do
+    with {:ok, secret_key_binaries} <- normalize_secret_keys(secret_keys) do
+      Native.refresh_and_submit(rpc_url, to_binary(transaction), secret_key_binaries)
+    end
+  end
+
+  @doc """
+  Signs a transaction without submitting it to the network.
+
+  ## Parameters
+
+  * `transaction` - The serialized transaction (binary or list of bytes)
+  * `secret_keys` - A list of secret keys (binary or base64-encoded strings) for all required signers
+  * `recent_blockhash` - The base58-encoded blockhash to sign against
+
+  ## Returns
+
+  * `{:ok, signed_transaction}` - The serialized signed transaction
+  * `{:error, reason}` - If an error occurs
+  """
+  def sign_transaction(transaction, secret_keys, recent_blockhash)
+      when is_list(secret_keys) and is_binary(recent_blockhash) do
+    with {:ok, secret_key_binaries} <- normalize_secret_keys(secret_keys) do
+      Native.sign_transaction(to_binary(transaction), secret_key_binaries, recent_blockhash)
+    end
+  end
+
+  @doc """
+  Adds the signatures of some of the signers of a transaction, leaving the
+  others to be added in later calls. This lets each party sign separately.
+
+  Every round must use the same blockhash: signing against a different one
+  clears the signatures collected so far.
+
+  ## Parameters
+
+  * `transaction` - The serialized, possibly partially signed, transaction (binary or list of bytes)
+  * `secret_keys` - A list of secret keys (binary or base64-encoded strings) for the signers available now
+  * `recent_blockhash` - The base58-encoded blockhash to sign against
+
+  ## Returns
+
+  * `{:ok, signed_transaction}` - The serialized transaction with the new signatures added
+  * `{:error, reason}` - If an error occurs
+  """
+  def partial_sign_transaction(transaction, secret_keys, recent_blockhash)
+      when is_list(secret_keys) and is_binary(recent_blockhash) do
+    with {:ok, secret_key_binaries} <- normalize_secret_keys(secret_keys) do
+      Native.partial_sign_transaction(to_binary(transaction), secret_key_binaries, recent_blockhash)
+    end
+  end
+
+  @doc """
+  Checks that every required signer of a transaction has a valid signature,
+  e.g. before submitting one assembled with `partial_sign_transaction/3`.
+
+  ## Parameters
+
+  * `transaction` - The serialized transaction (binary or list of bytes)
+
+  ## Returns
+
+  * `{:ok, true}` - If the transaction is fully and validly signed
+  * `{:error, {:unsigned_accounts, pubkeys}}` - The signers whose signature is missing or invalid
+  * `{:error, reason}` - If an error occurs
+  """
+  def verify_transaction_signatures(transaction) do
+    Native.verify_transaction_signatures(to_binary(transaction))
+  end
+
+  @doc """
+  Adds compute budget instructions to a transaction built without them, e.g.
+  to raise its priority fee after `estimate_compute_units/2`.
+
+  The message is rebuilt, so any existing signatures are dropped. Transactions
+  that already set a compute budget are returned unchanged.
+
+  ## Parameters
+
+  * `transaction` - The serialized transaction (binary or list of bytes)
+  * `compute_unit_limit` - Compute unit limit for the transaction
+  * `compute_unit_price` - Priority fee in micro-lamports per compute unit
+
+  ## Returns
+
+  * `{:ok, transaction}` - The serialized unsigned transaction
+  * `{:error, reason}` - If an error occurs
+  """
+  def prepend_priority_fee(transaction, compute_unit_limit, compute_unit_price)
+      when is_integer(compute_unit_limit) and is_integer(compute_unit_price) do
+    Native.prepend_priority_fee(to_binary(transaction), compute_unit_limit, compute_unit_price)
+  end
+
+  @doc """
+  Gets the status of a transaction on the Solana network.
+
+  ## Parameters
+
+  * `signature` - The transaction signature (string)
+  * `commitment` - The commitment to query at: `"processed"`, `"confirmed"` or `"finalized"` (defaults to `"confirmed"`)
+
+  ## Returns
+
+  * `{:ok, status}` - The status ("confirmed", "failed: <reason>", or "not_found")
+  * `{:error, reason}` - If an error occurs
+  """
+  def get_transaction_status(signature, commitment \\ "confirmed")
+      when is_binary(signature) and is_binary(commitment) do
+    Native.get_transaction_status(signature, commitment)
+  end
+
+  @doc """
+  Gets a confirmed transaction with its program logs, for post-mortem analysis.
+
+  ## Parameters
+
+  * `rpc_url` - The RPC endpoint URL
+  * `signature` - The transaction signature (string)
+  * `commitment` - `"confirmed"` or `"finalized"` (defaults to `"confirmed"`)
+
+  ## Returns
+
+  * `{:ok, details}` - A `TransactionDetails` with the slot, error, log messages, compute units consumed and fee
+  * `{:error, reason}` - If an error occurs or the transaction is not found
+  """
+  def get_transaction(rpc_url, signature, commitment \\ "confirmed")
+      when is_binary(rpc_url) and is_binary(signature) and is_binary(commitment) do
+    Native.get_transaction(rpc_url, signature, commitment)
+  end
+
+  @doc """
+  Gets the statuses of up to 256 transaction signatures in a single RPC request.
+
+  ## Parameters
+
+  * `rpc_url` - The RPC endpoint URL
+  * `signatures` - The transaction signatures (strings)
+
+  ## Returns
+
+  * `{:ok, statuses}` - A `SignatureStatus` per signature in the same order, or `nil` for unknown signatures
+  * `{:error, reason}` - If an error occurs
+  """
+  def get_signature_statuses(rpc_url, signatures)
+      when is_binary(rpc_url) and is_list(signatures) do
+    Native.get_signature_statuses(rpc_url, signatures)
+  end
+
+  @doc """
+  Checks whether a blockhash is still accepted by the cluster.
+
+  Use this before submitting a transaction that sat in a queue, and refresh
+  its blockhash instead of submitting when it has expired.
+
+  ## Parameters
+
+  * `blockhash` - The base58-encoded blockhash
+  * `rpc_url` - The RPC endpoint URL
+
+  ## Returns
+
+  * `{:ok, valid}` - Whether a transaction using the blockhash would be accepted
+  * `{:error, reason}` - If an error occurs
+  """
+  def is_blockhash_valid(blockhash, rpc_url) when is_binary(blockhash) and is_binary(rpc_url) do
+    Native.is_blockhash_valid(blockhash, rpc_url)
+  end
+
+  @doc """
+  Gets the blockhash stored in a durable nonce account.
+
+  Transactions built with `nonce_account` must be signed against this value
+  instead of a recent blockhash, and stay valid until the nonce is advanced.
+
+  ## Parameters
+
+  * `rpc_url` - The RPC endpoint URL
+  * `nonce_account` - The public key of the nonce account
+
+  ## Returns
+
+  * `{:ok, blockhash}` - The base58-encoded nonce value
+  * `{:error, reason}` - If the account is not an initialized nonce account
+  """
+  def get_nonce_blockhash(rpc_url, nonce_account) when is_binary(rpc_url) do
+    Native.get_nonce_blockhash(rpc_url, nonce_account)
+  end
+
+  @doc """
+  Gets the number of leaves minted into a tree so far, which is the nonce of
+  the next mint. Pass it to `get_asset_id/2` to learn the asset ID up front.
+
+  ## Parameters
+
+  * `rpc_url` - The RPC endpoint URL
+  * `tree_config` - The public key for the tree configuration account
+
+  ## Returns
+
+  * `{:ok, num_minted}` - The number of minted leaves
+  * `{:error, reason}` - If an error occurs
+  """
+  def get_tree_num_minted(rpc_url, tree_config) when is_binary(rpc_url) do
+    Native.get_tree_num_minted(rpc_url, tree_config)
+  end
+
+  @doc """
+  Gets the number of leaves that can still be minted into a tree, so a full
+  tree can be skipped before minting.
+
+  ## Parameters
+
+  * `rpc_url` - The RPC endpoint URL
+  * `tree_config` - The public key for the tree configuration account
+
+  ## Returns
+
+  * `{:ok, remaining}` - The tree's capacity minus the leaves minted so far
+  * `{:error, reason}` - If an error occurs
+  """
+  def remaining_capacity(rpc_url, tree_config) when is_binary(rpc_url) do
+    Native.remaining_capacity(rpc_url, tree_config)
+  end
+
+  @doc """
+  Gets the balance of an SPL token account, e.g. to confirm that
+  `decompress_v1/1` left exactly one token in the owner's account.
+
+  ## Parameters
+
+  * `rpc_url` - The RPC endpoint URL
+  * `token_account` - The public key of the token account
+
+  ## Returns
+
+  * `{:ok, %TokenAmount{}}` - The raw amount, the mint's decimals and the formatted amount
+  * `{:error, reason}` - If an error occurs
+  """
+  def get_token_account_balance(rpc_url, token_account) when is_binary(rpc_url) do
+    Native.get_token_account_balance(rpc_url, token_account)
+  end
+
+  @doc """
+  Gets the minimum balance an account of the given size needs to be rent exempt.
+
+  ## Parameters
+
+  * `rpc_url` - The RPC endpoint URL
+  * `space` - The account size in bytes
+
+  ## Returns
+
+  * `{:ok, lamports}` - The rent-exempt minimum balance
+  * `{:error, reason}` - If an error occurs
+  """
+  def min_balance_for_rent_exemption(rpc_url, space) when is_binary(rpc_url) and is_integer(space) do
+    Native.min_balance_for_rent_exemption(rpc_url, space)
+  end
+
+  @doc """
+  Estimates the fee of a transaction in lamports, including the priority fee
+  set by its compute budget instructions.
+
+  The transaction does not need to be signed; it is priced against a recent blockhash.
+
+  ## Parameters
+
+  * `rpc_url` - The RPC endpoint URL
+  * `transaction` - The serialized transaction (binary or list of bytes)
+
+  ## Returns
+
+  * `{:ok, fee}` - The fee in lamports, or `nil` if the blockhash expired before pricing
+  * `{:error, reason}` - If an error occurs
+  """
+  def estimate_fee(rpc_url, transaction) when is_binary(rpc_url) do
+    Native.estimate_fee(rpc_url, to_binary(transaction))
+  end
+
+  @doc """
+  Estimates the compute units a transaction consumes by simulating it, so a
+  `compute_unit_limit` can be set with a margin instead of guessed.
+
+  The transaction does not need to be signed; it is simulated against a recent blockhash.
+
+  ## Parameters
+
+  * `rpc_url` - The RPC endpoint URL
+  * `transaction` - The serialized transaction (binary or list of bytes)
+
+  ## Returns
+
+  * `{:ok, units}` - The consumed compute units, or `nil` if the simulation failed
+  * `{:error, reason}` - If an error occurs
+  """
+  def estimate_compute_units(rpc_url, transaction) when is_binary(rpc_url) do
+    Native.estimate_compute_units(rpc_url, to_binary(transaction))
+  end
+
+  @doc """
+  Gets the prioritization fees paid in recent slots, to pick a competitive
+  `compute_unit_price` instead of guessing, e.g. a percentile of the fees.
+
+  ## Parameters
+
+  * `rpc_url` - The RPC endpoint URL
+  * `accounts` - Writable accounts the transaction will lock, such as the merkle tree; the fees
+    are those of transactions locking all of them (optional, defaults to `[]` for the whole cluster)
+
+  ## Returns
+
+  * `{:ok, fees}` - A list of `{slot, prioritization_fee}` pairs, in micro-lamports per compute unit
+  * `{:error, reason}` - If an error occurs
+  """
+  def get_recent_prioritization_fees(rpc_url, accounts \\ []) when is_binary(rpc_url) and is_list(accounts) do
+    with {:ok, accounts} <- normalize_pubkeys(accounts) do
+      Native.get_recent_prioritization_fees(rpc_url, accounts)
+    end
+  end
+
+  @doc """
+  Gets the slot the cluster has reached at the given commitment.
+
+  ## Parameters
+
+  * `rpc_url` - The RPC endpoint URL
+  * `commitment` - `"processed"`, `"confirmed"` or `"finalized"` (defaults to `"confirmed"`)
+
+  ## Returns
+
+  * `{:ok, slot}` - The current slot
+  * `{:error, reason}` - If an error occurs
+  """
+  def get_slot(rpc_url, commitment \\ "confirmed") when is_binary(rpc_url) and is_binary(commitment) do
+    Native.get_slot(rpc_url, commitment)
+  end
+
+  @doc """
+  Gets the block height the cluster has reached at the given commitment.
+
+  Unlike slots, block heights skip no numbers, which makes them the measure
+  blockhash expiry is defined in.
+
+  ## Parameters
+
+  * `rpc_url` - The RPC endpoint URL
+  * `commitment` - `"processed"`, `"confirmed"` or `"finalized"` (defaults to `"confirmed"`)
+
+  ## Returns
+
+  * `{:ok, block_height}` - The current block height
+  * `{:error, reason}` - If an error occurs
+  """
+  def get_block_height(rpc_url, commitment \\ "confirmed")
+      when is_binary(rpc_url) and is_binary(commitment) do
+    Native.get_block_height(rpc_url, commitment)
+  end
+
+  @doc """
+  Checks whether an RPC node is healthy, i.e. caught up with the cluster.
+
+  This is a cheap probe for picking a working endpoint before submitting.
+
+  ## Parameters
+
+  * `rpc_url` - The RPC endpoint URL
+
+  ## Returns
+
+  * `{:ok, :ok}` - If the node is healthy
+  * `{:error, reason}` - If the node is behind or unreachable
+  """
+  def get_health(rpc_url) when is_binary(rpc_url) do
+    Native.get_health(rpc_url)
+  end
+
+  @doc """
+  Gets the earliest slot the RPC node still has a block for, below which
+  historical queries such as `get_transaction/3` cannot be served.
+
+  ## Parameters
+
+  * `rpc_url` - The RPC endpoint URL
+
+  ## Returns
+
+  * `{:ok, slot}` - The first available slot
+  * `{:error, reason}` - If an error occurs
+  """
+  def get_first_available_block(rpc_url) when is_binary(rpc_url) do
+    Native.get_first_available_block(rpc_url)
+  end
+
+  @doc """
+  Requests an airdrop from the cluster faucet and waits for it to confirm.
+
+  Intended for local validators and devnet; mainnet has no faucet and returns
+  an `:invalid_parameter` error.
+
+  ## Parameters
+
+  * `rpc_url` - The RPC endpoint URL
+  * `pubkey` - The account to fund (Pubkey struct or base58 string)
+  * `lamports` - The amount to airdrop
+
+  ## Returns
+
+  * `{:ok, signature}` - The confirmed airdrop signature
+  * `{:error, reason}` - If an error occurs
+  """
+  def request_airdrop(rpc_url, pubkey, lamports) when is_binary(rpc_url) and is_integer(lamports) do
+    with {:ok, pubkey_struct} <- normalize_pubkey(pubkey) do
+      Native.request_airdrop(rpc_url, pubkey_struct, lamports)
+    end
+  end
+
+  @doc """
+  Returns the program ID of Metaplex Bubblegum.
+  """
+  @spec bubblegum_program_id() :: Pubkey.t()
+  def bubblegum_program_id, do: Native.bubblegum_program_id()
+
+  @doc """
+  Returns the program ID of SPL Account Compression.
+  """
+  @spec account_compression_program_id() :: Pubkey.t()
+  def account_compression_program_id, do: Native.account_compression_program_id()
+
+  @doc """
+  Returns the program ID of SPL Noop, the log wrapper used by Bubblegum.
+  """
+  @spec noop_program_id() :: Pubkey.t()
+  def noop_program_id, do: Native.noop_program_id()
+
+  @doc """
+  Estimates the size and rent-exempt balance of a merkle tree account offline.
+
+  Uses the default rent parameters, which match mainnet, devnet and test validators.
+
+  ## Parameters
+
+  * `max_depth` - The maximum depth of the tree
+  * `max_buffer_size` - The maximum buffer size of the tree
+  * `canopy_depth` - The number of upper tree levels cached on chain
+
+  ## Returns
+
+  * `{:ok, space, lamports}` - The account size in bytes and its rent-exempt balance
+  * `{:error, reason}` - If the dimensions are invalid
+  """
+  def merkle_tree_rent(max_depth, max_buffer_size, canopy_depth \\ 0) do
+    Native.merkle_tree_rent(max_depth, max_buffer_size, canopy_depth)
+  end
+
+  @doc """
+  Computes the number of leaves a tree of the given depth can hold.
+
+  ## Parameters
+
+  * `max_depth` - The maximum depth of the merkle tree
+
+  ## Returns
+
+  * `{:ok, capacity}` - `2^max_depth`
+  * `{:error, reason}` - If `max_depth` is not between 1 and 30
+  """
+  def tree_capacity(max_depth) when is_integer(max_depth) do
+    Native.tree_capacity(max_depth)
+  end
+
+  # Update helper function to handle a list of secret keys
+  defp normalize_secret_keys(secret_keys) do
+    Enum.reduce_while(secret_keys, {:ok, []}, fn key, {:ok, acc} ->
+      case normalize_secret_key(key) do
+        {:ok, binary} -> {:cont, {:ok, [binary | acc]}}
+        {:error, reason} -> {:halt, {:error, reason}}
+      end
+    end)
+    |> case do
+         {:ok, list} -> {:ok, Enum.reverse(list)}
+         {:error, reason} -> {:error, reason}
+       end
+  end
+
+   @doc """
+  Gets account information from the Solana network.
+
+  ## Parameters
+
+  * `pubkey` - The public key of the account (Pubkey struct or base58 string)
+  * `commitment` - The commitment to query at: `"processed"`, `"confirmed"` or `"finalized"` (defaults to `"confirmed"`)
+
+  ## Returns
+
+  * `{:ok, account_info}` - An `AccountInfo` with lamports, owner, executable, rent_epoch and the raw data
+  * `{:error, reason}` - If an error occurs
+  """
+  def get_account_info(pubkey, commitment \\ "confirmed") do
+    with {:ok, pubkey_struct} <- normalize_pubkey(pubkey),
+         {:ok, account_info} <- Native.get_account_info(pubkey_struct, commitment) do
+      {:ok, binary_data(account_info)}
+    end
+  end
+
+  @doc """
+  Gets information for up to 100 accounts in a single RPC request.
+
+  ## Parameters
+
+  * `rpc_url` - The RPC endpoint URL
+  * `pubkeys` - The public keys of the accounts (Pubkey structs or base58 strings)
+
+  ## Returns
+
+  * `{:ok, accounts}` - An `AccountInfo` per pubkey in the same order, or `nil` for missing accounts
+  * `{:error, reason}` - If an error occurs
+  """
+  def batch_get_account_info(rpc_url, pubkeys) when is_binary(rpc_url) and is_list(pubkeys) do
+    with {:ok, pubkey_structs} <- normalize_pubkeys(pubkeys),
+         {:ok, accounts} <- Native.batch_get_account_info(rpc_url, pubkey_structs) do
+      {:ok, Enum.map(accounts, &(&1 && binary_data(&1)))}
+    end
+  end
+
+  @doc """
+  Gets every account owned by a program that matches all the given filters,
+  e.g. the tree configs created by a given tree creator.
+
+  ## Parameters
+
+  * `rpc_url` - The RPC endpoint URL
+  * `program_id` - The owning program (Pubkey struct or base58 string)
+  * `filters` - A list of `{:memcmp, offset, bytes}` and `{:data_size, size}` tuples (optional)
+
+  ## Returns
+
+  * `{:ok, accounts}` - A list of `{pubkey, account_info}` pairs
+  * `{:error, reason}` - If an error occurs
+  """
+  def get_program_accounts(rpc_url, program_id, filters \\ [])
+      when is_binary(rpc_url) and is_list(filters) do
+    with {:ok, program_id} <- normalize_pubkey(program_id),
+         {:ok, accounts} <- Native.get_program_accounts(rpc_url, program_id, filters) do
+      {:ok, Enum.map(accounts, fn {pubkey, account_info} -> {pubkey, binary_data(account_info)} end)}
+    end
+  end
+
+  defp normalize_pubkeys(pubkeys) do
+    Enum.reduce_while(pubkeys, {:ok, []}, fn pubkey, {:ok, acc} ->
+      case normalize_pubkey(pubkey) do
+        {:ok, pubkey_struct} -> {:cont, {:ok, [pubkey_struct | acc]}}
+        {:error, reason} -> {:halt, {:error, reason}}
+      end
+    end)
+    |> case do
+      {:ok, list} -> {:ok, Enum.reverse(list)}
+      {:error, reason} -> {:error, reason}
+    end
+  end
+
+  # Helper functions for parameter validation
+
+  # Solana CLI JSON format; a raw key or seed can also start with "["
+  defp normalize_secret_key("[" <> _ = secret_key) when byte_size(secret_key) not in [32, 64],
+    do: {:ok, secret_key}
+  defp normalize_secret_key(secret_key) when is_binary(secret_key) do
+    case byte_size(secret_key) do
+      size when size in [32, 64] -> {:ok, secret_key}  # Already in binary format, or a 32-byte seed
+      _ ->
+        case Base.decode64(secret_key) do
+          {:ok, binary} when byte_size(binary) in [32, 64] -> {:ok, binary}
+          _ -> {:error, "Invalid secret key format; must be 64 bytes, a 32-byte seed or their base64 encoding"}
+        end
+    end
+  end
+
+  defp byte_list(%Hash{bytes: bytes}), do: byte_list(bytes)
+  defp byte_list(bytes) when is_binary(bytes), do: :binary.bin_to_list(bytes)
+  defp byte_list(bytes) when is_list(bytes), do: bytes
+
+  # Proof nodes from the DAS API are base58 strings rather than raw 32-byte binaries
+  defp proof_node(node) when is_binary(node) and byte_size(node) != 32,
+    do: node |> Base58.decode() |> byte_list()
+
+  defp proof_node(node), do: byte_list(node)
+
+  defp to_binary(bytes) when is_list(bytes), do: :binary.list_to_bin(bytes)
+  defp to_binary(bytes) when is_binary(bytes), do: bytes
+
+  defp binary_data(%AccountInfo{data: data} = account_info),
+    do: %{account_info | data: to_binary(data)}
+
+  defp normalize_pubkey(%Pubkey{} = pubkey), do: {:ok, pubkey}
+  defp normalize_pubkey(pubkey) when is_binary(pubkey) do
+    Pubkey.from_base58(pubkey)
+  end
+  defp normalize_pubkey(_), do: {:error, "Invalid public key format"}
+
+  @doc """
+  Builds NFT metadata with the given name, symbol and URI and defaults for
+  everything else: no royalties, no creators, not yet sold, mutable and owned
+  by the original token program. Update the fields that differ, e.g.
+  `%{metadata | creators: [creator]}`.
+  """
+  @spec default_metadata(String.t(), String.t(), String.t()) :: MplBubblegum.Types.Metadata.t()
+  def default_metadata(name, symbol, uri) when is_binary(name) and is_binary(symbol) and is_binary(uri) do
+    Native.default_metadata(name, symbol, uri)
+  end
+
+  @doc """
+  Serializes NFT metadata into its canonical borsh encoding, the bytes that
+  `hash_metadata/1` hashes, e.g. to keep an audit trail of minted assets.
+
+  ## Parameters
+
+  * `metadata` - The metadata to serialize
+
+  ## Returns
+
+  * `{:ok, bytes}` - The borsh-encoded metadata as a list of bytes
+  * `{:error, reason}` - If the metadata is invalid
+  """
+  def metadata_to_borsh(metadata) do
+    Native.metadata_to_borsh(metadata)
+  end
+
+  @doc """
+  Reconstructs NFT metadata from the bytes returned by `metadata_to_borsh/1`.
+
+  ## Parameters
+
+  * `bytes` - The borsh-encoded metadata (binary or list of bytes)
+
+  ## Returns
+
+  * `{:ok, metadata}` - The decoded `MplBubblegum.Types.Metadata`
+  * `{:error, reason}` - If the bytes are not exactly one encoded metadata
+  """
+  def metadata_from_borsh(bytes) do
+    Native.metadata_from_borsh(to_binary(bytes))
+  end
+
+  @doc """
+  Hashes the metadata of an NFT.
+
+  ## Parameters
+
+  * `metadata` - The metadata to hash
+
+  ## Returns
+
+  * `{:ok, hash}` - The hash of the metadata
+  * `{:error, reason}` - If an error occurs
+  """
+  def hash_metadata(metadata) do
+    Native.hash_metadata(metadata)
+  end
+
+  @doc """
+  Hashes the metadata of many NFTs in a single call.
+
+  ## Parameters
+
+  * `metadatas` - The metadata to hash
+
+  ## Returns
+
+  * `{:ok, hashes}` - The hashes, in the same order as the metadata
+  * `{:error, {index, reason}}` - If the metadata at `index` is invalid
+  """
+  def hash_metadata_batch(metadatas) when is_list(metadatas) do
+    Native.hash_metadata_batch(metadatas)
+  end
+
+  @doc """
+  Hashes the creators of an NFT.
+
+  ## Parameters
+
+  * `creators` - The creators to hash
+
+  ## Returns
+
+  * `{:ok, hash}` - The hash of the creators
+  * `{:error, reason}` - If an error occurs
+  """
+  def hash_creators(creators) do
+    Native.hash_creators(creators)
+  end
+
+  @doc """
+  Computes the data hash, creator hash and leaf hash of a compressed NFT,
+  as the Bubblegum program does when it appends or replaces a leaf.
+
+  ## Parameters
+
+  * `metadata` - The metadata of the NFT
+  * `merkle_tree` - The public key of the merkle tree, from which the asset ID is derived
+  * `leaf_owner` - The public key of the leaf owner
+  * `leaf_delegate` - The public key of the leaf delegate
+  * `nonce` - The nonce of the leaf
+
+  ## Returns
+
+  * `{:ok, data_hash, creator_hash, leaf_hash}` - The hashes of the leaf
+  * `{:error, reason}` - If an error occurs
+  """
+  def compute_leaf_hash(metadata, merkle_tree, leaf_owner, leaf_delegate, nonce) do
+    Native.compute_leaf_hash(metadata, merkle_tree, leaf_owner, leaf_delegate, nonce)
+  end
+
+  @doc """
+  Computes the node hash of a leaf from its fields, e.g. to check that a proof
+  returned by `get_asset_proof/2` belongs to the leaf it is used for.
+
+  ## Parameters
+
+  * `asset_id` - The asset ID of the leaf
+  * `leaf_owner` - The public key of the leaf owner
+  * `leaf_delegate` - The public key of the leaf delegate
+  * `nonce` - The nonce of the leaf
+  * `index` - The index of the leaf in the tree, which must equal the nonce
+  * `data_hash` - The data hash of the leaf
+  * `creator_hash` - The creator hash of the leaf
+
+  ## Returns
+
+  * `{:ok, leaf_node}` - The hash of the leaf as stored in the tree
+  * `{:error, reason}` - If an error occurs
+  """
+  def compute_leaf_node(asset_id, leaf_owner, leaf_delegate, nonce, index, data_hash, creator_hash) do
+    Native.compute_leaf_node(asset_id, leaf_owner, leaf_delegate, nonce, index, data_hash, creator_hash)
+  end
+
+  @doc """
+  Gets the asset ID for a leaf.
+
+  ## Parameters
+
+  * `tree` - The public key of the merkle tree
+  * `nonce` - The nonce of the leaf
+
+  ## Returns
+
+  * `{:ok, asset_id}` - The asset ID
+  * `{:error, reason}` - If an error occurs
+  """
+  def get_asset_id(tree, nonce) do
+    Native.get_asset_id(tree, nonce)
+  end
+
+  @doc """
+  Gets the asset IDs for many leaves of a tree in a single call.
+
+  ## Parameters
+
+  * `tree` - The public key of the merkle tree
+  * `nonces` - The nonces of the leaves
+
+  ## Returns
+
+  * `{:ok, asset_ids}` - The asset IDs, in the same order as the nonces
+  * `{:error, reason}` - If an error occurs
+  """
+  def get_asset_id_batch(tree, nonces) when is_list(nonces) do
+    Native.get_asset_id_batch(tree, nonces)
+  end
+
+  @doc """
+  Derives the tree config PDA of a merkle tree, seeded with the merkle tree address.
+
+  ## Parameters
+
+  * `merkle_tree` - The public key of the merkle tree
+
+  ## Returns
+
+  * `{:ok, tree_config, bump}` - The tree config address and its bump seed
+  """
+  def derive_tree_config_pda(merkle_tree) do
+    Native.derive_tree_config_pda(merkle_tree)
+  end
+
+  @doc """
+  Derives the asset ID PDA of a leaf, seeded with `"asset"`, the tree address
+  and the little-endian nonce. Unlike `get_asset_id/2` it also returns the bump.
+
+  ## Parameters
+
+  * `tree` - The public key of the merkle tree
+  * `nonce` - The nonce of the leaf
+
+  ## Returns
+
+  * `{:ok, asset_id, bump}` - The asset ID and its bump seed
+  """
+  def derive_asset_pda(tree, nonce) when is_integer(nonce) do
+    Native.derive_asset_pda(tree, nonce)
+  end
+
+  @doc """
+  Derives the bubblegum signer PDA, seeded with `"collection_cpi"` under the
+  bubblegum program.
+
+  The bubblegum program signs with it when calling into token metadata, so it is
+  an account of `mint_to_collection_v1/1` and the collection verification
+  instructions. The builders derive it themselves.
+
+  ## Returns
+
+  * `{:ok, bubblegum_signer, bump}` - The bubblegum signer address and its bump seed
+  """
+  def derive_bubblegum_signer_pda, do: Native.derive_bubblegum_signer_pda()
+
+  @doc """
+  Decodes the data of a tree config account.
+
+  ## Parameters
+
+  * `data` - The raw account data, including the 8-byte discriminator (e.g. the `data` of `get_account_info/2`)
+
+  ## Returns
+
+  * `{:ok, tree_config}` - An `MplBubblegum.Types.TreeConfig`
+  * `{:error, reason}` - If the data is too short or its discriminator is not that of a tree config
+  """
+  def parse_tree_config(data) when is_binary(data) do
+    Native.parse_tree_config(data)
+  end
+
+  @doc """
+  Decodes the header of a concurrent merkle tree account, e.g. to check a tree's
+  depth, buffer size and authority before minting into it.
+
+  ## Parameters
+
+  * `data` - The raw account data of the merkle tree (e.g. the `data` of `get_account_info/2`)
+
+  ## Returns
+
+  * `{:ok, header}` - An `MplBubblegum.Types.MerkleTreeHeader`
+  * `{:error, reason}` - If the data is not an initialized merkle tree
+  """
+  def parse_merkle_tree_header(data) when is_binary(data) do
+    Native.parse_merkle_tree_header(data)
+  end
+
+  @doc """
+  Benchmarks local metadata hashing throughput.
+
+  Requires the native crate to be compiled with the `benchmark` feature.
+
+  ## Parameters
+
+  * `iterations` - The number of times to hash the metadata
+
+  ## Returns
+
+  * `{:ok, elapsed_microseconds, hashes_per_second}` - The benchmark results
+  * `{:error, reason}` - If an error occurs
+  """
+  def benchmark_hashing(iterations) when is_integer(iterations) and iterations > 0 do
+    Native.benchmark_hashing(iterations)
+  end
+
+  @doc """
+  Fetches a compressed asset through a DAS API endpoint.
+
+  Compressed assets have no on-chain account, so reading back a minted asset
+  requires an indexer. Newly minted assets may take a few seconds to appear.
+
+  ## Parameters
+
+  * `das_url` - The DAS API endpoint URL
+  * `asset_id` - The asset ID, e.g. as returned by `mint_v1/1`
+
+  ## Returns
+
+  * `{:ok, asset}` - The asset as returned by `getAsset`
+  * `{:error, reason}` - If an error occurs or the asset is not indexed yet
+  """
+  def get_asset(das_url, asset_id) when is_binary(das_url) do
+    with {:ok, asset_id} <- normalize_pubkey(asset_id) do
+      Native.get_asset(das_url, asset_id)
+    end
+  end
+
+  @doc """
+  Fetches the current merkle proof of a compressed asset through a DAS API endpoint.
+
+  The `proof` can be passed as is to `transfer/1`, `delegate/1` and the other
+  leaf instructions; its nodes are ordered from the leaf up to the root.
+
+  ## Parameters
+
+  * `das_url` - The DAS API endpoint URL
+  * `asset_id` - The asset ID
+
+  ## Returns
+
+  * `{:ok, proof}` - An `AssetProof` with the base58-encoded root, proof nodes, leaf and tree
+  * `{:error, reason}` - If an error occurs or the asset is not indexed yet
+  """
+  def get_asset_proof(das_url, asset_id) when is_binary(das_url) do
+    with {:ok, asset_id} <- normalize_pubkey(asset_id) do
+      Native.get_asset_proof(das_url, asset_id)
+    end
+  end
+
+  @doc """
+  Looks up an asset by its tree and leaf index through a DAS API endpoint.
+
+  The asset ID is derived from the tree and leaf index, so no separate
+  indexer lookup is needed to resolve it.
+
+  ## Parameters
+
+  * `das_url` - The DAS API endpoint URL
+  * `tree` - The public key of the merkle tree
+  * `leaf_index` - The index of the leaf in the tree
+
+  ## Returns
+
+  * `{:ok, asset}` - The asset as returned by `getAsset`
+  * `{:error, reason}` - If an error occurs or the asset is not indexed yet
+  """
+  def get_asset_by_index(das_url, tree, leaf_index) when is_binary(das_url) and is_integer(leaf_index) do
+    with {:ok, tree} <- normalize_pubkey(tree) do
+      Native.get_asset_by_index(das_url, tree, leaf_index)
+    end
   end
 
   defp get_pubkey(params, key) do
@@ -12,34 +12,174 @@ defmodule MplBubblegum.Native do
   # These function stubs are here to provide documentation and to prevent compile-time warnings.
 
   @doc false
-  def create_tree_config(_tree_config, _merkle_tree, _payer, _tree_creator, _max_depth, _max_buffer_size, _public),
+  def create_tree_config(_tree_config, _merkle_tree, _payer, _tree_creator, _max_depth, _max_buffer_size, _public, _extra_accounts, _options),
     do: :erlang.nif_error(:nif_not_loaded)
 
   @doc false
-  def mint_v1(_tree_config, _leaf_owner, _leaf_delegate, _merkle_tree, _payer, _tree_creator_or_delegate, _metadata),
+  def create_tree_config_instruction_only(_tree_config, _merkle_tree, _payer, _tree_creator, _max_depth, _max_buffer_size, _public, _extra_accounts, _options),
     do: :erlang.nif_error(:nif_not_loaded)
 
   @doc false
-  def transfer(_tree_config, _leaf_owner, _leaf_delegate, _new_leaf_owner, _merkle_tree, _root, _data_hash, _creator_hash, _nonce, _index),
+  def check_tree_funding(_rpc_url, _payer, _transaction, _max_depth, _max_buffer_size),
     do: :erlang.nif_error(:nif_not_loaded)
 
+  @doc false
+  def mint_v1(_tree_config, _leaf_owner, _leaf_delegate, _merkle_tree, _payer, _tree_creator_or_delegate, _metadata, _nonce, _extra_accounts, _extra_instructions, _options),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc false
+  def build_batch_mint(_payer, _mints, _options),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc false
+  def mint_to_collection_v1(_tree_config, _leaf_owner, _leaf_delegate, _merkle_tree, _payer, _tree_creator_or_delegate, _collection_authority, _collection_authority_record_pda, _collection_mint, _collection_metadata, _collection_edition, _metadata, _extra_accounts, _options),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc false
+  def transfer(_tree_config, _leaf_owner, _leaf_delegate, _new_leaf_owner, _merkle_tree, _payer, _root, _data_hash, _creator_hash, _nonce, _index, _leaf_owner_is_signer, _leaf_delegate_is_signer, _proof, _canopy_depth, _extra_accounts, _max_depth, _options),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc false
+  def transfer_with_signers(_tree_config, _leaf_owner, _leaf_delegate, _new_leaf_owner, _merkle_tree, _payer, _root, _data_hash, _creator_hash, _nonce, _index, _signers, _proof, _canopy_depth, _extra_accounts, _options),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc false
+  def build_transfer_from_proof(_asset_proof, _leaf_owner, _leaf_delegate, _new_leaf_owner, _payer, _data_hash, _creator_hash, _canopy_depth, _options),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc false
+  def delegate_and_transfer(_asset_proof, _leaf_owner, _previous_leaf_delegate, _new_leaf_delegate, _new_leaf_owner, _data_hash, _creator_hash, _canopy_depth, _options),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc false
+  def build_transfer_v0(_rpc_url, _tree_config, _leaf_owner, _leaf_delegate, _new_leaf_owner, _merkle_tree, _payer, _root, _data_hash, _creator_hash, _nonce, _index, _leaf_owner_is_signer, _leaf_delegate_is_signer, _proof, _canopy_depth, _extra_accounts, _options, _lookup_table),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc false
+  def delegate(_tree_config, _leaf_owner, _previous_leaf_delegate, _new_leaf_delegate, _merkle_tree, _root, _data_hash, _creator_hash, _nonce, _index, _proof, _canopy_depth, _options),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc false
+  def escrow_delegate(_tree_config, _leaf_owner, _escrow, _merkle_tree, _root, _data_hash, _creator_hash, _nonce, _index, _proof, _canopy_depth, _options),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc false
+  def release_delegate(_tree_config, _leaf_owner, _escrow, _merkle_tree, _root, _data_hash, _creator_hash, _nonce, _index, _proof, _canopy_depth, _options),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc false
+  def burn(_tree_config, _leaf_owner, _leaf_delegate, _merkle_tree, _payer, _root, _data_hash, _creator_hash, _nonce, _index, _leaf_owner_is_signer, _leaf_delegate_is_signer, _proof, _canopy_depth, _extra_accounts, _options),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc false
+  def verify_collection(_tree_config, _leaf_owner, _leaf_delegate, _merkle_tree, _payer, _tree_creator_or_delegate, _collection_authority, _collection_authority_record_pda, _collection_mint, _collection_metadata, _collection_edition, _root, _data_hash, _creator_hash, _nonce, _index, _metadata, _proof, _options),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc false
+  def unverify_collection(_tree_config, _leaf_owner, _leaf_delegate, _merkle_tree, _payer, _tree_creator_or_delegate, _collection_authority, _collection_authority_record_pda, _collection_mint, _collection_metadata, _collection_edition, _root, _data_hash, _creator_hash, _nonce, _index, _metadata, _proof, _options),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc false
+  def set_and_verify_collection(_tree_config, _leaf_owner, _leaf_delegate, _merkle_tree, _payer, _tree_creator_or_delegate, _collection_authority, _collection_authority_record_pda, _new_collection, _collection_metadata, _collection_edition, _root, _data_hash, _creator_hash, _nonce, _index, _metadata, _proof, _options),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc false
+  def update_metadata(_tree_config, _authority, _collection_mint, _collection_metadata, _collection_authority_record_pda, _leaf_owner, _leaf_delegate, _payer, _merkle_tree, _root, _nonce, _index, _current_metadata, _update_args, _proof, _options),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc false
+  def redeem(_tree_config, _leaf_owner, _leaf_delegate, _merkle_tree, _voucher, _root, _data_hash, _creator_hash, _nonce, _index, _proof, _options),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc false
+  def cancel_redeem(_tree_config, _leaf_owner, _merkle_tree, _voucher, _root, _proof, _options),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc false
+  def decompress_v1(_voucher, _leaf_owner, _token_account, _mint, _mint_authority, _metadata_account, _master_edition, _associated_token_program, _system_program, _token_metadata_program, _metadata, _options),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc false
+  def set_decompressible_state(_tree_config, _tree_creator, _merkle_tree, _decompressable, _options),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc false
+  def default_metadata(_name, _symbol, _uri), do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc false
+  def metadata_to_borsh(_metadata), do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc false
+  def metadata_from_borsh(_bytes), do: :erlang.nif_error(:nif_not_loaded)
+
   @doc false
   def hash_metadata(_metadata),
     do: :erlang.nif_error(:nif_not_loaded)
 
+  @doc false
+  def hash_metadata_batch(_metadatas),
+    do: :erlang.nif_error(:nif_not_loaded)
+
   @doc false
   def hash_creators(_creators),
     do: :erlang.nif_error(:nif_not_loaded)
 
+  @doc false
+  def compute_leaf_hash(_metadata, _merkle_tree, _leaf_owner, _leaf_delegate, _nonce),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc false
+  def compute_leaf_node(_asset_id, _leaf_owner, _leaf_delegate, _nonce, _index, _data_hash, _creator_hash),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc false
+  def unsigned_with_digest(_transaction_binary),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc false
+  def decode_transaction(_transaction_binary),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc false
+  def decode_bubblegum_instruction(_data), do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc false
+  def transaction_to_base64(_transaction_binary), do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc false
+  def transaction_from_base64(_encoded), do: :erlang.nif_error(:nif_not_loaded)
+
   @doc false
   def get_asset_id(_tree, _nonce),
     do: :erlang.nif_error(:nif_not_loaded)
 
+  @doc false
+  def get_asset_id_batch(_tree, _nonces),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc false
+  def derive_tree_config_pda(_merkle_tree),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc false
+  def derive_asset_pda(_tree, _nonce),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc false
+  def derive_bubblegum_signer_pda, do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc false
+  def parse_tree_config(_data),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc false
+  def parse_merkle_tree_header(_data), do: :erlang.nif_error(:nif_not_loaded)
+
   @doc """
   Derives a public key from a secret key.
 
   Args:
-    - secret_key: Binary secret key (64 bytes)
+    - secret_key: Binary secret key (64 bytes) or its 32-byte seed
 
   Returns:
     - {:ok, pubkey} where pubkey is an ElixirPubkey struct
@@ -48,18 +188,120 @@ defmodule MplBubblegum.Native do
   def derive_pubkey_from_secret(_secret_key),
     do: :erlang.nif_error(:nif_not_loaded)
 
+  @doc """
+  Parses a keypair in the Solana CLI JSON format and returns its public key.
+
+  Args:
+    - json: JSON array of 64 integers, as written by solana-keygen
+
+  Returns:
+    - {:ok, pubkey} where pubkey is an ElixirPubkey struct
+    - {:error, reason} if an error occurs
+  """
+  def keypair_from_json(_json),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc false
+  def keypair_from_base58(_secret_base58), do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc """
+  Signs an arbitrary message with a keypair.
+
+  Args:
+    - secret_key: Binary secret key (64 bytes or Solana CLI JSON)
+    - message: Binary message to sign
+
+  Returns:
+    - {:ok, signature} where signature is the 64-byte ed25519 signature
+    - {:error, reason} if an error occurs
+  """
+  def sign_message(_secret_key, _message),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc """
+  Verifies an ed25519 signature over a message.
+
+  Args:
+    - pubkey: ElixirPubkey struct of the signer
+    - message: Binary message that was signed
+    - signature: Binary 64-byte signature
+
+  Returns:
+    - {:ok, valid} where valid is a boolean
+    - {:error, reason} if the signature is malformed
+  """
+  def verify_signature(_pubkey, _message, _signature),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc false
+  def pubkey_is_on_curve(_pubkey),
+    do: :erlang.nif_error(:nif_not_loaded)
+
   @doc """
   Signs and submits a transaction to the Solana network.
 
   Args:
     - transaction_binary: Binary serialized transaction
-    - payer_secret_key: Binary secret key of the payer (64 bytes)
+    - payer_secret_key: Binary secret keys (64 bytes or Solana CLI JSON) of the signers
+    - commitment: "processed", "confirmed" or "finalized" ("" defaults to "confirmed")
+    - max_retries: Number of times to re-sign with a fresh blockhash and resubmit after a failure
+    - skip_preflight: Whether to skip the preflight simulation
+
+  Returns:
+    - {:ok, signature} if successful
+    - {:error, reason} if an error occurs
+  """
+  def sign_and_submit_transaction(_rpc_url, _transaction_binary, _payer_secret_key, _commitment, _max_retries, _skip_preflight, _preflight_commitment),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc false
+  def invalidate_blockhash_cache, do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc """
+  Submits an already signed transaction without re-signing it.
+
+  Args:
+    - rpc_url: The RPC endpoint URL
+    - transaction_binary: Binary serialized, fully signed transaction
+    - skip_preflight: Whether to skip the preflight simulation
 
   Returns:
     - {:ok, signature} if successful
+    - {:error, reason} if the transaction is not fully signed or an error occurs
+  """
+  def send_raw_transaction(_rpc_url, _transaction_binary, _skip_preflight),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc false
+  def submit_transactions_batch(_rpc_url, _signed_transactions), do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc false
+  def refresh_and_submit(_rpc_url, _transaction_binary, _secret_keys), do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc """
+  Signs a transaction offline with the provided blockhash without submitting it.
+
+  Args:
+    - transaction_binary: Binary serialized transaction
+    - secret_keys: List of binary secret keys (64 bytes each)
+    - recent_blockhash: Base58-encoded blockhash string
+
+  Returns:
+    - {:ok, signed_transaction} if successful
     - {:error, reason} if an error occurs
   """
-  def sign_and_submit_transaction(_transaction_binary, _payer_secret_key),
+  def sign_transaction(_transaction_binary, _secret_keys, _recent_blockhash),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc false
+  def partial_sign_transaction(_transaction_binary, _secret_keys, _recent_blockhash),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc false
+  def verify_transaction_signatures(_transaction_binary), do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc false
+  def prepend_priority_fee(_transaction_binary, _compute_unit_limit, _compute_unit_price),
     do: :erlang.nif_error(:nif_not_loaded)
 
   @doc """
@@ -67,12 +309,28 @@ defmodule MplBubblegum.Native do
 
   Args:
     - signature: String representing the transaction signature
+    - commitment: "processed", "confirmed" or "finalized" ("" defaults to "confirmed")
 
   Returns:
     - {:ok, status} where status is "confirmed", "failed: <reason>", or "not_found"
     - {:error, reason} if an error occurs
   """
-  def get_transaction_status(_signature),
+  def get_transaction_status(_signature, _commitment),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc """
+  Retrieves a confirmed transaction with its status, program logs and fee.
+
+  Args:
+    - rpc_url: The RPC endpoint URL
+    - signature: String representing the transaction signature
+    - commitment: "confirmed" or "finalized" ("" defaults to "confirmed")
+
+  Returns:
+    - {:ok, details} where details is a TransactionDetails struct
+    - {:error, reason} if an error occurs or the transaction is not found
+  """
+  def get_transaction(_rpc_url, _signature, _commitment),
     do: :erlang.nif_error(:nif_not_loaded)
 
   @doc """
@@ -80,11 +338,164 @@ defmodule MplBubblegum.Native do
 
   Args:
     - pubkey: ElixirPubkey struct representing the account's public key
+    - commitment: "processed", "confirmed" or "finalized" ("" defaults to "confirmed")
+
+  Returns:
+    - {:ok, account_info} where account_info is an AccountInfo struct with the raw data as a byte list
+    - {:error, reason} if an error occurs
+  """
+  def get_account_info(_pubkey, _commitment),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc """
+  Retrieves information for several accounts in a single RPC round-trip.
+
+  Args:
+    - rpc_url: The RPC endpoint URL
+    - pubkeys: List of up to 100 ElixirPubkey structs
+
+  Returns:
+    - {:ok, accounts} where each entry is an AccountInfo struct, or nil if the account does not exist
+    - {:error, reason} if an error occurs
+  """
+  def batch_get_account_info(_rpc_url, _pubkeys),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc false
+  def get_program_accounts(_rpc_url, _program_id, _filters), do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc """
+  Retrieves the statuses of several transaction signatures in a single RPC request.
+
+  Args:
+    - rpc_url: The RPC endpoint URL
+    - signatures: List of up to 256 base58-encoded signature strings
 
   Returns:
-    - {:ok, account_info} where account_info is a map with lamports, owner, etc.
+    - {:ok, statuses} where each entry is a SignatureStatus struct, or nil if the signature is unknown
     - {:error, reason} if an error occurs
   """
-  def get_account_info(_pubkey),
+  def get_signature_statuses(_rpc_url, _signatures),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc """
+  Hashes a fixed metadata object repeatedly to measure hashing throughput.
+  Only available when the crate is compiled with the `benchmark` feature.
+
+  Args:
+    - iterations: Number of times to hash the metadata
+
+  Returns:
+    - {:ok, elapsed_microseconds, hashes_per_second} if successful
+    - {:error, reason} if an error occurs or the feature is disabled
+  """
+  def benchmark_hashing(_iterations),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc """
+  Checks whether a blockhash is still valid for transaction submission.
+
+  Args:
+    - blockhash: Base58-encoded blockhash string
+    - rpc_url: URL of the Solana RPC endpoint
+
+  Returns:
+    - {:ok, valid} where valid is a boolean
+    - {:error, reason} if an error occurs
+  """
+  def is_blockhash_valid(_blockhash, _rpc_url),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc false
+  def get_nonce_blockhash(_rpc_url, _nonce_account), do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc false
+  def get_tree_num_minted(_rpc_url, _tree_config), do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc false
+  def remaining_capacity(_rpc_url, _tree_config), do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc false
+  def get_token_account_balance(_rpc_url, _token_account), do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc false
+  def min_balance_for_rent_exemption(_rpc_url, _space),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc false
+  def estimate_fee(_rpc_url, _transaction_binary), do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc false
+  def estimate_compute_units(_rpc_url, _transaction_binary), do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc false
+  def get_recent_prioritization_fees(_rpc_url, _accounts), do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc false
+  def get_slot(_rpc_url, _commitment), do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc false
+  def get_block_height(_rpc_url, _commitment), do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc false
+  def get_health(_rpc_url), do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc false
+  def get_first_available_block(_rpc_url), do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc false
+  def request_airdrop(_rpc_url, _pubkey, _lamports),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc false
+  def bubblegum_program_id, do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc false
+  def account_compression_program_id, do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc false
+  def noop_program_id, do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc false
+  def merkle_tree_rent(_max_depth, _max_buffer_size, _canopy_depth),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc false
+  def tree_capacity(_max_depth), do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc """
+  Fetches an asset by its ID from a DAS API endpoint.
+
+  Args:
+    - das_url: URL of the DAS API endpoint
+    - asset_id: ElixirPubkey struct of the asset ID
+
+  Returns:
+    - {:ok, asset} where asset is the decoded DAS asset map
+    - {:error, reason} if an error occurs or the asset is not indexed yet
+  """
+  def get_asset(_das_url, _asset_id),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc false
+  def check_asset_owner(_das_url, _asset_id, _leaf_owner), do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc false
+  def get_asset_proof(_das_url, _asset_id),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc """
+  Computes the asset ID for a leaf and fetches the asset from a DAS API endpoint.
+
+  Args:
+    - das_url: URL of the DAS API endpoint
+    - tree: ElixirPubkey struct of the merkle tree
+    - leaf_index: Index of the leaf in the tree
+
+  Returns:
+    - {:ok, asset} where asset is the decoded DAS asset map
+    - {:error, reason} if an error occurs or the asset is not indexed yet
+  """
+  def get_asset_by_index(_das_url, _tree, _leaf_index),
     do: :erlang.nif_error(:nif_not_loaded)
 end
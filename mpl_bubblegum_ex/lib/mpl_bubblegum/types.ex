@@ -181,79 +181,275 @@ defmodule MplBubblegum.Types do
     def non_fungible_edition, do: 3
   end
 
-  defmodule AccountInfo do
+  defmodule MintRequest do
     @moduledoc """
-    Represents account information retrieved from the Solana network.
+    The accounts and metadata for one mint within a batch mint transaction.
     """
-    @enforce_keys [:lamports, :owner, :executable, :rent_epoch, :data_len]
-    defstruct [:lamports, :owner, :executable, :rent_epoch, :data_len]
+    @enforce_keys [
+      :tree_config,
+      :leaf_owner,
+      :leaf_delegate,
+      :merkle_tree,
+      :tree_creator_or_delegate,
+      :metadata
+    ]
+    defstruct [
+      :tree_config,
+      :leaf_owner,
+      :leaf_delegate,
+      :merkle_tree,
+      :tree_creator_or_delegate,
+      :metadata
+    ]
 
     @type t :: %__MODULE__{
-            lamports: non_neg_integer(),
-            owner: String.t(),
-            executable: boolean(),
-            rent_epoch: non_neg_integer(),
-            data_len: non_neg_integer()
+            tree_config: Pubkey.t(),
+            leaf_owner: Pubkey.t(),
+            leaf_delegate: Pubkey.t(),
+            merkle_tree: Pubkey.t(),
+            tree_creator_or_delegate: Pubkey.t(),
+            metadata: Metadata.t()
           }
+  end
 
-    @doc """
-    Creates an AccountInfo struct from a map returned by the native function.
+  defmodule TreeConfig do
+    @moduledoc """
+    Represents the decoded state of a tree config account.
+    """
+    @enforce_keys [
+      :tree_creator,
+      :tree_delegate,
+      :total_mint_capacity,
+      :num_minted,
+      :is_public,
+      :is_decompressible
+    ]
+    defstruct [
+      :tree_creator,
+      :tree_delegate,
+      :total_mint_capacity,
+      :num_minted,
+      :is_public,
+      :is_decompressible
+    ]
+
+    @type t :: %__MODULE__{
+            tree_creator: Pubkey.t(),
+            tree_delegate: Pubkey.t(),
+            total_mint_capacity: non_neg_integer(),
+            num_minted: non_neg_integer(),
+            is_public: boolean(),
+            is_decompressible: boolean()
+          }
+  end
 
-    ## Parameters
-    - map: A map with string keys "lamports", "owner", "executable", "rent_epoch", "data_len"
+  defmodule MerkleTreeHeader do
+    @moduledoc """
+    Represents the header of a concurrent merkle tree account.
 
-    ## Returns
-    - {:ok, AccountInfo.t()} if successful
-    - {:error, reason} if the map is invalid
+    The `authority` of a bubblegum tree is its tree config account.
     """
-    @spec from_map(map()) :: {:ok, t()} | {:error, String.t()}
-    def from_map(map) when is_map(map) do
-      with {:ok, lamports} <- get_integer(map, "lamports"),
-           {:ok, owner} <- get_string(map, "owner"),
-           {:ok, executable} <- get_boolean(map, "executable"),
-           {:ok, rent_epoch} <- get_integer(map, "rent_epoch"),
-           {:ok, data_len} <- get_integer(map, "data_len") do
-        {:ok, %__MODULE__{
-          lamports: lamports,
-          owner: owner,
-          executable: executable,
-          rent_epoch: rent_epoch,
-          data_len: data_len
-        }}
-      else
-        {:error, reason} -> {:error, reason}
-      end
-    end
+    @enforce_keys [:max_depth, :max_buffer_size, :authority, :creation_slot]
+    defstruct [:max_depth, :max_buffer_size, :authority, :creation_slot]
 
-    defp get_integer(map, key) do
-      case Map.get(map, key) do
-        nil -> {:error, "Missing #{key}"}
-        value when is_binary(value) ->
-          case Integer.parse(value) do
-            {int, ""} -> {:ok, int}
-            _ -> {:error, "Invalid integer for #{key}"}
-          end
-        value when is_integer(value) -> {:ok, value}
-        _ -> {:error, "Invalid type for #{key}"}
-      end
-    end
+    @type t :: %__MODULE__{
+            max_depth: non_neg_integer(),
+            max_buffer_size: non_neg_integer(),
+            authority: Pubkey.t(),
+            creation_slot: non_neg_integer()
+          }
+  end
 
-    defp get_string(map, key) do
-      case Map.get(map, key) do
-        nil -> {:error, "Missing #{key}"}
-        value when is_binary(value) -> {:ok, value}
-        _ -> {:error, "Invalid type for #{key}"}
-      end
-    end
+  defmodule AssetProof do
+    @moduledoc """
+    The merkle proof of a compressed asset as returned by the DAS `getAssetProof` method.
 
-    defp get_boolean(map, key) do
-      case Map.get(map, key) do
-        nil -> {:error, "Missing #{key}"}
-        value when is_boolean(value) -> {:ok, value}
-        "true" -> {:ok, true}
-        "false" -> {:ok, false}
-        _ -> {:error, "Invalid type for #{key}"}
-      end
+    The root, proof nodes, leaf and tree ID are base58-encoded; `node_index` is the
+    index of the leaf node in the full tree, not the leaf index.
+    """
+    @enforce_keys [:root, :proof, :node_index, :leaf, :tree_id]
+    defstruct [:root, :proof, :node_index, :leaf, :tree_id]
+
+    @type t :: %__MODULE__{
+            root: String.t(),
+            proof: [String.t()],
+            node_index: non_neg_integer(),
+            leaf: String.t(),
+            tree_id: String.t()
+          }
+  end
+
+  defmodule TokenAmount do
+    @moduledoc """
+    Represents the balance of an SPL token account.
+
+    `amount` is in base units; `ui_amount_string` divides it by `10^decimals`.
+    """
+    @enforce_keys [:amount, :decimals, :ui_amount_string]
+    defstruct [:amount, :decimals, :ui_amount_string]
+
+    @type t :: %__MODULE__{
+            amount: non_neg_integer(),
+            decimals: non_neg_integer(),
+            ui_amount_string: String.t()
+          }
+  end
+
+  defmodule SignatureStatus do
+    @moduledoc """
+    Represents the status of a submitted transaction signature.
+
+    `confirmations` counts the blocks confirming the transaction's slot and is
+    `nil` once that slot is rooted.
+    """
+    @enforce_keys [:slot, :confirmation_status]
+    defstruct [:slot, :confirmations, :confirmation_status, :err]
+
+    @type t :: %__MODULE__{
+            slot: non_neg_integer(),
+            confirmations: non_neg_integer() | nil,
+            confirmation_status: :processed | :confirmed | :finalized,
+            err: String.t() | nil
+          }
+  end
+
+  defmodule TransactionDetails do
+    @moduledoc """
+    The outcome of a confirmed transaction, including its program logs.
+    """
+    @enforce_keys [:slot, :log_messages]
+    defstruct [:slot, :err, :log_messages, :compute_units_consumed, :fee]
+
+    @type t :: %__MODULE__{
+            slot: non_neg_integer(),
+            err: String.t() | nil,
+            log_messages: [String.t()],
+            compute_units_consumed: non_neg_integer() | nil,
+            fee: non_neg_integer() | nil
+          }
+  end
+
+  defmodule UpdateArgs do
+    @moduledoc """
+    Represents a metadata update for a compressed NFT. Fields left as `nil` are unchanged.
+    """
+    defstruct [
+      :name,
+      :symbol,
+      :uri,
+      :creators,
+      :seller_fee_basis_points,
+      :primary_sale_happened,
+      :is_mutable
+    ]
+
+    @type t :: %__MODULE__{
+            name: String.t() | nil,
+            symbol: String.t() | nil,
+            uri: String.t() | nil,
+            creators: [Creator.t()] | nil,
+            seller_fee_basis_points: integer() | nil,
+            primary_sale_happened: boolean() | nil,
+            is_mutable: boolean() | nil
+          }
+  end
+
+  defmodule TransactionOptions do
+    @moduledoc """
+    Transaction-level options applied by the instruction builders.
+
+    * `compute_unit_limit` - Compute unit limit requested for the transaction
+    * `compute_unit_price` - Priority fee in micro-lamports per compute unit
+    * `signers` - Every pubkey that will eventually sign, so the message reserves
+      a signature slot for each even if they sign later
+    * `bubblegum_program_id`, `compression_program_id`, `noop_program_id` - Program
+      addresses to target instead of the canonical deployments, e.g. for forks on a
+      local validator
+    * `memo` - A memo recorded in the transaction, e.g. an order ID for reconciliation
+    * `nonce_account`, `nonce_authority` - A durable nonce to advance as the first instruction,
+      for transactions signed offline long after they were built; the authority defaults to
+      the fee payer
+    * `placeholder_fee_payer` - Leave the fee payer as the all-zero pubkey for a relayer to
+      replace before signing; the `payer` argument then only acts as an instruction account
+    """
+    @fields [
+      :compute_unit_limit,
+      :compute_unit_price,
+      :signers,
+      :bubblegum_program_id,
+      :compression_program_id,
+      :noop_program_id,
+      :memo,
+      :nonce_account,
+      :nonce_authority,
+      :placeholder_fee_payer
+    ]
+    defstruct @fields
+
+    @type t :: %__MODULE__{
+            compute_unit_limit: non_neg_integer() | nil,
+            compute_unit_price: non_neg_integer() | nil,
+            signers: [Pubkey.t()] | nil,
+            bubblegum_program_id: Pubkey.t() | nil,
+            compression_program_id: Pubkey.t() | nil,
+            noop_program_id: Pubkey.t() | nil,
+            memo: String.t() | nil,
+            nonce_account: Pubkey.t() | nil,
+            nonce_authority: Pubkey.t() | nil,
+            placeholder_fee_payer: boolean() | nil
+          }
+
+    @doc """
+    Builds transaction options from the optional keys of a params map.
+    """
+    @spec from_params(map()) :: t()
+    def from_params(params) when is_map(params) do
+      struct(__MODULE__, Map.take(params, @fields))
     end
   end
+
+  defmodule DecodedInstruction do
+    @moduledoc """
+    An instruction of a decoded transaction, with base58 pubkeys and base64 data.
+    """
+    @enforce_keys [:program_id, :accounts, :data]
+    defstruct [:program_id, :accounts, :data]
+
+    @type t :: %__MODULE__{
+            program_id: String.t(),
+            accounts: [String.t()],
+            data: String.t()
+          }
+  end
+
+  defmodule DecodedTransaction do
+    @moduledoc """
+    The contents of a serialized transaction, for inspecting what the builders produced.
+    """
+    @enforce_keys [:fee_payer, :recent_blockhash, :num_signatures, :instructions]
+    defstruct [:fee_payer, :recent_blockhash, :num_signatures, :instructions]
+
+    @type t :: %__MODULE__{
+            fee_payer: String.t() | nil,
+            recent_blockhash: String.t(),
+            num_signatures: non_neg_integer(),
+            instructions: [DecodedInstruction.t()]
+          }
+  end
+
+  defmodule AccountInfo do
+    @moduledoc """
+    Represents account information retrieved from the Solana network.
+    """
+    @enforce_keys [:lamports, :owner, :executable, :rent_epoch, :data]
+    defstruct [:lamports, :owner, :executable, :rent_epoch, :data]
+
+    @type t :: %__MODULE__{
+            lamports: non_neg_integer(),
+            owner: Pubkey.t(),
+            executable: boolean(),
+            rent_epoch: non_neg_integer(),
+            data: binary()
+          }
+  end
 end
@@ -0,0 +1,92 @@
+use serde_json::{json, Value};
+use solana_sdk::pubkey::Pubkey;
+use crate::error::Error;
+
+/// Issues a JSON-RPC request against a DAS (Digital Asset Standard) endpoint
+/// and returns the `result` field of the response.
+pub async fn call(das_url: &str, method: &str, params: Value) -> Result<Value, Error> {
+    let response = send(das_url, method, params).await?;
+    result(method, &response)
+}
+
+/// Issues a JSON-RPC request against a DAS endpoint and returns the whole
+/// response, so callers can tell the kinds of failure apart.
+async fn send(das_url: &str, method: &str, params: Value) -> Result<Value, Error> {
+    let body = json!({
+        "jsonrpc": "2.0",
+        "id": "mpl_bubblegum",
+        "method": method,
+        "params": params,
+    });
+
+    reqwest::Client::new()
+        .post(das_url)
+        .json(&body)
+        .send()
+        .await
+        .map_err(|e| Error::Das(format!("{} request failed: {}", method, e)))?
+        .json()
+        .await
+        .map_err(|e| Error::Das(format!("Invalid {} response: {}", method, e)))
+}
+
+fn result(method: &str, response: &Value) -> Result<Value, Error> {
+    if let Some(error) = response.get("error") {
+        return Err(Error::Das(format!("{} returned an error: {}", method, error)));
+    }
+
+    match response.get("result") {
+        Some(result) if !result.is_null() => Ok(result.clone()),
+        _ => Err(Error::Das(format!("{} returned no result", method))),
+    }
+}
+
+/// Fetches an asset by its ID via the DAS `getAsset` method.
+pub async fn get_asset(das_url: &str, asset_id: &Pubkey) -> Result<Value, Error> {
+    let response = send(das_url, "getAsset", json!({ "id": asset_id.to_string() })).await?;
+    if asset_not_found(&response) {
+        return Err(Error::Das(format!("Asset {} not found; it may not be indexed yet", asset_id)));
+    }
+    result("getAsset", &response)
+}
+
+/// Whether a `getAsset` response says the asset is unknown, either with a
+/// `null` result or with the not-found error DAS providers return, as
+/// opposed to failing for another reason.
+fn asset_not_found(response: &Value) -> bool {
+    match response.get("error") {
+        Some(error) => error
+            .get("message")
+            .and_then(Value::as_str)
+            .is_some_and(|message| message.to_lowercase().contains("not found")),
+        None => response.get("result").is_none_or(Value::is_null),
+    }
+}
+
+/// Fetches the current merkle proof of a compressed asset via the DAS
+/// `getAssetProof` method.
+pub async fn get_asset_proof(das_url: &str, asset_id: &Pubkey) -> Result<Value, Error> {
+    call(das_url, "getAssetProof", json!({ "id": asset_id.to_string() })).await
+}
+
+/// Reads the current owner from a `getAsset` response.
+pub fn asset_owner(asset: &Value) -> Result<Pubkey, Error> {
+    asset
+        .pointer("/ownership/owner")
+        .and_then(Value::as_str)
+        .and_then(|owner| owner.parse().ok())
+        .ok_or_else(|| Error::Das("getAsset response has no valid ownership.owner".to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_unknown_assets_are_reported_as_not_indexed() {
+        assert!(asset_not_found(&json!({ "jsonrpc": "2.0", "result": null })));
+        assert!(asset_not_found(&json!({ "error": { "code": -32000, "message": "Asset Not Found" } })));
+        assert!(!asset_not_found(&json!({ "error": { "code": -32603, "message": "Internal error" } })));
+        assert!(!asset_not_found(&json!({ "result": { "id": "asset" } })));
+    }
+}
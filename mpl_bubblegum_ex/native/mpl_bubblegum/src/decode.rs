@@ -0,0 +1,211 @@
+use borsh::BorshDeserialize;
+use mpl_bubblegum::{
+    instructions::{
+        BurnInstructionArgs, CancelRedeemInstructionArgs, CreateTreeConfigInstructionArgs, DecompressV1InstructionArgs,
+        DelegateInstructionArgs, MintToCollectionV1InstructionArgs, MintV1InstructionArgs, RedeemInstructionArgs,
+        SetAndVerifyCollectionInstructionArgs, SetDecompressibleStateInstructionArgs, TransferInstructionArgs,
+        UnverifyCollectionInstructionArgs, UpdateMetadataInstructionArgs, VerifyCollectionInstructionArgs,
+    },
+    types::{Creator, DecompressibleState, MetadataArgs, UpdateArgs},
+};
+use serde_json::{json, Value};
+use solana_sdk::bs58;
+use crate::error::Error;
+
+// Anchor discriminators of the bubblegum instructions, from the generated client
+const MINT_V1: [u8; 8] = [145, 98, 192, 118, 184, 147, 118, 104];
+const MINT_TO_COLLECTION_V1: [u8; 8] = [153, 18, 178, 47, 197, 158, 86, 15];
+const TRANSFER: [u8; 8] = [163, 52, 200, 231, 140, 3, 69, 186];
+const DELEGATE: [u8; 8] = [90, 147, 75, 178, 85, 88, 4, 137];
+const BURN: [u8; 8] = [116, 110, 29, 56, 107, 219, 42, 93];
+const REDEEM: [u8; 8] = [184, 12, 86, 149, 70, 196, 97, 225];
+const CANCEL_REDEEM: [u8; 8] = [111, 76, 232, 50, 39, 175, 48, 242];
+const DECOMPRESS_V1: [u8; 8] = [54, 85, 76, 70, 228, 250, 164, 81];
+const VERIFY_COLLECTION: [u8; 8] = [56, 113, 101, 253, 79, 55, 122, 169];
+const UNVERIFY_COLLECTION: [u8; 8] = [250, 251, 42, 106, 41, 137, 186, 168];
+const SET_AND_VERIFY_COLLECTION: [u8; 8] = [235, 242, 121, 216, 158, 234, 180, 234];
+const UPDATE_METADATA: [u8; 8] = [170, 182, 43, 239, 97, 78, 225, 186];
+const CREATE_TREE_CONFIG: [u8; 8] = [165, 83, 136, 142, 89, 202, 47, 220];
+const SET_DECOMPRESSIBLE_STATE: [u8; 8] = [82, 104, 152, 6, 149, 111, 100, 13];
+
+/// Decodes the data of a bubblegum instruction into its name and arguments,
+/// with hashes and pubkeys as base58 strings and metadata laid out like
+/// `MplBubblegum.Types.Metadata`. Returns `None` for an unknown discriminator.
+pub fn decode_bubblegum_instruction(data: &[u8]) -> Result<Option<(&'static str, Value)>, Error> {
+    if data.len() < 8 {
+        return Ok(None);
+    }
+    let (discriminator, data) = data.split_at(8);
+
+    let decoded = match <[u8; 8]>::try_from(discriminator).unwrap_or_default() {
+        MINT_V1 => ("mint_v1", json!({ "metadata": metadata(&args::<MintV1InstructionArgs>(data)?.metadata) })),
+        MINT_TO_COLLECTION_V1 => {
+            let args = args::<MintToCollectionV1InstructionArgs>(data)?;
+            ("mint_to_collection_v1", json!({ "metadata": metadata(&args.metadata) }))
+        }
+        TRANSFER => {
+            let args = args::<TransferInstructionArgs>(data)?;
+            ("transfer", leaf(args.root, args.data_hash, args.creator_hash, args.nonce, args.index))
+        }
+        DELEGATE => {
+            let args = args::<DelegateInstructionArgs>(data)?;
+            ("delegate", leaf(args.root, args.data_hash, args.creator_hash, args.nonce, args.index))
+        }
+        BURN => {
+            let args = args::<BurnInstructionArgs>(data)?;
+            ("burn", leaf(args.root, args.data_hash, args.creator_hash, args.nonce, args.index))
+        }
+        REDEEM => {
+            let args = args::<RedeemInstructionArgs>(data)?;
+            ("redeem", leaf(args.root, args.data_hash, args.creator_hash, args.nonce, args.index))
+        }
+        CANCEL_REDEEM => ("cancel_redeem", json!({ "root": base58(args::<CancelRedeemInstructionArgs>(data)?.root) })),
+        DECOMPRESS_V1 => {
+            let args = args::<DecompressV1InstructionArgs>(data)?;
+            ("decompress_v1", json!({ "metadata": metadata(&args.metadata) }))
+        }
+        VERIFY_COLLECTION => {
+            let args = args::<VerifyCollectionInstructionArgs>(data)?;
+            let mut decoded = leaf(args.root, args.data_hash, args.creator_hash, args.nonce, args.index);
+            decoded["metadata"] = metadata(&args.metadata);
+            ("verify_collection", decoded)
+        }
+        UNVERIFY_COLLECTION => {
+            let args = args::<UnverifyCollectionInstructionArgs>(data)?;
+            let mut decoded = leaf(args.root, args.data_hash, args.creator_hash, args.nonce, args.index);
+            decoded["metadata"] = metadata(&args.metadata);
+            ("unverify_collection", decoded)
+        }
+        SET_AND_VERIFY_COLLECTION => {
+            let args = args::<SetAndVerifyCollectionInstructionArgs>(data)?;
+            let mut decoded = leaf(args.root, args.data_hash, args.creator_hash, args.nonce, args.index);
+            decoded["metadata"] = metadata(&args.metadata);
+            decoded["collection"] = json!(args.collection.to_string());
+            ("set_and_verify_collection", decoded)
+        }
+        UPDATE_METADATA => {
+            let args = args::<UpdateMetadataInstructionArgs>(data)?;
+            let decoded = json!({
+                "root": base58(args.root),
+                "nonce": args.nonce,
+                "index": args.index,
+                "current_metadata": metadata(&args.current_metadata),
+                "update_args": update_args(&args.update_args),
+            });
+            ("update_metadata", decoded)
+        }
+        CREATE_TREE_CONFIG => {
+            let args = args::<CreateTreeConfigInstructionArgs>(data)?;
+            let decoded = json!({
+                "max_depth": args.max_depth,
+                "max_buffer_size": args.max_buffer_size,
+                "public": args.public,
+            });
+            ("create_tree_config", decoded)
+        }
+        SET_DECOMPRESSIBLE_STATE => {
+            let args = args::<SetDecompressibleStateInstructionArgs>(data)?;
+            let decompressable = args.decompressable_state == DecompressibleState::Enabled;
+            ("set_decompressible_state", json!({ "decompressable": decompressable }))
+        }
+        _ => return Ok(None),
+    };
+
+    Ok(Some(decoded))
+}
+
+fn args<T: BorshDeserialize>(data: &[u8]) -> Result<T, Error> {
+    T::try_from_slice(data).map_err(|e| Error::Deserialization(format!("Malformed instruction arguments: {}", e)))
+}
+
+fn base58(bytes: [u8; 32]) -> String {
+    bs58::encode(bytes).into_string()
+}
+
+fn leaf(root: [u8; 32], data_hash: [u8; 32], creator_hash: [u8; 32], nonce: u64, index: u32) -> Value {
+    json!({
+        "root": base58(root),
+        "data_hash": base58(data_hash),
+        "creator_hash": base58(creator_hash),
+        "nonce": nonce,
+        "index": index,
+    })
+}
+
+fn creators(creators: &[Creator]) -> Value {
+    creators
+        .iter()
+        .map(|creator| {
+            json!({
+                "address": creator.address.to_string(),
+                "verified": creator.verified,
+                "share": creator.share,
+            })
+        })
+        .collect()
+}
+
+fn metadata(metadata: &MetadataArgs) -> Value {
+    json!({
+        "name": metadata.name,
+        "symbol": metadata.symbol,
+        "uri": metadata.uri,
+        "seller_fee_basis_points": metadata.seller_fee_basis_points,
+        "primary_sale_happened": metadata.primary_sale_happened,
+        "is_mutable": metadata.is_mutable,
+        "edition_nonce": metadata.edition_nonce,
+        "token_standard": metadata.token_standard.clone().map(|standard| standard as u8),
+        "collection": metadata.collection.as_ref().map(|collection| {
+            json!({ "verified": collection.verified, "key": collection.key.to_string() })
+        }),
+        "uses": metadata.uses.as_ref().map(|uses| {
+            json!({ "use_method": uses.use_method.clone() as u8, "remaining": uses.remaining, "total": uses.total })
+        }),
+        "token_program_version": metadata.token_program_version.clone() as u8,
+        "creators": creators(&metadata.creators),
+    })
+}
+
+fn update_args(update_args: &UpdateArgs) -> Value {
+    json!({
+        "name": update_args.name,
+        "symbol": update_args.symbol,
+        "uri": update_args.uri,
+        "creators": update_args.creators.as_deref().map(creators),
+        "seller_fee_basis_points": update_args.seller_fee_basis_points,
+        "primary_sale_happened": update_args.primary_sale_happened,
+        "is_mutable": update_args.is_mutable,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mpl_bubblegum::instructions::TransferBuilder;
+    use solana_sdk::pubkey::Pubkey;
+
+    #[test]
+    fn transfer_arguments_are_decoded() {
+        let instruction = TransferBuilder::new()
+            .tree_config(Pubkey::new_unique())
+            .leaf_owner(Pubkey::new_unique(), true)
+            .leaf_delegate(Pubkey::new_unique(), false)
+            .new_leaf_owner(Pubkey::new_unique())
+            .merkle_tree(Pubkey::new_unique())
+            .root([1; 32])
+            .data_hash([2; 32])
+            .creator_hash([3; 32])
+            .nonce(7)
+            .index(7)
+            .instruction();
+
+        let (name, args) = decode_bubblegum_instruction(&instruction.data).unwrap().unwrap();
+        assert_eq!(name, "transfer");
+        assert_eq!(args["root"], base58([1; 32]));
+        assert_eq!(args["nonce"], 7);
+
+        assert!(decode_bubblegum_instruction(&[0; 8]).unwrap().is_none());
+        let truncated = &instruction.data[..instruction.data.len() - 1];
+        assert!(matches!(decode_bubblegum_instruction(truncated), Err(Error::Deserialization(_))));
+    }
+}
@@ -1,6 +1,18 @@
 use thiserror::Error;
-use rustler::Error as NifError;
-use solana_sdk::program_error::ProgramError;
+use rustler::{Encoder, Env, Error as NifError, Term};
+
+mod atoms {
+    rustler::atoms! {
+        solana_program,
+        borsh,
+        bubblegum,
+        invalid_parameter,
+        conversion,
+        das,
+        rpc,
+        deserialization,
+    }
+}
 
 #[derive(Error, Debug)]
 pub enum Error {
@@ -18,6 +30,33 @@ pub enum Error {
 
     #[error("Conversion error: {0}")]
     Conversion(String),
+
+    #[error("DAS API error: {0}")]
+    Das(String),
+
+    #[error("RPC error: {0}")]
+    Rpc(String),
+
+    #[error("Deserialization error: {0}")]
+    Deserialization(String),
+}
+
+/// Encodes the error as a `{kind, message}` tuple so Elixir callers can
+/// pattern match on the kind atom instead of parsing the message.
+impl Encoder for Error {
+    fn encode<'a>(&self, env: Env<'a>) -> Term<'a> {
+        let (kind, message) = match self {
+            Error::SolanaProgram(e) => (atoms::solana_program(), e.to_string()),
+            Error::Borsh(e) => (atoms::borsh(), e.to_string()),
+            Error::Bubblegum(msg) => (atoms::bubblegum(), msg.clone()),
+            Error::InvalidParameter(msg) => (atoms::invalid_parameter(), msg.clone()),
+            Error::Conversion(msg) => (atoms::conversion(), msg.clone()),
+            Error::Das(msg) => (atoms::das(), msg.clone()),
+            Error::Rpc(msg) => (atoms::rpc(), msg.clone()),
+            Error::Deserialization(msg) => (atoms::deserialization(), msg.clone()),
+        };
+        (kind, message).encode(env)
+    }
 }
 
 impl From<Error> for NifError {
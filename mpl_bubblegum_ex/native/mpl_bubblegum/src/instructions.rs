@@ -1,23 +1,179 @@
 use solana_sdk::pubkey::Pubkey;
 use solana_sdk::{
+    address_lookup_table::AddressLookupTableAccount,
+    compute_budget::ComputeBudgetInstruction,
+    hash::Hash,
+    keccak,
     instruction::{AccountMeta, Instruction},
-    transaction::Transaction,
-    message::Message,
+    signature::Signature,
+    transaction::{Transaction, VersionedTransaction},
+    message::{v0, Message, VersionedMessage},
+    packet::PACKET_DATA_SIZE,
     system_instruction,
     rent::Rent,
+    sanitize::Sanitize,
 };
 use mpl_bubblegum::{
     instructions::{
-        CreateTreeConfigBuilder, MintV1Builder, TransferBuilder,
+        BurnBuilder, CancelRedeemBuilder, CreateTreeConfigBuilder, DecompressV1Builder, DelegateBuilder, MintV1Builder, MintToCollectionV1Builder,
+        RedeemBuilder, SetAndVerifyCollectionBuilder, SetDecompressibleStateBuilder, TransferBuilder, UnverifyCollectionBuilder, UpdateMetadataBuilder, VerifyCollectionBuilder,
         MintV1InstructionArgs, TransferInstructionArgs,
     },
-    types::MetadataArgs,
+    accounts::TreeConfig,
+    types::{DecompressibleState, MetadataArgs, TokenProgramVersion, UpdateArgs},
     ID as BUBBLEGUM_ID,
 };
 use spl_account_compression::ID as SPL_ACCOUNT_COMPRESSION_ID;
-use spl_noop::ID as SPL_NOOP_ID;
 use crate::error::Error;
+use crate::utils::derive_bubblegum_signer_pda;
 
+/// Program ID of Metaplex Token Metadata, which owns collection metadata accounts.
+pub const MPL_TOKEN_METADATA_ID: Pubkey = solana_sdk::pubkey!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+
+/// Program ID of SPL Memo (v3), which logs its instruction data as UTF-8.
+pub const SPL_MEMO_ID: Pubkey = solana_sdk::pubkey!("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr");
+
+/// Program ID of SPL Token, used for `TokenProgramVersion::Original` leaves.
+pub const SPL_TOKEN_ID: Pubkey = solana_sdk::pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+
+/// Program ID of SPL Token-2022, used for `TokenProgramVersion::Token2022` leaves.
+pub const SPL_TOKEN_2022_ID: Pubkey = solana_sdk::pubkey!("TokenzQdBNbLqP5VEhdkAS6EHFLC1NwnkCfFrF7nVu5");
+
+/// Transaction-level options shared by all instruction builders.
+#[derive(Debug, Clone, Default)]
+pub struct TransactionOptions {
+    /// Compute unit limit to request for the transaction.
+    pub compute_unit_limit: Option<u32>,
+    /// Priority fee in micro-lamports per compute unit.
+    pub compute_unit_price: Option<u64>,
+    /// Every account that will eventually sign the transaction, so the
+    /// message reserves a signature slot for each of them up front.
+    pub signers: Vec<Pubkey>,
+    /// Program IDs to target instead of the canonical deployments.
+    pub program_ids: ProgramIds,
+    /// Memo recorded ahead of the program instructions, e.g. an order ID.
+    pub memo: Option<String>,
+    /// Durable nonce to advance, for transactions signed long after they were built.
+    pub durable_nonce: Option<DurableNonce>,
+    /// Leave the fee payer as `Pubkey::default()` for a relayer to fill in,
+    /// instead of having the payer argument pay.
+    pub placeholder_fee_payer: bool,
+}
+
+/// A nonce account whose stored blockhash replaces the recent blockhash.
+/// The transaction must then be signed against the nonce value instead of
+/// the latest blockhash.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DurableNonce {
+    pub account: Pubkey,
+    /// Authority allowed to advance the nonce, defaulting to the fee payer.
+    pub authority: Option<Pubkey>,
+}
+
+/// Addresses of the programs the builders target. Overriding them allows
+/// building transactions for forks deployed at custom addresses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProgramIds {
+    pub bubblegum: Pubkey,
+    pub compression: Pubkey,
+    pub noop: Pubkey,
+}
+
+impl Default for ProgramIds {
+    fn default() -> Self {
+        ProgramIds {
+            bubblegum: BUBBLEGUM_ID,
+            compression: SPL_ACCOUNT_COMPRESSION_ID,
+            noop: spl_noop::ID,
+        }
+    }
+}
+
+/// Returns the lamports the payer of `create_tree_config` transfers into the
+/// new tree config and merkle tree accounts to make them rent-exempt. The
+/// tree config is allocated by bubblegum at its full on-chain size.
+pub fn create_tree_config_rent(max_depth: u32, max_buffer_size: u32) -> u64 {
+    let rent = Rent::default();
+    rent.minimum_balance(TreeConfig::LEN)
+        + rent.minimum_balance(get_merkle_tree_size(max_depth, max_buffer_size, 0))
+}
+
+/// How to shrink an oversized transaction, by what made it large. Long proofs
+/// are the usual cause for instructions that carry one.
+const PROOF_SIZE_HINT: &str =
+    "build a v0 transaction with address lookup tables or pass the tree's canopy_depth to shorten the proof";
+const MINT_SIZE_HINT: &str = "shorten the metadata or drop optional instructions such as the memo";
+const BATCH_MINT_SIZE_HINT: &str = "split the batch into fewer mints";
+const OPTIONS_SIZE_HINT: &str = "drop optional instructions such as the memo";
+
+/// Lamports charged for each signature a transaction requires.
+const LAMPORTS_PER_SIGNATURE: u64 = 5_000;
+
+/// Compute units the runtime grants each instruction when a transaction sets
+/// no compute unit limit, and the most a transaction can be granted.
+const DEFAULT_INSTRUCTION_COMPUTE_UNIT_LIMIT: u64 = 200_000;
+const MAX_COMPUTE_UNIT_LIMIT: u64 = 1_400_000;
+
+/// Returns the lamports the fee payer of a serialized transaction is charged:
+/// the signature fee for every required signature plus the priority fee set by
+/// its compute budget instructions, rounded up to whole lamports.
+pub fn transaction_fee(transaction_bytes: &[u8]) -> Result<u64, Error> {
+    let transaction: VersionedTransaction = bincode::deserialize(transaction_bytes)
+        .map_err(|e| Error::Deserialization(format!("Failed to deserialize transaction: {}", e)))?;
+    let message = &transaction.message;
+
+    let mut compute_unit_limit = None;
+    let mut compute_unit_price = 0;
+    let mut instruction_count = 0;
+    for instruction in message.instructions() {
+        let program_id = message
+            .static_account_keys()
+            .get(usize::from(instruction.program_id_index))
+            .ok_or_else(|| Error::Deserialization("Malformed transaction: program ID out of range".to_string()))?;
+        if *program_id != solana_sdk::compute_budget::ID {
+            instruction_count += 1;
+            continue;
+        }
+        match solana_sdk::borsh1::try_from_slice_unchecked(&instruction.data) {
+            Ok(ComputeBudgetInstruction::SetComputeUnitLimit(limit)) => compute_unit_limit = Some(u64::from(limit)),
+            Ok(ComputeBudgetInstruction::SetComputeUnitPrice(price)) => compute_unit_price = price,
+            _ => {}
+        }
+    }
+
+    let compute_unit_limit = compute_unit_limit
+        .unwrap_or(DEFAULT_INSTRUCTION_COMPUTE_UNIT_LIMIT * instruction_count)
+        .min(MAX_COMPUTE_UNIT_LIMIT);
+    // The price is in micro-lamports per compute unit
+    let priority_fee = (u128::from(compute_unit_price) * u128::from(compute_unit_limit)).div_ceil(1_000_000);
+    let signature_fee = LAMPORTS_PER_SIGNATURE * u64::from(message.header().num_required_signatures);
+
+    Ok(signature_fee + priority_fee as u64)
+}
+
+/// Checks that a payer can cover both the rent and the fee of a serialized
+/// `create_tree_config` transaction, returning the total it needs.
+pub fn check_tree_funding(
+    balance: u64,
+    transaction_bytes: &[u8],
+    max_depth: u32,
+    max_buffer_size: u32,
+) -> Result<u64, Error> {
+    let required = create_tree_config_rent(max_depth, max_buffer_size) + transaction_fee(transaction_bytes)?;
+    if balance < required {
+        return Err(Error::InvalidParameter(format!(
+            "payer balance {} < required {}",
+            balance, required
+        )));
+    }
+    Ok(required)
+}
+
+/// Creates a transaction that creates and funds the merkle tree account and
+/// initializes the tree. `tree_config` must be the bubblegum PDA of the merkle
+/// tree, which bubblegum allocates itself, so only the merkle tree signs as a
+/// new account. `payer` funds both accounts and may differ from
+/// `tree_creator`, the recorded authority; both sign.
 pub fn create_tree_config(
     tree_config: Pubkey,
     merkle_tree: Pubkey,
@@ -26,37 +182,78 @@ pub fn create_tree_config(
     max_depth: u32,
     max_buffer_size: u32,
     public: Option<bool>,
+    extra_accounts: &[AccountMeta],
+    options: &TransactionOptions,
 ) -> Result<Vec<u8>, Error> {
     let rent = Rent::default();
 
-    // Space and rent for tree_config
-    let tree_config_space = 8 + 32 + 1; // Discriminator + pubkey + bool (simplified)
-    let tree_config_lamports = rent.minimum_balance(tree_config_space);
-
     // Space and rent for merkle_tree
-    let merkle_tree_space = get_merkle_tree_size(max_depth, max_buffer_size);
+    let merkle_tree_space = get_merkle_tree_size(max_depth, max_buffer_size, 0);
     let merkle_tree_lamports = rent.minimum_balance(merkle_tree_space);
 
     let mut instructions = vec![
-        // Create tree_config account
-        system_instruction::create_account(
-            &payer,
-            &tree_config,
-            tree_config_lamports,
-            tree_config_space as u64,
-            &BUBBLEGUM_ID,
-        ),
         // Create merkle_tree account
         system_instruction::create_account(
             &payer,
             &merkle_tree,
             merkle_tree_lamports,
             merkle_tree_space as u64,
-            &SPL_ACCOUNT_COMPRESSION_ID,
+            &options.program_ids.compression,
         ),
     ];
 
     // Add the create_tree_config instruction
+    instructions.push(create_tree_config_instruction(
+        tree_config,
+        merkle_tree,
+        payer,
+        tree_creator,
+        max_depth,
+        max_buffer_size,
+        public,
+        extra_accounts,
+    ));
+
+    build_transaction(instructions, &payer, options, OPTIONS_SIZE_HINT)
+}
+
+/// Creates a transaction with only the create_tree_config instruction, for
+/// callers that create and fund the merkle tree account themselves.
+pub fn create_tree_config_instruction_only(
+    tree_config: Pubkey,
+    merkle_tree: Pubkey,
+    payer: Pubkey,
+    tree_creator: Pubkey,
+    max_depth: u32,
+    max_buffer_size: u32,
+    public: Option<bool>,
+    extra_accounts: &[AccountMeta],
+    options: &TransactionOptions,
+) -> Result<Vec<u8>, Error> {
+    let instruction = create_tree_config_instruction(
+        tree_config,
+        merkle_tree,
+        payer,
+        tree_creator,
+        max_depth,
+        max_buffer_size,
+        public,
+        extra_accounts,
+    );
+
+    build_transaction(vec![instruction], &payer, options, OPTIONS_SIZE_HINT)
+}
+
+fn create_tree_config_instruction(
+    tree_config: Pubkey,
+    merkle_tree: Pubkey,
+    payer: Pubkey,
+    tree_creator: Pubkey,
+    max_depth: u32,
+    max_buffer_size: u32,
+    public: Option<bool>,
+    extra_accounts: &[AccountMeta],
+) -> Instruction {
     let mut builder = CreateTreeConfigBuilder::new();
     builder
         .tree_config(tree_config)
@@ -69,19 +266,14 @@ pub fn create_tree_config(
     if let Some(public_value) = public {
         builder.public(public_value);
     }
+    builder.add_remaining_accounts(extra_accounts);
 
-    let instruction = builder.instruction();
-    instructions.push(instruction);
-
-    // Create a Message from the Instructions
-    let message = Message::new(&instructions, Some(&payer));
-    let transaction = Transaction::new_unsigned(message);
-
-    bincode::serialize(&transaction)
-        .map_err(|e| Error::Conversion(format!("Failed to serialize transaction: {}", e)))
+    builder.instruction()
 }
 
-/// Creates a transaction for minting a compressed NFT.
+/// Creates a transaction for minting a compressed NFT. The tree creator or
+/// delegate signs alongside the payer when the two differ. `extra_instructions`
+/// follow the mint in the same transaction, so they succeed or fail with it.
 pub fn mint_v1(
     tree_config: Pubkey,
     leaf_owner: Pubkey,
@@ -90,7 +282,11 @@ pub fn mint_v1(
     payer: Pubkey,
     tree_creator_or_delegate: Pubkey,
     metadata: MetadataArgs,
+    extra_accounts: &[AccountMeta],
+    extra_instructions: &[Instruction],
+    options: &TransactionOptions,
 ) -> Result<Vec<u8>, Error> {
+    check_unverified_collection(&metadata)?;
     let args = MintV1InstructionArgs { metadata };
 
     let mut builder = MintV1Builder::new();
@@ -101,19 +297,114 @@ pub fn mint_v1(
         .merkle_tree(merkle_tree)
         .payer(payer)
         .tree_creator_or_delegate(tree_creator_or_delegate)
-        .metadata(args.metadata);
+        .metadata(args.metadata)
+        .add_remaining_accounts(extra_accounts);
 
-    let instruction = builder.instruction();
+    let mut instructions = vec![builder.instruction()];
+    instructions.extend_from_slice(extra_instructions);
 
-    // Create a Message from the Instruction
-    let message = Message::new(&[instruction], Some(&payer)); // Payer as fee payer
+    build_transaction(instructions, &payer, options, MINT_SIZE_HINT) // Payer as fee payer
+}
 
-    // Create a Transaction
-    let transaction = Transaction::new_unsigned(message);
+/// Accounts and metadata for a single mint within a batch.
+#[derive(Debug, Clone)]
+pub struct MintRequest {
+    pub tree_config: Pubkey,
+    pub leaf_owner: Pubkey,
+    pub leaf_delegate: Pubkey,
+    pub merkle_tree: Pubkey,
+    pub tree_creator_or_delegate: Pubkey,
+    pub metadata: MetadataArgs,
+}
 
-    // Serialize the transaction
-    bincode::serialize(&transaction)
-        .map_err(|e| Error::Conversion(format!("Failed to serialize transaction: {}", e)))
+/// Creates a single transaction containing one MintV1 instruction per request,
+/// all paid for by `payer`.
+pub fn batch_mint(
+    payer: Pubkey,
+    mints: Vec<MintRequest>,
+    options: &TransactionOptions,
+) -> Result<Vec<u8>, Error> {
+    if mints.is_empty() {
+        return Err(Error::InvalidParameter("at least one mint is required".to_string()));
+    }
+
+    for mint in &mints {
+        check_unverified_collection(&mint.metadata)?;
+    }
+
+    let instructions = mints
+        .into_iter()
+        .map(|mint| {
+            MintV1Builder::new()
+                .tree_config(mint.tree_config)
+                .leaf_owner(mint.leaf_owner)
+                .leaf_delegate(mint.leaf_delegate)
+                .merkle_tree(mint.merkle_tree)
+                .payer(payer)
+                .tree_creator_or_delegate(mint.tree_creator_or_delegate)
+                .metadata(mint.metadata)
+                .instruction()
+        })
+        .collect();
+
+    build_transaction(instructions, &payer, options, BATCH_MINT_SIZE_HINT)
+}
+
+/// Creates a transaction for minting a compressed NFT into a verified collection.
+pub fn mint_to_collection_v1(
+    tree_config: Pubkey,
+    leaf_owner: Pubkey,
+    leaf_delegate: Pubkey,
+    merkle_tree: Pubkey,
+    payer: Pubkey,
+    tree_creator_or_delegate: Pubkey,
+    collection_authority: Pubkey,
+    collection_authority_record_pda: Option<Pubkey>,
+    collection_mint: Pubkey,
+    collection_metadata: Pubkey,
+    collection_edition: Pubkey,
+    metadata: MetadataArgs,
+    extra_accounts: &[AccountMeta],
+    options: &TransactionOptions,
+) -> Result<Vec<u8>, Error> {
+    match &metadata.collection {
+        Some(collection) if collection.key == collection_mint => {}
+        Some(collection) => {
+            return Err(Error::InvalidParameter(format!(
+                "metadata collection {} does not match collection_mint {}",
+                collection.key, collection_mint
+            )))
+        }
+        None => {
+            return Err(Error::InvalidParameter(
+                "metadata collection must be set for mint_to_collection_v1".to_string(),
+            ))
+        }
+    }
+
+    let (bubblegum_signer, _) = derive_bubblegum_signer_pda(&options.program_ids.bubblegum);
+
+    let mut builder = MintToCollectionV1Builder::new();
+    builder
+        .tree_config(tree_config)
+        .leaf_owner(leaf_owner)
+        .leaf_delegate(leaf_delegate)
+        .merkle_tree(merkle_tree)
+        .payer(payer)
+        .tree_creator_or_delegate(tree_creator_or_delegate)
+        .collection_authority(collection_authority)
+        .collection_authority_record_pda(collection_authority_record_pda)
+        .collection_mint(collection_mint)
+        .collection_metadata(collection_metadata)
+        .collection_edition(collection_edition)
+        .bubblegum_signer(bubblegum_signer)
+        .token_metadata_program(MPL_TOKEN_METADATA_ID)
+        .metadata(metadata)
+        .add_remaining_accounts(extra_accounts);
+
+    let instruction = builder.instruction();
+
+    build_transaction(vec![instruction], &payer, options, MINT_SIZE_HINT) // Payer as fee payer
 }
 
 /// Creates a transaction for transferring a compressed NFT.
@@ -123,12 +414,332 @@ pub fn transfer(
     leaf_delegate: Pubkey,
     new_leaf_owner: Pubkey,
     merkle_tree: Pubkey,
+    payer: Pubkey,
+    root: [u8; 32],
+    data_hash: [u8; 32],
+    creator_hash: [u8; 32],
+    nonce: u64,
+    index: u32,
+    leaf_owner_is_signer: bool,
+    leaf_delegate_is_signer: bool,
+    proof: &[[u8; 32]],
+    canopy_depth: u32,
+    extra_accounts: &[AccountMeta],
+    options: &TransactionOptions,
+) -> Result<Vec<u8>, Error> {
+    let instruction = transfer_instruction(
+        tree_config,
+        leaf_owner,
+        leaf_delegate,
+        new_leaf_owner,
+        merkle_tree,
+        root,
+        data_hash,
+        creator_hash,
+        nonce,
+        index,
+        leaf_owner_is_signer,
+        leaf_delegate_is_signer,
+        proof,
+        canopy_depth,
+        extra_accounts,
+    )?;
+
+    build_transaction(vec![instruction], &payer, options, PROOF_SIZE_HINT)
+}
+
+/// The merkle proof of a compressed asset as returned by the DAS
+/// `getAssetProof` method, decoded from base58.
+#[derive(Debug, Clone)]
+pub struct AssetProof {
+    pub tree: Pubkey,
+    pub root: [u8; 32],
+    pub proof: Vec<[u8; 32]>,
+    pub node_index: u64,
+    pub leaf: [u8; 32],
+}
+
+/// Creates an owner-signed transfer from a DAS asset proof. The proof holds
+/// one node per tree level, so the leaf index follows from its node index. The
+/// proof's leaf must match the given owner, delegate and hashes, which catches
+/// stale ownership before the transaction reaches the cluster.
+pub fn transfer_from_proof(
+    asset_proof: &AssetProof,
+    leaf_owner: Pubkey,
+    leaf_delegate: Pubkey,
+    new_leaf_owner: Pubkey,
+    payer: Pubkey,
+    data_hash: [u8; 32],
+    creator_hash: [u8; 32],
+    canopy_depth: u32,
+    options: &TransactionOptions,
+) -> Result<Vec<u8>, Error> {
+    let leaf = ProofLeaf::new(asset_proof, leaf_owner, leaf_delegate, data_hash, creator_hash, options)?;
+
+    transfer(
+        leaf.tree_config,
+        leaf_owner,
+        leaf_delegate,
+        new_leaf_owner,
+        asset_proof.tree,
+        payer,
+        asset_proof.root,
+        data_hash,
+        creator_hash,
+        leaf.nonce,
+        leaf.index,
+        true,
+        false,
+        &asset_proof.proof,
+        canopy_depth,
+        &[],
+        options,
+    )
+}
+
+/// Creates a single owner-signed transaction that delegates a compressed NFT
+/// and then transfers it, e.g. to settle a marketplace sale atomically.
+///
+/// The delegation replaces the leaf, so the transfer has to prove the new
+/// leaf, whose delegate is `new_leaf_delegate`, against the root that results
+/// from it. Both follow from the full DAS proof, since the sibling nodes stay
+/// the same, which is why the proof must also hash up to `asset_proof.root`.
+/// The leaf owner pays the fee, as for `delegate`.
+pub fn delegate_and_transfer(
+    asset_proof: &AssetProof,
+    leaf_owner: Pubkey,
+    previous_leaf_delegate: Pubkey,
+    new_leaf_delegate: Pubkey,
+    new_leaf_owner: Pubkey,
+    data_hash: [u8; 32],
+    creator_hash: [u8; 32],
+    canopy_depth: u32,
+    options: &TransactionOptions,
+) -> Result<Vec<u8>, Error> {
+    let leaf = ProofLeaf::new(asset_proof, leaf_owner, previous_leaf_delegate, data_hash, creator_hash, options)?;
+    if recompute_root(asset_proof.leaf, &asset_proof.proof, leaf.index) != asset_proof.root {
+        return Err(Error::InvalidParameter(format!(
+            "proof of leaf {} does not hash to the given root",
+            leaf.index
+        )));
+    }
+    let delegated_leaf = crate::utils::compute_leaf_node(
+        leaf.asset_id,
+        leaf_owner,
+        new_leaf_delegate,
+        leaf.nonce,
+        data_hash,
+        creator_hash,
+    );
+    let delegated_root = recompute_root(delegated_leaf, &asset_proof.proof, leaf.index);
+    let proof = truncate_proof(&asset_proof.proof, canopy_depth);
+
+    let mut builder = DelegateBuilder::new();
+    builder
+        .tree_config(leaf.tree_config)
+        .leaf_owner(leaf_owner)
+        .previous_leaf_delegate(previous_leaf_delegate)
+        .new_leaf_delegate(new_leaf_delegate)
+        .merkle_tree(asset_proof.tree)
+        .root(asset_proof.root)
+        .data_hash(data_hash)
+        .creator_hash(creator_hash)
+        .nonce(leaf.nonce)
+        .index(leaf.index)
+        .add_remaining_accounts(&remaining_accounts(proof, &[]));
+
+    let transfer = transfer_instruction(
+        leaf.tree_config,
+        leaf_owner,
+        new_leaf_delegate,
+        new_leaf_owner,
+        asset_proof.tree,
+        delegated_root,
+        data_hash,
+        creator_hash,
+        leaf.nonce,
+        leaf.index,
+        true,
+        false,
+        proof,
+        0,
+        &[],
+    )?;
+
+    build_transaction(vec![builder.instruction(), transfer], &leaf_owner, options, PROOF_SIZE_HINT)
+}
+
+/// The leaf of a DAS asset proof, checked against the fields it is expected
+/// to hash from.
+struct ProofLeaf {
+    tree_config: Pubkey,
+    asset_id: Pubkey,
+    nonce: u64,
+    index: u32,
+}
+
+impl ProofLeaf {
+    fn new(
+        asset_proof: &AssetProof,
+        leaf_owner: Pubkey,
+        leaf_delegate: Pubkey,
+        data_hash: [u8; 32],
+        creator_hash: [u8; 32],
+        options: &TransactionOptions,
+    ) -> Result<Self, Error> {
+        let max_depth = asset_proof.proof.len() as u32;
+        if max_depth == 0 || max_depth > 30 {
+            return Err(Error::InvalidParameter(format!("Invalid proof length: {}", max_depth)));
+        }
+        let first_leaf = 1u64 << max_depth;
+        if asset_proof.node_index < first_leaf || asset_proof.node_index >= first_leaf << 1 {
+            return Err(Error::InvalidParameter(format!(
+                "node_index {} is not a leaf of a tree of depth {}",
+                asset_proof.node_index, max_depth
+            )));
+        }
+        // Leaves are numbered from 2^depth in DAS, and bubblegum mints each leaf with its index as nonce
+        let index = (asset_proof.node_index - first_leaf) as u32;
+        let nonce = u64::from(index);
+
+        let program_id = &options.program_ids.bubblegum;
+        let tree = asset_proof.tree;
+        let (tree_config, _) = Pubkey::find_program_address(&[tree.as_ref()], program_id);
+        let (asset_id, _) = Pubkey::find_program_address(&[b"asset", tree.as_ref(), &nonce.to_le_bytes()], program_id);
+        let leaf = crate::utils::compute_leaf_node(asset_id, leaf_owner, leaf_delegate, nonce, data_hash, creator_hash);
+        if leaf != asset_proof.leaf {
+            return Err(Error::InvalidParameter(format!(
+                "leaf {} of the proof does not match the given owner, delegate and hashes",
+                index
+            )));
+        }
+
+        Ok(Self { tree_config, asset_id, nonce, index })
+    }
+}
+
+/// Hashes a leaf up to the root of its tree along a full proof, the same way
+/// the account compression program does.
+fn recompute_root(leaf: [u8; 32], proof: &[[u8; 32]], index: u32) -> [u8; 32] {
+    proof.iter().enumerate().fold(leaf, |node, (depth, sibling)| {
+        let (left, right) = if index >> depth & 1 == 0 { (&node, sibling) } else { (sibling, &node) };
+        keccak::hashv(&[left, right]).to_bytes()
+    })
+}
+
+/// Creates a transfer signed by an explicit set of parties, e.g. a delegate
+/// authorizing an escrow release while a relayer pays the fee. The leaf owner
+/// and delegate sign if they are among `signers`, and every signer gets a
+/// signature slot in the message.
+pub fn transfer_with_signers(
+    tree_config: Pubkey,
+    leaf_owner: Pubkey,
+    leaf_delegate: Pubkey,
+    new_leaf_owner: Pubkey,
+    merkle_tree: Pubkey,
+    payer: Pubkey,
+    root: [u8; 32],
+    data_hash: [u8; 32],
+    creator_hash: [u8; 32],
+    nonce: u64,
+    index: u32,
+    signers: &[Pubkey],
+    proof: &[[u8; 32]],
+    canopy_depth: u32,
+    extra_accounts: &[AccountMeta],
+    options: &TransactionOptions,
+) -> Result<Vec<u8>, Error> {
+    let mut options = options.clone();
+    options.signers.extend_from_slice(signers);
+
+    transfer(
+        tree_config,
+        leaf_owner,
+        leaf_delegate,
+        new_leaf_owner,
+        merkle_tree,
+        payer,
+        root,
+        data_hash,
+        creator_hash,
+        nonce,
+        index,
+        signers.contains(&leaf_owner),
+        signers.contains(&leaf_delegate),
+        proof,
+        canopy_depth,
+        extra_accounts,
+        &options,
+    )
+}
+
+/// Creates a versioned (v0) transaction for transferring a compressed NFT,
+/// resolving accounts through the given address lookup tables. Proofs of deep
+/// trees only fit in a transaction when their nodes come from a lookup table.
+pub fn transfer_v0(
+    tree_config: Pubkey,
+    leaf_owner: Pubkey,
+    leaf_delegate: Pubkey,
+    new_leaf_owner: Pubkey,
+    merkle_tree: Pubkey,
+    payer: Pubkey,
     root: [u8; 32],
     data_hash: [u8; 32],
     creator_hash: [u8; 32],
     nonce: u64,
     index: u32,
+    leaf_owner_is_signer: bool,
+    leaf_delegate_is_signer: bool,
+    proof: &[[u8; 32]],
+    canopy_depth: u32,
+    extra_accounts: &[AccountMeta],
+    lookup_tables: &[AddressLookupTableAccount],
+    options: &TransactionOptions,
 ) -> Result<Vec<u8>, Error> {
+    let instruction = transfer_instruction(
+        tree_config,
+        leaf_owner,
+        leaf_delegate,
+        new_leaf_owner,
+        merkle_tree,
+        root,
+        data_hash,
+        creator_hash,
+        nonce,
+        index,
+        leaf_owner_is_signer,
+        leaf_delegate_is_signer,
+        proof,
+        canopy_depth,
+        extra_accounts,
+    )?;
+
+    build_versioned_transaction(vec![instruction], &payer, lookup_tables, options)
+}
+
+fn transfer_instruction(
+    tree_config: Pubkey,
+    leaf_owner: Pubkey,
+    leaf_delegate: Pubkey,
+    new_leaf_owner: Pubkey,
+    merkle_tree: Pubkey,
+    root: [u8; 32],
+    data_hash: [u8; 32],
+    creator_hash: [u8; 32],
+    nonce: u64,
+    index: u32,
+    leaf_owner_is_signer: bool,
+    leaf_delegate_is_signer: bool,
+    proof: &[[u8; 32]],
+    canopy_depth: u32,
+    extra_accounts: &[AccountMeta],
+) -> Result<Instruction, Error> {
+    if !leaf_owner_is_signer && !leaf_delegate_is_signer {
+        return Err(Error::InvalidParameter(
+            "either leaf_owner or leaf_delegate must be a signer".to_string(),
+        ));
+    }
+
     let _args = TransferInstructionArgs {
         root,
         data_hash,
@@ -140,34 +751,1598 @@ pub fn transfer(
     let mut builder = TransferBuilder::new();
     builder
         .tree_config(tree_config)
-        .leaf_owner(leaf_owner, true) // Assuming leaf_owner is a signer
-        .leaf_delegate(leaf_delegate, false) // Assuming leaf_delegate is not a signer
+        .leaf_owner(leaf_owner, leaf_owner_is_signer)
+        .leaf_delegate(leaf_delegate, leaf_delegate_is_signer)
         .new_leaf_owner(new_leaf_owner)
         .merkle_tree(merkle_tree)
         .root(root)
         .data_hash(data_hash)
         .creator_hash(creator_hash)
         .nonce(nonce)
-        .index(index);
+        .index(index)
+        .add_remaining_accounts(&remaining_accounts(
+            truncate_proof(proof, canopy_depth),
+            extra_accounts,
+        ));
+
+    Ok(builder.instruction())
+}
+
+/// Creates a transaction for setting the delegate of a compressed NFT.
+pub fn delegate(
+    tree_config: Pubkey,
+    leaf_owner: Pubkey,
+    previous_leaf_delegate: Pubkey,
+    new_leaf_delegate: Pubkey,
+    merkle_tree: Pubkey,
+    root: [u8; 32],
+    data_hash: [u8; 32],
+    creator_hash: [u8; 32],
+    nonce: u64,
+    index: u32,
+    proof: &[[u8; 32]],
+    canopy_depth: u32,
+    options: &TransactionOptions,
+) -> Result<Vec<u8>, Error> {
+    let mut builder = DelegateBuilder::new();
+    builder
+        .tree_config(tree_config)
+        .leaf_owner(leaf_owner)
+        .previous_leaf_delegate(previous_leaf_delegate)
+        .new_leaf_delegate(new_leaf_delegate)
+        .merkle_tree(merkle_tree)
+        .root(root)
+        .data_hash(data_hash)
+        .creator_hash(creator_hash)
+        .nonce(nonce)
+        .index(index)
+        .add_remaining_accounts(&remaining_accounts(truncate_proof(proof, canopy_depth), &[]));
 
     let instruction = builder.instruction();
 
-    // Create a Message from the Instruction
-    let message = Message::new(&[instruction], Some(&leaf_owner)); // Leaf owner as fee payer
+    build_transaction(vec![instruction], &leaf_owner, options, PROOF_SIZE_HINT) // Leaf owner as fee payer
+}
 
-    // Create a Transaction
-    let transaction = Transaction::new_unsigned(message);
+/// Creates a transaction that makes an escrow the delegate of a compressed NFT
+/// that has no other delegate, so the escrow can transfer it on a sale.
+pub fn escrow_delegate(
+    tree_config: Pubkey,
+    leaf_owner: Pubkey,
+    escrow: Pubkey,
+    merkle_tree: Pubkey,
+    root: [u8; 32],
+    data_hash: [u8; 32],
+    creator_hash: [u8; 32],
+    nonce: u64,
+    index: u32,
+    proof: &[[u8; 32]],
+    canopy_depth: u32,
+    options: &TransactionOptions,
+) -> Result<Vec<u8>, Error> {
+    delegate(
+        tree_config, leaf_owner, leaf_owner, escrow, merkle_tree, root, data_hash, creator_hash, nonce, index,
+        proof, canopy_depth, options,
+    )
+}
 
-    // Serialize the transaction
-    bincode::serialize(&transaction)
-        .map_err(|e| Error::Conversion(format!("Failed to serialize transaction: {}", e)))
+/// Creates a transaction that takes the delegation back from an escrow,
+/// leaving the leaf owner as its own delegate. Like any delegation it is
+/// signed by the leaf owner.
+pub fn release_delegate(
+    tree_config: Pubkey,
+    leaf_owner: Pubkey,
+    escrow: Pubkey,
+    merkle_tree: Pubkey,
+    root: [u8; 32],
+    data_hash: [u8; 32],
+    creator_hash: [u8; 32],
+    nonce: u64,
+    index: u32,
+    proof: &[[u8; 32]],
+    canopy_depth: u32,
+    options: &TransactionOptions,
+) -> Result<Vec<u8>, Error> {
+    delegate(
+        tree_config, leaf_owner, escrow, leaf_owner, merkle_tree, root, data_hash, creator_hash, nonce, index,
+        proof, canopy_depth, options,
+    )
 }
 
-/// Helper function to calculate the size needed for a merkle tree account
-fn get_merkle_tree_size(max_depth: u32, max_buffer_size: u32) -> usize {
-    let header_size = 8 + 32 + 32; // Discriminator + pubkey + misc
-    let canopy_size = (1 << (max_depth - 1)) * 32; // Simplified canopy
-    let tree_size = (1 << (max_depth + 1)) * 32; // Nodes
-    let buffer_size = max_buffer_size as usize * 32;
-    header_size + canopy_size + tree_size + buffer_size
-}
\ No newline at end of file
+/// Creates a transaction that burns a compressed NFT. Either the leaf owner
+/// or the leaf delegate authorizes the burn, while `payer` pays the fee.
+pub fn burn(
+    tree_config: Pubkey,
+    leaf_owner: Pubkey,
+    leaf_delegate: Pubkey,
+    merkle_tree: Pubkey,
+    payer: Pubkey,
+    root: [u8; 32],
+    data_hash: [u8; 32],
+    creator_hash: [u8; 32],
+    nonce: u64,
+    index: u32,
+    leaf_owner_is_signer: bool,
+    leaf_delegate_is_signer: bool,
+    proof: &[[u8; 32]],
+    canopy_depth: u32,
+    extra_accounts: &[AccountMeta],
+    options: &TransactionOptions,
+) -> Result<Vec<u8>, Error> {
+    if !leaf_owner_is_signer && !leaf_delegate_is_signer {
+        return Err(Error::InvalidParameter(
+            "either leaf_owner or leaf_delegate must be a signer".to_string(),
+        ));
+    }
+
+    let mut builder = BurnBuilder::new();
+    builder
+        .tree_config(tree_config)
+        .leaf_owner(leaf_owner, leaf_owner_is_signer)
+        .leaf_delegate(leaf_delegate, leaf_delegate_is_signer)
+        .merkle_tree(merkle_tree)
+        .root(root)
+        .data_hash(data_hash)
+        .creator_hash(creator_hash)
+        .nonce(nonce)
+        .index(index)
+        .add_remaining_accounts(&remaining_accounts(
+            truncate_proof(proof, canopy_depth),
+            extra_accounts,
+        ));
+
+    let instruction = builder.instruction();
+
+    build_transaction(vec![instruction], &payer, options, PROOF_SIZE_HINT)
+}
+
+/// Creates a transaction that verifies (or unverifies) the collection of a compressed NFT.
+pub fn set_collection_verification(
+    verify: bool,
+    tree_config: Pubkey,
+    leaf_owner: Pubkey,
+    leaf_delegate: Pubkey,
+    merkle_tree: Pubkey,
+    payer: Pubkey,
+    tree_creator_or_delegate: Pubkey,
+    collection_authority: Pubkey,
+    collection_authority_record_pda: Option<Pubkey>,
+    collection_mint: Pubkey,
+    collection_metadata: Pubkey,
+    collection_edition: Pubkey,
+    root: [u8; 32],
+    data_hash: [u8; 32],
+    creator_hash: [u8; 32],
+    nonce: u64,
+    index: u32,
+    metadata: MetadataArgs,
+    proof: &[[u8; 32]],
+    options: &TransactionOptions,
+) -> Result<Vec<u8>, Error> {
+    let (bubblegum_signer, _) = derive_bubblegum_signer_pda(&options.program_ids.bubblegum);
+    let remaining_accounts = remaining_accounts(proof, &[]);
+
+    let instruction = if verify {
+        VerifyCollectionBuilder::new()
+            .tree_config(tree_config)
+            .leaf_owner(leaf_owner)
+            .leaf_delegate(leaf_delegate)
+            .merkle_tree(merkle_tree)
+            .payer(payer)
+            .tree_creator_or_delegate(tree_creator_or_delegate)
+            .collection_authority(collection_authority)
+            .collection_authority_record_pda(collection_authority_record_pda)
+            .collection_mint(collection_mint)
+            .collection_metadata(collection_metadata)
+            .collection_edition(collection_edition)
+            .bubblegum_signer(bubblegum_signer)
+            .token_metadata_program(MPL_TOKEN_METADATA_ID)
+            .root(root)
+            .data_hash(data_hash)
+            .creator_hash(creator_hash)
+            .nonce(nonce)
+            .index(index)
+            .metadata(metadata)
+            .add_remaining_accounts(&remaining_accounts)
+            .instruction()
+    } else {
+        UnverifyCollectionBuilder::new()
+            .tree_config(tree_config)
+            .leaf_owner(leaf_owner)
+            .leaf_delegate(leaf_delegate)
+            .merkle_tree(merkle_tree)
+            .payer(payer)
+            .tree_creator_or_delegate(tree_creator_or_delegate)
+            .collection_authority(collection_authority)
+            .collection_authority_record_pda(collection_authority_record_pda)
+            .collection_mint(collection_mint)
+            .collection_metadata(collection_metadata)
+            .collection_edition(collection_edition)
+            .bubblegum_signer(bubblegum_signer)
+            .token_metadata_program(MPL_TOKEN_METADATA_ID)
+            .root(root)
+            .data_hash(data_hash)
+            .creator_hash(creator_hash)
+            .nonce(nonce)
+            .index(index)
+            .metadata(metadata)
+            .add_remaining_accounts(&remaining_accounts)
+            .instruction()
+    };
+
+    build_transaction(vec![instruction], &payer, options, PROOF_SIZE_HINT) // Payer as fee payer
+}
+
+/// Creates a transaction that sets the collection of a compressed NFT to
+/// `new_collection` and verifies it in one step.
+pub fn set_and_verify_collection(
+    tree_config: Pubkey,
+    leaf_owner: Pubkey,
+    leaf_delegate: Pubkey,
+    merkle_tree: Pubkey,
+    payer: Pubkey,
+    tree_creator_or_delegate: Pubkey,
+    collection_authority: Pubkey,
+    collection_authority_record_pda: Option<Pubkey>,
+    new_collection: Pubkey,
+    collection_metadata: Pubkey,
+    collection_edition: Pubkey,
+    root: [u8; 32],
+    data_hash: [u8; 32],
+    creator_hash: [u8; 32],
+    nonce: u64,
+    index: u32,
+    metadata: MetadataArgs,
+    proof: &[[u8; 32]],
+    options: &TransactionOptions,
+) -> Result<Vec<u8>, Error> {
+    let (bubblegum_signer, _) = derive_bubblegum_signer_pda(&options.program_ids.bubblegum);
+
+    let instruction = SetAndVerifyCollectionBuilder::new()
+        .tree_config(tree_config)
+        .leaf_owner(leaf_owner)
+        .leaf_delegate(leaf_delegate)
+        .merkle_tree(merkle_tree)
+        .payer(payer)
+        .tree_creator_or_delegate(tree_creator_or_delegate, true) // Must sign to change the metadata
+        .collection_authority(collection_authority)
+        .collection_authority_record_pda(collection_authority_record_pda)
+        .collection_mint(new_collection)
+        .collection_metadata(collection_metadata)
+        .collection_edition(collection_edition)
+        .bubblegum_signer(bubblegum_signer)
+        .token_metadata_program(MPL_TOKEN_METADATA_ID)
+        .root(root)
+        .data_hash(data_hash)
+        .creator_hash(creator_hash)
+        .nonce(nonce)
+        .index(index)
+        .metadata(metadata)
+        .collection(new_collection)
+        .add_remaining_accounts(&remaining_accounts(proof, &[]))
+        .instruction();
+
+    build_transaction(vec![instruction], &payer, options, PROOF_SIZE_HINT) // Payer as fee payer
+}
+
+/// Creates a transaction that updates the metadata of a compressed NFT.
+pub fn update_metadata(
+    tree_config: Pubkey,
+    authority: Pubkey,
+    collection_mint: Option<Pubkey>,
+    collection_metadata: Option<Pubkey>,
+    collection_authority_record_pda: Option<Pubkey>,
+    leaf_owner: Pubkey,
+    leaf_delegate: Pubkey,
+    payer: Pubkey,
+    merkle_tree: Pubkey,
+    root: [u8; 32],
+    nonce: u64,
+    index: u32,
+    current_metadata: MetadataArgs,
+    update_args: UpdateArgs,
+    proof: &[[u8; 32]],
+    options: &TransactionOptions,
+) -> Result<Vec<u8>, Error> {
+    let instruction = UpdateMetadataBuilder::new()
+        .tree_config(tree_config)
+        .authority(authority)
+        .collection_mint(collection_mint)
+        .collection_metadata(collection_metadata)
+        .collection_authority_record_pda(collection_authority_record_pda)
+        .leaf_owner(leaf_owner)
+        .leaf_delegate(leaf_delegate)
+        .payer(payer)
+        .merkle_tree(merkle_tree)
+        .token_metadata_program(MPL_TOKEN_METADATA_ID)
+        .root(root)
+        .nonce(nonce)
+        .index(index)
+        .current_metadata(current_metadata)
+        .update_args(update_args)
+        .add_remaining_accounts(&remaining_accounts(proof, &[]))
+        .instruction();
+
+    build_transaction(vec![instruction], &payer, options, PROOF_SIZE_HINT) // Payer as fee payer
+}
+
+/// Creates a transaction that redeems a compressed NFT leaf into a voucher.
+pub fn redeem(
+    tree_config: Pubkey,
+    leaf_owner: Pubkey,
+    leaf_delegate: Pubkey,
+    merkle_tree: Pubkey,
+    voucher: Pubkey,
+    root: [u8; 32],
+    data_hash: [u8; 32],
+    creator_hash: [u8; 32],
+    nonce: u64,
+    index: u32,
+    proof: &[[u8; 32]],
+    options: &TransactionOptions,
+) -> Result<Vec<u8>, Error> {
+    let instruction = RedeemBuilder::new()
+        .tree_config(tree_config)
+        .leaf_owner(leaf_owner)
+        .leaf_delegate(leaf_delegate)
+        .merkle_tree(merkle_tree)
+        .voucher(voucher)
+        .root(root)
+        .data_hash(data_hash)
+        .creator_hash(creator_hash)
+        .nonce(nonce)
+        .index(index)
+        .add_remaining_accounts(&remaining_accounts(proof, &[]))
+        .instruction();
+
+    build_transaction(vec![instruction], &leaf_owner, options, PROOF_SIZE_HINT) // Leaf owner as fee payer
+}
+
+/// Creates a transaction that cancels a redeem, returning the voucher's leaf to the tree.
+pub fn cancel_redeem(
+    tree_config: Pubkey,
+    leaf_owner: Pubkey,
+    merkle_tree: Pubkey,
+    voucher: Pubkey,
+    root: [u8; 32],
+    proof: &[[u8; 32]],
+    options: &TransactionOptions,
+) -> Result<Vec<u8>, Error> {
+    let instruction = CancelRedeemBuilder::new()
+        .tree_config(tree_config)
+        .leaf_owner(leaf_owner)
+        .merkle_tree(merkle_tree)
+        .voucher(voucher)
+        .root(root)
+        .add_remaining_accounts(&remaining_accounts(proof, &[]))
+        .instruction();
+
+    build_transaction(vec![instruction], &leaf_owner, options, PROOF_SIZE_HINT) // Leaf owner as fee payer
+}
+
+/// Creates a transaction that decompresses a redeemed leaf into a Token Metadata NFT.
+///
+/// The program accounts default to the canonical deployments when `None`. The
+/// token program follows the metadata's `token_program_version`, so the token
+/// account and mint of a Token-2022 leaf are owned by SPL Token-2022.
+pub fn decompress_v1(
+    voucher: Pubkey,
+    leaf_owner: Pubkey,
+    token_account: Pubkey,
+    mint: Pubkey,
+    mint_authority: Pubkey,
+    metadata_account: Pubkey,
+    master_edition: Pubkey,
+    associated_token_program: Option<Pubkey>,
+    system_program: Option<Pubkey>,
+    token_metadata_program: Option<Pubkey>,
+    metadata: MetadataArgs,
+    options: &TransactionOptions,
+) -> Result<Vec<u8>, Error> {
+    let mut builder = DecompressV1Builder::new();
+    builder
+        .voucher(voucher)
+        .leaf_owner(leaf_owner)
+        .token_account(token_account)
+        .mint(mint)
+        .mint_authority(mint_authority)
+        .metadata_account(metadata_account)
+        .master_edition(master_edition)
+        .token_program(token_program_id(&metadata.token_program_version))
+        .metadata(metadata);
+
+    if let Some(associated_token_program) = associated_token_program {
+        builder.associated_token_program(associated_token_program);
+    }
+    if let Some(system_program) = system_program {
+        builder.system_program(system_program);
+    }
+    if let Some(token_metadata_program) = token_metadata_program {
+        builder.token_metadata_program(token_metadata_program);
+    }
+
+    let instruction = builder.instruction();
+
+    build_transaction(vec![instruction], &leaf_owner, options, OPTIONS_SIZE_HINT) // Leaf owner as fee payer
+}
+
+/// Returns the token program that owns the token accounts of a leaf with the
+/// given token program version.
+fn token_program_id(version: &TokenProgramVersion) -> Pubkey {
+    match version {
+        TokenProgramVersion::Original => SPL_TOKEN_ID,
+        TokenProgramVersion::Token2022 => SPL_TOKEN_2022_ID,
+    }
+}
+
+/// Creates a transaction that enables or disables decompression of the leaves
+/// of a tree. The tree config must belong to `merkle_tree`.
+pub fn set_decompressible_state(
+    tree_config: Pubkey,
+    tree_creator: Pubkey,
+    merkle_tree: Pubkey,
+    decompressable: bool,
+    options: &TransactionOptions,
+) -> Result<Vec<u8>, Error> {
+    let (expected, _) = Pubkey::find_program_address(&[merkle_tree.as_ref()], &options.program_ids.bubblegum);
+    if tree_config != expected {
+        return Err(Error::InvalidParameter(format!(
+            "tree_config {} is not the tree config of merkle tree {}",
+            tree_config, merkle_tree
+        )));
+    }
+
+    let state = if decompressable {
+        DecompressibleState::Enabled
+    } else {
+        DecompressibleState::Disabled
+    };
+    let instruction = SetDecompressibleStateBuilder::new()
+        .tree_config(tree_config)
+        .tree_creator(tree_creator)
+        .decompressable_state(state)
+        .instruction();
+
+    build_transaction(vec![instruction], &tree_creator, options, OPTIONS_SIZE_HINT) // Tree creator as fee payer
+}
+
+/// Rejects metadata claiming a verified collection. Only the collection
+/// authority can verify, so plain mints must leave the collection unverified
+/// and use `mint_to_collection_v1` to mint into a collection verified.
+fn check_unverified_collection(metadata: &MetadataArgs) -> Result<(), Error> {
+    match &metadata.collection {
+        Some(collection) if collection.verified => Err(Error::InvalidParameter(
+            "collection.verified must be false for mint_v1; use mint_to_collection_v1 to mint a verified collection item".to_string(),
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Applies the transaction options to the program instructions and serializes
+/// the resulting unsigned transaction.
+fn build_transaction(
+    program_instructions: Vec<Instruction>,
+    payer: &Pubkey,
+    options: &TransactionOptions,
+    size_hint: &str,
+) -> Result<Vec<u8>, Error> {
+    let instructions = transaction_instructions(program_instructions, payer, options)?;
+
+    // Create a Message from the Instructions
+    let message = if options.placeholder_fee_payer {
+        placeholder_fee_payer_message(&instructions)
+    } else {
+        Message::new(&instructions, Some(payer))
+    };
+
+    // Create a Transaction
+    let transaction = Transaction::new_unsigned(message);
+
+    // Serialize the transaction
+    let bytes = bincode::serialize(&transaction)
+        .map_err(|e| Error::Conversion(format!("Failed to serialize transaction: {}", e)))?;
+    check_transaction_size(bytes, size_hint)
+}
+
+/// Serializes the instructions as an unsigned v0 transaction whose accounts
+/// are looked up in the given tables where possible.
+fn build_versioned_transaction(
+    program_instructions: Vec<Instruction>,
+    payer: &Pubkey,
+    lookup_tables: &[AddressLookupTableAccount],
+    options: &TransactionOptions,
+) -> Result<Vec<u8>, Error> {
+    if options.placeholder_fee_payer {
+        return Err(Error::InvalidParameter(
+            "placeholder_fee_payer is only supported for legacy transactions".to_string(),
+        ));
+    }
+    let instructions = transaction_instructions(program_instructions, payer, options)?;
+
+    let message = v0::Message::try_compile(payer, &instructions, lookup_tables, Hash::default())
+        .map_err(|e| Error::InvalidParameter(format!("Failed to compile v0 message: {}", e)))?;
+    let transaction = VersionedTransaction {
+        signatures: vec![Signature::default(); message.header.num_required_signatures as usize],
+        message: VersionedMessage::V0(message),
+    };
+
+    let bytes = bincode::serialize(&transaction)
+        .map_err(|e| Error::Conversion(format!("Failed to serialize transaction: {}", e)))?;
+    check_transaction_size(
+        bytes,
+        "add the remaining accounts to the lookup tables or pass the tree's canopy_depth to shorten the proof",
+    )
+}
+
+/// Compiles a message without a fee payer and reserves the first account for
+/// one, set to `Pubkey::default()`. That key is also the system program, so the
+/// transaction is only valid once the relayer replaces it with its own key.
+fn placeholder_fee_payer_message(instructions: &[Instruction]) -> Message {
+    let mut message = Message::new(instructions, None);
+    message.account_keys.insert(0, Pubkey::default());
+    message.header.num_required_signatures += 1;
+    for instruction in &mut message.instructions {
+        instruction.program_id_index += 1;
+        instruction.accounts.iter_mut().for_each(|index| *index += 1);
+    }
+    message
+}
+
+/// Rejects transactions over the packet size limit, which the RPC node would
+/// otherwise refuse with a less helpful error, along with the caller's
+/// suggestion for making the transaction fit.
+fn check_transaction_size(bytes: Vec<u8>, suggestion: &str) -> Result<Vec<u8>, Error> {
+    if bytes.len() > PACKET_DATA_SIZE {
+        return Err(Error::InvalidParameter(format!(
+            "transaction exceeds {} bytes ({}); {}",
+            PACKET_DATA_SIZE,
+            bytes.len(),
+            suggestion
+        )));
+    }
+    Ok(bytes)
+}
+
+/// Prepends the advance nonce, compute budget and memo instructions, applies
+/// program ID overrides and marks the requested signers.
+fn transaction_instructions(
+    program_instructions: Vec<Instruction>,
+    payer: &Pubkey,
+    options: &TransactionOptions,
+) -> Result<Vec<Instruction>, Error> {
+    let mut instructions = Vec::new();
+    // The runtime only recognizes a durable nonce transaction if advancing
+    // the nonce is its first instruction
+    if let Some(nonce) = &options.durable_nonce {
+        instructions.push(system_instruction::advance_nonce_account(
+            &nonce.account,
+            &nonce.authority.unwrap_or(*payer),
+        ));
+    }
+    instructions.extend(compute_budget_instructions(
+        options.compute_unit_limit,
+        options.compute_unit_price,
+    ));
+    if let Some(memo) = &options.memo {
+        instructions.push(memo_instruction(memo));
+    }
+    instructions.extend(
+        program_instructions
+            .into_iter()
+            .map(|instruction| apply_program_ids(instruction, &options.program_ids)),
+    );
+    mark_signers(&mut instructions, payer, &options.signers)?;
+    Ok(instructions)
+}
+
+/// Rewrites references to the canonical program IDs, both as the invoked
+/// program and as accounts, to the configured ones.
+fn apply_program_ids(mut instruction: Instruction, program_ids: &ProgramIds) -> Instruction {
+    let canonical = ProgramIds::default();
+    if *program_ids == canonical {
+        return instruction;
+    }
+
+    let remap = |pubkey: &mut Pubkey| {
+        if *pubkey == canonical.bubblegum {
+            *pubkey = program_ids.bubblegum;
+        } else if *pubkey == canonical.compression {
+            *pubkey = program_ids.compression;
+        } else if *pubkey == canonical.noop {
+            *pubkey = program_ids.noop;
+        }
+    };
+
+    remap(&mut instruction.program_id);
+    for account in instruction.accounts.iter_mut() {
+        remap(&mut account.pubkey);
+    }
+    instruction
+}
+
+/// Marks every occurrence of the given signers as a signer in the instruction
+/// accounts. Signers other than the fee payer must be referenced by at least one
+/// instruction, otherwise there would be no account to attach the signature to.
+fn mark_signers(
+    instructions: &mut [Instruction],
+    payer: &Pubkey,
+    signers: &[Pubkey],
+) -> Result<(), Error> {
+    for signer in signers {
+        let mut found = signer == payer;
+        for account in instructions.iter_mut().flat_map(|ix| ix.accounts.iter_mut()) {
+            if account.pubkey == *signer {
+                account.is_signer = true;
+                found = true;
+            }
+        }
+        if !found {
+            return Err(Error::InvalidParameter(format!(
+                "Signer {} is not an account of the transaction",
+                signer
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Drops the proof nodes covered by the tree's canopy. The proof runs from the
+/// leaf up to the root, and the top `canopy_depth` levels are already stored
+/// on-chain, so only the nodes below them have to be passed as accounts.
+pub fn truncate_proof(proof: &[[u8; 32]], canopy_depth: u32) -> &[[u8; 32]] {
+    let len = proof.len().saturating_sub(canopy_depth as usize);
+    &proof[..len]
+}
+
+/// Builds the remaining accounts of a leaf instruction: the proof nodes as
+/// read-only accounts, followed by any caller-supplied extra accounts.
+fn remaining_accounts(proof: &[[u8; 32]], extra_accounts: &[AccountMeta]) -> Vec<AccountMeta> {
+    proof
+        .iter()
+        .map(|node| AccountMeta::new_readonly(Pubkey::new_from_array(*node), false))
+        .chain(extra_accounts.iter().cloned())
+        .collect()
+}
+
+/// Builds the compute budget instructions for the optional limit and price.
+/// These must come before any other instruction in the message.
+fn compute_budget_instructions(
+    compute_unit_limit: Option<u32>,
+    compute_unit_price: Option<u64>,
+) -> Vec<Instruction> {
+    let mut instructions = Vec::new();
+    if let Some(limit) = compute_unit_limit {
+        instructions.push(ComputeBudgetInstruction::set_compute_unit_limit(limit));
+    }
+    if let Some(price) = compute_unit_price {
+        instructions.push(ComputeBudgetInstruction::set_compute_unit_price(price));
+    }
+    instructions
+}
+
+/// Adds compute budget instructions to an already built transaction. The
+/// message is recompiled, so any existing signatures are dropped. Messages
+/// that already set a compute budget are returned unchanged.
+pub fn prepend_priority_fee(
+    transaction_bytes: &[u8],
+    compute_unit_limit: u32,
+    compute_unit_price: u64,
+) -> Result<Vec<u8>, Error> {
+    let transaction: Transaction = bincode::deserialize(transaction_bytes)
+        .map_err(|e| Error::Deserialization(format!("Failed to deserialize transaction: {}", e)))?;
+    // Sanitizing rejects out-of-range account indexes before they are looked up
+    transaction
+        .sanitize()
+        .map_err(|e| Error::Deserialization(format!("Malformed transaction: {}", e)))?;
+    let message = &transaction.message;
+    if message.program_ids().contains(&&solana_sdk::compute_budget::ID) {
+        return Ok(transaction_bytes.to_vec());
+    }
+    let payer = *message
+        .account_keys
+        .first()
+        .ok_or_else(|| Error::InvalidParameter("Transaction has no fee payer".to_string()))?;
+
+    let mut instructions: Vec<Instruction> = message
+        .instructions
+        .iter()
+        .map(|compiled| Instruction {
+            program_id: message.account_keys[compiled.program_id_index as usize],
+            accounts: compiled
+                .accounts
+                .iter()
+                .map(|&index| AccountMeta {
+                    pubkey: message.account_keys[index as usize],
+                    is_signer: message.is_signer(index as usize),
+                    is_writable: message.is_writable(index as usize),
+                })
+                .collect(),
+            data: compiled.data.clone(),
+        })
+        .collect();
+    // Advancing a durable nonce has to stay the first instruction
+    let position = usize::from(solana_sdk::transaction::uses_durable_nonce(&transaction).is_some());
+    instructions.splice(
+        position..position,
+        compute_budget_instructions(Some(compute_unit_limit), Some(compute_unit_price)),
+    );
+
+    let message = Message::new_with_blockhash(&instructions, Some(&payer), &message.recent_blockhash);
+    bincode::serialize(&Transaction::new_unsigned(message))
+        .map_err(|e| Error::Conversion(format!("Failed to serialize transaction: {}", e)))
+}
+
+/// Builds an SPL Memo instruction. Without signer accounts the memo program
+/// only checks that the data is valid UTF-8.
+pub fn memo_instruction(memo: &str) -> Instruction {
+    Instruction {
+        program_id: SPL_MEMO_ID,
+        accounts: vec![],
+        data: memo.as_bytes().to_vec(),
+    }
+}
+
+/// Size of a concurrent merkle tree account, laid out as spl-account-compression does:
+/// the header, the tree itself (changelog buffer plus rightmost proof) and the canopy.
+pub fn get_merkle_tree_size(max_depth: u32, max_buffer_size: u32, canopy_depth: u32) -> usize {
+    const HEADER_SIZE: usize = 56; // Account type + V1 header
+    let depth = max_depth as usize;
+    // sequence_number + active_index + buffer_size
+    let counters_size = 3 * 8;
+    // root + path nodes + index + padding
+    let change_log_size = 32 + depth * 32 + 4 + 4;
+    // proof nodes + leaf + index + padding
+    let rightmost_path_size = depth * 32 + 32 + 4 + 4;
+    let tree_size = counters_size + max_buffer_size as usize * change_log_size + rightmost_path_size;
+    // Every node of the upper `canopy_depth` levels except the root
+    let canopy_size = ((1usize << (canopy_depth + 1)) - 2) * 32;
+    HEADER_SIZE + tree_size + canopy_size
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::compute_budget::ID as COMPUTE_BUDGET_ID;
+    use mpl_bubblegum::types::TokenStandard;
+    use mpl_bubblegum::instructions::DelegateInstructionArgs;
+    use borsh::BorshDeserialize;
+
+    fn decode(bytes: &[u8]) -> Transaction {
+        bincode::deserialize(bytes).expect("transaction should deserialize")
+    }
+
+    fn program_ids(transaction: &Transaction) -> Vec<Pubkey> {
+        transaction
+            .message
+            .instructions
+            .iter()
+            .map(|ix| transaction.message.account_keys[ix.program_id_index as usize])
+            .collect()
+    }
+
+    fn owner_transfer(
+        leaf_owner: Pubkey,
+        leaf_delegate: Pubkey,
+        proof: &[[u8; 32]],
+        extra_accounts: &[AccountMeta],
+        options: &TransactionOptions,
+    ) -> Result<Vec<u8>, Error> {
+        transfer(
+            Pubkey::new_unique(),
+            leaf_owner,
+            leaf_delegate,
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            leaf_owner,
+            [0; 32],
+            [1; 32],
+            [2; 32],
+            0,
+            0,
+            true,
+            false,
+            proof,
+            0,
+            extra_accounts,
+            options,
+        )
+    }
+
+    #[test]
+    fn transfer_prepends_compute_budget_instructions() {
+        let leaf_owner = Pubkey::new_unique();
+        let options = TransactionOptions {
+            compute_unit_limit: Some(200_000),
+            compute_unit_price: Some(1_000),
+            ..Default::default()
+        };
+        let bytes = owner_transfer(leaf_owner, leaf_owner, &[], &[], &options).unwrap();
+
+        assert_eq!(
+            program_ids(&decode(&bytes)),
+            vec![COMPUTE_BUDGET_ID, COMPUTE_BUDGET_ID, BUBBLEGUM_ID]
+        );
+    }
+
+    #[test]
+    fn transfer_without_compute_budget_has_single_instruction() {
+        let leaf_owner = Pubkey::new_unique();
+        let bytes =
+            owner_transfer(leaf_owner, leaf_owner, &[], &[], &TransactionOptions::default()).unwrap();
+
+        assert_eq!(program_ids(&decode(&bytes)), vec![BUBBLEGUM_ID]);
+    }
+
+    #[test]
+    fn signers_reserve_signature_slots() {
+        let leaf_owner = Pubkey::new_unique();
+        let leaf_delegate = Pubkey::new_unique();
+        let options = TransactionOptions {
+            signers: vec![leaf_delegate],
+            ..Default::default()
+        };
+        let bytes = owner_transfer(leaf_owner, leaf_delegate, &[], &[], &options).unwrap();
+
+        let transaction = decode(&bytes);
+        assert_eq!(transaction.message.header.num_required_signatures, 2);
+        assert_eq!(transaction.signatures.len(), 2);
+        assert_eq!(&transaction.message.account_keys[..2], &[leaf_owner, leaf_delegate]);
+    }
+
+    #[test]
+    fn signers_must_be_transaction_accounts() {
+        let leaf_owner = Pubkey::new_unique();
+        let options = TransactionOptions {
+            signers: vec![Pubkey::new_unique()],
+            ..Default::default()
+        };
+        let result = owner_transfer(leaf_owner, leaf_owner, &[], &[], &options);
+
+        assert!(matches!(result, Err(Error::InvalidParameter(_))));
+    }
+
+    #[test]
+    fn transfer_appends_extra_accounts_after_proof() {
+        let leaf_owner = Pubkey::new_unique();
+        let extra = Pubkey::new_unique();
+        let bytes = owner_transfer(
+            leaf_owner,
+            leaf_owner,
+            &[[7; 32], [8; 32]],
+            &[AccountMeta::new(extra, false)],
+            &TransactionOptions::default(),
+        )
+        .unwrap();
+
+        let transaction = decode(&bytes);
+        let instruction = &transaction.message.instructions[0];
+        let accounts: Vec<Pubkey> = instruction
+            .accounts
+            .iter()
+            .map(|index| transaction.message.account_keys[*index as usize])
+            .collect();
+        assert_eq!(
+            &accounts[accounts.len() - 3..],
+            &[Pubkey::new_from_array([7; 32]), Pubkey::new_from_array([8; 32]), extra]
+        );
+    }
+
+    #[test]
+    fn relayer_pays_for_delegate_signed_transfer() {
+        let leaf_owner = Pubkey::new_unique();
+        let leaf_delegate = Pubkey::new_unique();
+        let payer = Pubkey::new_unique();
+        let bytes = transfer(
+            Pubkey::new_unique(),
+            leaf_owner,
+            leaf_delegate,
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            payer,
+            [0; 32],
+            [1; 32],
+            [2; 32],
+            0,
+            0,
+            false,
+            true,
+            &[],
+            0,
+            &[],
+            &TransactionOptions::default(),
+        )
+        .unwrap();
+
+        let transaction = decode(&bytes);
+        assert_eq!(transaction.message.header.num_required_signatures, 2);
+        assert_eq!(&transaction.message.account_keys[..2], &[payer, leaf_delegate]);
+    }
+
+    #[test]
+    fn transfer_with_signers_requires_every_signer() {
+        let leaf_owner = Pubkey::new_unique();
+        let leaf_delegate = Pubkey::new_unique();
+        let relayer = Pubkey::new_unique();
+        let bytes = transfer_with_signers(
+            Pubkey::new_unique(),
+            leaf_owner,
+            leaf_delegate,
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            relayer,
+            [0; 32],
+            [1; 32],
+            [2; 32],
+            0,
+            0,
+            &[leaf_delegate, relayer],
+            &[],
+            0,
+            &[],
+            &TransactionOptions::default(),
+        )
+        .unwrap();
+
+        let transaction = decode(&bytes);
+        assert_eq!(transaction.message.header.num_required_signatures, 2);
+        assert_eq!(&transaction.message.account_keys[..2], &[relayer, leaf_delegate]);
+        assert!(!transaction.message.is_signer(
+            transaction.message.account_keys.iter().position(|key| *key == leaf_owner).unwrap()
+        ));
+    }
+
+    #[test]
+    fn create_tree_config_instruction_only_skips_account_creation() {
+        let payer = Pubkey::new_unique();
+        let bytes = create_tree_config_instruction_only(
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            payer,
+            payer,
+            14,
+            64,
+            None,
+            &[],
+            &TransactionOptions::default(),
+        )
+        .unwrap();
+
+        assert_eq!(program_ids(&decode(&bytes)), vec![BUBBLEGUM_ID]);
+    }
+
+    #[test]
+    fn create_tree_config_requires_payer_and_tree_creator_signatures() {
+        let merkle_tree = Pubkey::new_unique();
+        let (tree_config, _) = crate::utils::derive_tree_config_pda(&merkle_tree);
+        let (payer, tree_creator) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let bytes = create_tree_config(
+            tree_config,
+            merkle_tree,
+            payer,
+            tree_creator,
+            14,
+            64,
+            None,
+            &[],
+            &TransactionOptions::default(),
+        )
+        .unwrap();
+
+        let message = decode(&bytes).message;
+        let mut signers = message.signer_keys();
+        signers.sort();
+        // The tree config PDA is allocated by bubblegum and cannot sign
+        let mut expected = vec![&payer, &tree_creator, &merkle_tree];
+        expected.sort();
+        assert_eq!(signers, expected);
+        assert_eq!(message.account_keys[0], payer);
+    }
+
+    #[test]
+    fn create_tree_config_rent_matches_the_funded_accounts() {
+        use solana_sdk::system_instruction::SystemInstruction;
+
+        let payer = Pubkey::new_unique();
+        let bytes = create_tree_config(
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            payer,
+            payer,
+            14,
+            64,
+            None,
+            &[],
+            &TransactionOptions::default(),
+        )
+        .unwrap();
+
+        let funded: u64 = decode(&bytes)
+            .message
+            .instructions
+            .iter()
+            .filter_map(|ix| match bincode::deserialize(&ix.data) {
+                Ok(SystemInstruction::CreateAccount { lamports, .. }) => Some(lamports),
+                _ => None,
+            })
+            .sum();
+        let tree_config_rent = Rent::default().minimum_balance(TreeConfig::LEN);
+        assert_eq!(funded + tree_config_rent, create_tree_config_rent(14, 64));
+    }
+
+    #[test]
+    fn tree_funding_covers_rent_and_fees() {
+        let (merkle_tree, payer, tree_creator) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let (tree_config, _) = crate::utils::derive_tree_config_pda(&merkle_tree);
+        let options = TransactionOptions {
+            compute_unit_limit: Some(300_000),
+            compute_unit_price: Some(10_001),
+            ..TransactionOptions::default()
+        };
+        let bytes = create_tree_config(tree_config, merkle_tree, payer, tree_creator, 14, 64, None, &[], &options).unwrap();
+
+        // Payer, tree creator and merkle tree sign; 300_000 units at 10_001 micro-lamports round up
+        assert_eq!(transaction_fee(&bytes).unwrap(), 3 * 5_000 + 3_001);
+        let required = create_tree_config_rent(14, 64) + 3 * 5_000 + 3_001;
+        assert_eq!(check_tree_funding(required, &bytes, 14, 64).unwrap(), required);
+        assert!(matches!(check_tree_funding(required - 1, &bytes, 14, 64), Err(Error::InvalidParameter(_))));
+        // A balance that only covers the rent cannot pay the fee
+        let rent = create_tree_config_rent(14, 64);
+        assert!(matches!(check_tree_funding(rent, &bytes, 14, 64), Err(Error::InvalidParameter(_))));
+    }
+
+    #[test]
+    fn delegate_authorizes_burn() {
+        let leaf_owner = Pubkey::new_unique();
+        let leaf_delegate = Pubkey::new_unique();
+        let bytes = burn(
+            Pubkey::new_unique(),
+            leaf_owner,
+            leaf_delegate,
+            Pubkey::new_unique(),
+            leaf_delegate,
+            [0; 32],
+            [1; 32],
+            [2; 32],
+            0,
+            0,
+            false,
+            true,
+            &[],
+            0,
+            &[],
+            &TransactionOptions::default(),
+        )
+        .unwrap();
+
+        let transaction = decode(&bytes);
+        let message = &transaction.message;
+        let position = |key: Pubkey| message.account_keys.iter().position(|k| *k == key).unwrap();
+        assert_eq!(message.header.num_required_signatures, 1);
+        assert!(message.is_signer(position(leaf_delegate)));
+        assert!(!message.is_signer(position(leaf_owner)));
+    }
+
+    #[test]
+    fn decompressible_state_requires_the_tree_config_of_the_tree() {
+        let merkle_tree = Pubkey::new_unique();
+        let (tree_config, _) = crate::utils::derive_tree_config_pda(&merkle_tree);
+        let options = TransactionOptions::default();
+
+        assert!(set_decompressible_state(tree_config, Pubkey::new_unique(), merkle_tree, true, &options).is_ok());
+        assert!(matches!(
+            set_decompressible_state(Pubkey::new_unique(), Pubkey::new_unique(), merkle_tree, true, &options),
+            Err(Error::InvalidParameter(_))
+        ));
+    }
+
+    #[test]
+    fn priority_fee_is_prepended_once() {
+        let leaf_owner = Pubkey::new_unique();
+        let bytes = owner_transfer(leaf_owner, leaf_owner, &[], &[], &TransactionOptions::default()).unwrap();
+
+        let prepended = prepend_priority_fee(&bytes, 200_000, 1_000).unwrap();
+        let transaction = decode(&prepended);
+        let message = &transaction.message;
+        assert_eq!(message.instructions.len(), 3);
+        assert_eq!(*message.program_id(0).unwrap(), solana_sdk::compute_budget::ID);
+        assert_eq!(*message.program_id(2).unwrap(), BUBBLEGUM_ID);
+        assert_eq!(message.account_keys[0], leaf_owner);
+        assert!(message.is_signer(0));
+
+        assert_eq!(prepend_priority_fee(&prepended, 300_000, 2_000).unwrap(), prepended);
+    }
+
+    #[test]
+    fn priority_fee_rejects_malformed_messages() {
+        let leaf_owner = Pubkey::new_unique();
+        let bytes = owner_transfer(leaf_owner, leaf_owner, &[], &[], &TransactionOptions::default()).unwrap();
+        let mut transaction = decode(&bytes);
+        transaction.message.instructions[0].accounts[0] = u8::MAX;
+        let malformed = bincode::serialize(&transaction).unwrap();
+
+        assert!(matches!(prepend_priority_fee(&malformed, 200_000, 1_000), Err(Error::Deserialization(_))));
+    }
+
+    #[test]
+    fn release_delegate_hands_the_leaf_back_to_its_owner() {
+        let (leaf_owner, escrow) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let options = TransactionOptions::default();
+        let tree = (Pubkey::new_unique(), Pubkey::new_unique());
+        // Previous and new leaf delegate of the delegate instruction
+        let delegates = |bytes: Vec<u8>| {
+            let message = decode(&bytes).message;
+            let accounts = &message.instructions[0].accounts;
+            (message.account_keys[accounts[2] as usize], message.account_keys[accounts[3] as usize])
+        };
+
+        let escrowed =
+            escrow_delegate(tree.0, leaf_owner, escrow, tree.1, [0; 32], [1; 32], [2; 32], 0, 0, &[], 0, &options);
+        assert_eq!(delegates(escrowed.unwrap()), (leaf_owner, escrow));
+
+        let released =
+            release_delegate(tree.0, leaf_owner, escrow, tree.1, [0; 32], [1; 32], [2; 32], 0, 0, &[], 0, &options);
+        assert_eq!(delegates(released.unwrap()), (escrow, leaf_owner));
+    }
+
+    #[test]
+    fn oversized_transactions_are_rejected() {
+        let leaf_owner = Pubkey::new_unique();
+        let proof: Vec<[u8; 32]> = (0..30u8).map(|i| [i; 32]).collect();
+        let options = TransactionOptions::default();
+
+        let result = owner_transfer(leaf_owner, leaf_owner, &proof, &[], &options);
+        assert!(matches!(
+            result,
+            Err(Error::InvalidParameter(message)) if message.contains("exceeds 1232 bytes") && message.ends_with(PROOF_SIZE_HINT)
+        ));
+        assert!(owner_transfer(leaf_owner, leaf_owner, &proof[..10], &[], &options).is_ok());
+    }
+
+    #[test]
+    fn placeholder_fee_payer_is_left_for_the_relayer() {
+        let (tree_config, merkle_tree) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let (leaf_owner, new_leaf_owner, relayer) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let build = |payer: Pubkey, options: &TransactionOptions| {
+            let bytes = transfer(
+                tree_config, leaf_owner, leaf_owner, new_leaf_owner, merkle_tree, payer, [0; 32], [1; 32], [2; 32], 0,
+                0, true, false, &[], 0, &[], options,
+            )
+            .unwrap();
+            decode(&bytes).message
+        };
+        let options = TransactionOptions {
+            placeholder_fee_payer: true,
+            ..Default::default()
+        };
+
+        let mut message = build(leaf_owner, &options);
+        assert_eq!(message.account_keys[0], Pubkey::default());
+        assert_eq!(message.header.num_required_signatures, 2);
+        assert_eq!(message.account_keys[1], leaf_owner);
+
+        // Once the relayer fills in its key, the message is the one it would have built
+        message.account_keys[0] = relayer;
+        assert_eq!(message, build(relayer, &TransactionOptions::default()));
+    }
+
+    #[test]
+    fn merkle_tree_size_matches_account_compression_layout() {
+        assert_eq!(get_merkle_tree_size(14, 64, 0), 31_800);
+        assert_eq!(get_merkle_tree_size(14, 64, 10), 31_800 + 2_046 * 32);
+        assert_eq!(get_merkle_tree_size(3, 8, 0), 1_304);
+    }
+
+    #[test]
+    fn program_id_overrides_replace_canonical_programs() {
+        let leaf_owner = Pubkey::new_unique();
+        let overrides = ProgramIds {
+            bubblegum: Pubkey::new_unique(),
+            compression: Pubkey::new_unique(),
+            noop: Pubkey::new_unique(),
+        };
+        let options = TransactionOptions {
+            program_ids: overrides,
+            ..Default::default()
+        };
+        let bytes = owner_transfer(leaf_owner, leaf_owner, &[], &[], &options).unwrap();
+
+        let transaction = decode(&bytes);
+        let keys = &transaction.message.account_keys;
+        assert_eq!(program_ids(&transaction), vec![overrides.bubblegum]);
+        assert!(keys.contains(&overrides.compression));
+        assert!(keys.contains(&overrides.noop));
+        assert!(!keys.contains(&BUBBLEGUM_ID));
+        assert!(!keys.contains(&SPL_ACCOUNT_COMPRESSION_ID));
+    }
+
+    #[test]
+    fn transfer_from_proof_checks_the_leaf() {
+        let (tree, leaf_owner) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let asset_id = crate::utils::get_asset_id(tree, 5).unwrap();
+        let asset_proof = AssetProof {
+            tree,
+            root: [9; 32],
+            proof: vec![[3; 32], [4; 32], [5; 32]],
+            node_index: 8 + 5,
+            leaf: crate::utils::compute_leaf_node(asset_id, leaf_owner, leaf_owner, 5, [1; 32], [2; 32]),
+        };
+        let transfer = |data_hash| {
+            transfer_from_proof(
+                &asset_proof,
+                leaf_owner,
+                leaf_owner,
+                Pubkey::new_unique(),
+                leaf_owner,
+                data_hash,
+                [2; 32],
+                1,
+                &TransactionOptions::default(),
+            )
+        };
+
+        let transaction = decode(&transfer([1; 32]).unwrap());
+        let instruction = &transaction.message.instructions[0];
+        let (tree_config, _) = crate::utils::derive_tree_config_pda(&tree);
+        assert_eq!(transaction.message.account_keys[instruction.accounts[0] as usize], tree_config);
+        // Eight transfer accounts followed by the two proof nodes below the canopy
+        assert_eq!(instruction.accounts.len(), 8 + 2);
+        assert!(matches!(transfer([7; 32]), Err(Error::InvalidParameter(_))));
+    }
+
+    #[test]
+    fn delegate_and_transfer_proves_the_delegated_leaf() {
+        let (tree, leaf_owner, buyer) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let asset_id = crate::utils::get_asset_id(tree, 5).unwrap();
+        let proof = vec![[3; 32], [4; 32], [5; 32]];
+        let leaf = crate::utils::compute_leaf_node(asset_id, leaf_owner, leaf_owner, 5, [1; 32], [2; 32]);
+        let mut asset_proof = AssetProof {
+            tree,
+            root: recompute_root(leaf, &proof, 5),
+            proof: proof.clone(),
+            node_index: 8 + 5,
+            leaf,
+        };
+        let delegate_and_transfer = |asset_proof: &AssetProof| {
+            delegate_and_transfer(
+                asset_proof,
+                leaf_owner,
+                leaf_owner,
+                buyer,
+                buyer,
+                [1; 32],
+                [2; 32],
+                1,
+                &TransactionOptions::default(),
+            )
+        };
+
+        let transaction = decode(&delegate_and_transfer(&asset_proof).unwrap());
+        let data: Vec<_> = transaction.message.instructions.iter().map(|ix| ix.data.as_slice()).collect();
+        let delegate_args = DelegateInstructionArgs::try_from_slice(&data[0][8..]).unwrap();
+        let transfer_args = TransferInstructionArgs::try_from_slice(&data[1][8..]).unwrap();
+        assert_eq!(delegate_args.root, asset_proof.root);
+        let delegated_leaf = crate::utils::compute_leaf_node(asset_id, leaf_owner, buyer, 5, [1; 32], [2; 32]);
+        assert_eq!(transfer_args.root, recompute_root(delegated_leaf, &proof, 5));
+        assert_eq!(transaction.message.header.num_required_signatures, 1);
+        assert_eq!(transaction.message.account_keys[0], leaf_owner);
+
+        asset_proof.root = [9; 32];
+        assert!(matches!(delegate_and_transfer(&asset_proof), Err(Error::InvalidParameter(_))));
+    }
+
+    fn mint_request(merkle_tree: Pubkey) -> MintRequest {
+        let leaf_owner = Pubkey::new_unique();
+        MintRequest {
+            tree_config: Pubkey::new_unique(),
+            leaf_owner,
+            leaf_delegate: leaf_owner,
+            merkle_tree,
+            tree_creator_or_delegate: Pubkey::new_unique(),
+            metadata: MetadataArgs {
+                name: "Test NFT".to_string(),
+                symbol: "TNFT".to_string(),
+                uri: "https://example.com/test.json".to_string(),
+                seller_fee_basis_points: 500,
+                primary_sale_happened: false,
+                is_mutable: true,
+                edition_nonce: None,
+                token_standard: Some(TokenStandard::NonFungible),
+                collection: None,
+                uses: None,
+                token_program_version: TokenProgramVersion::Original,
+                creators: vec![],
+            },
+        }
+    }
+
+    #[test]
+    fn decompress_uses_the_token_program_of_the_leaf() {
+        for (version, token_program) in [
+            (TokenProgramVersion::Original, SPL_TOKEN_ID),
+            (TokenProgramVersion::Token2022, SPL_TOKEN_2022_ID),
+        ] {
+            let leaf_owner = Pubkey::new_unique();
+            let mut metadata = mint_request(Pubkey::new_unique()).metadata;
+            metadata.token_program_version = version;
+            let bytes = decompress_v1(
+                Pubkey::new_unique(),
+                leaf_owner,
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                None,
+                None,
+                None,
+                metadata,
+                &TransactionOptions::default(),
+            )
+            .unwrap();
+
+            let transaction = decode(&bytes);
+            let instruction = &transaction.message.instructions[0];
+            let token_program_index = instruction.accounts[10] as usize;
+            assert_eq!(transaction.message.account_keys[token_program_index], token_program);
+        }
+    }
+
+    #[test]
+    fn batch_mint_builds_one_instruction_per_mint() {
+        let payer = Pubkey::new_unique();
+        let merkle_tree = Pubkey::new_unique();
+        let mints = vec![mint_request(merkle_tree), mint_request(merkle_tree)];
+        let bytes = batch_mint(payer, mints, &TransactionOptions::default()).unwrap();
+
+        let transaction = decode(&bytes);
+        assert_eq!(program_ids(&transaction), vec![BUBBLEGUM_ID, BUBBLEGUM_ID]);
+        assert_eq!(transaction.message.account_keys[0], payer);
+    }
+
+    #[test]
+    fn batch_mint_rejects_transactions_over_packet_size() {
+        let payer = Pubkey::new_unique();
+        let mints = (0..10).map(|_| mint_request(Pubkey::new_unique())).collect();
+
+        assert!(matches!(
+            batch_mint(payer, mints, &TransactionOptions::default()),
+            Err(Error::InvalidParameter(message)) if message.ends_with(BATCH_MINT_SIZE_HINT)
+        ));
+    }
+
+    #[test]
+    fn mint_v1_requires_tree_delegate_signature() {
+        let payer = Pubkey::new_unique();
+        let mint = mint_request(Pubkey::new_unique());
+        let mint_with = |tree_creator_or_delegate: Pubkey| {
+            let mint = mint.clone();
+            let bytes = mint_v1(
+                mint.tree_config,
+                mint.leaf_owner,
+                mint.leaf_delegate,
+                mint.merkle_tree,
+                payer,
+                tree_creator_or_delegate,
+                mint.metadata,
+                &[],
+                &[],
+                &TransactionOptions::default(),
+            )
+            .unwrap();
+            decode(&bytes).message
+        };
+
+        let message = mint_with(mint.tree_creator_or_delegate);
+        assert_eq!(message.header.num_required_signatures, 2);
+        assert_eq!(message.account_keys[0], payer);
+        assert!(message.is_signer(1));
+        assert_eq!(message.account_keys[1], mint.tree_creator_or_delegate);
+
+        assert_eq!(mint_with(payer).header.num_required_signatures, 1);
+    }
+
+    #[test]
+    fn mint_v1_rejects_verified_collection() {
+        let mut mint = mint_request(Pubkey::new_unique());
+        mint.metadata.collection = Some(mpl_bubblegum::types::Collection {
+            verified: true,
+            key: Pubkey::new_unique(),
+        });
+        let result = mint_v1(
+            mint.tree_config,
+            mint.leaf_owner,
+            mint.leaf_delegate,
+            mint.merkle_tree,
+            mint.tree_creator_or_delegate,
+            mint.tree_creator_or_delegate,
+            mint.metadata,
+            &[],
+            &[],
+            &TransactionOptions::default(),
+        );
+
+        assert!(matches!(result, Err(Error::InvalidParameter(_))));
+    }
+
+    #[test]
+    fn mint_v1_appends_extra_instructions() {
+        let payer = Pubkey::new_unique();
+        let mint = mint_request(Pubkey::new_unique());
+        let fee = system_instruction::transfer(&payer, &Pubkey::new_unique(), 5_000);
+        let bytes = mint_v1(
+            mint.tree_config,
+            mint.leaf_owner,
+            mint.leaf_delegate,
+            mint.merkle_tree,
+            payer,
+            payer,
+            mint.metadata,
+            &[],
+            &[fee],
+            &TransactionOptions::default(),
+        )
+        .unwrap();
+
+        let system_program = solana_sdk::system_program::ID;
+        assert_eq!(program_ids(&decode(&bytes)), vec![BUBBLEGUM_ID, system_program]);
+    }
+
+    #[test]
+    fn memo_is_prepended_to_program_instructions() {
+        let leaf_owner = Pubkey::new_unique();
+        let options = TransactionOptions {
+            compute_unit_limit: Some(200_000),
+            memo: Some("order-42".to_string()),
+            ..Default::default()
+        };
+        let bytes = owner_transfer(leaf_owner, leaf_owner, &[], &[], &options).unwrap();
+
+        let transaction = decode(&bytes);
+        assert_eq!(
+            program_ids(&transaction),
+            vec![COMPUTE_BUDGET_ID, SPL_MEMO_ID, BUBBLEGUM_ID]
+        );
+        assert_eq!(transaction.message.instructions[1].data, b"order-42");
+    }
+
+    #[test]
+    fn durable_nonce_advances_nonce_first() {
+        let leaf_owner = Pubkey::new_unique();
+        let nonce_account = Pubkey::new_unique();
+        let options = TransactionOptions {
+            compute_unit_limit: Some(200_000),
+            durable_nonce: Some(DurableNonce {
+                account: nonce_account,
+                authority: None,
+            }),
+            ..Default::default()
+        };
+        let bytes = owner_transfer(leaf_owner, leaf_owner, &[], &[], &options).unwrap();
+
+        let transaction = decode(&bytes);
+        assert_eq!(
+            program_ids(&transaction),
+            vec![solana_sdk::system_program::ID, COMPUTE_BUDGET_ID, BUBBLEGUM_ID]
+        );
+        assert!(solana_sdk::transaction::uses_durable_nonce(&transaction).is_some());
+        // The fee payer doubles as the nonce authority and signs once
+        assert_eq!(transaction.message.header.num_required_signatures, 1);
+        assert_eq!(transaction.message.account_keys[0], leaf_owner);
+    }
+
+    #[test]
+    fn canopy_depth_truncates_proof_accounts() {
+        let leaf_owner = Pubkey::new_unique();
+        let proof: Vec<[u8; 32]> = (1..=14).map(|i| [i; 32]).collect();
+        let bytes = transfer(
+            Pubkey::new_unique(),
+            leaf_owner,
+            leaf_owner,
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            leaf_owner,
+            [0; 32],
+            [1; 32],
+            [2; 32],
+            0,
+            0,
+            true,
+            false,
+            &proof,
+            10,
+            &[],
+            &TransactionOptions::default(),
+        )
+        .unwrap();
+
+        let transaction = decode(&bytes);
+        let keys = &transaction.message.account_keys;
+        let kept: Vec<&[u8; 32]> = proof
+            .iter()
+            .filter(|node| keys.contains(&Pubkey::new_from_array(**node)))
+            .collect();
+        assert_eq!(kept, proof[..4].iter().collect::<Vec<_>>());
+        assert_eq!(truncate_proof(&proof, 20).len(), 0);
+    }
+
+    #[test]
+    fn transfer_v0_resolves_proof_nodes_through_lookup_table() {
+        let leaf_owner = Pubkey::new_unique();
+        let proof: Vec<[u8; 32]> = (1..=20).map(|i| [i; 32]).collect();
+        let lookup_table = AddressLookupTableAccount {
+            key: Pubkey::new_unique(),
+            addresses: proof.iter().map(|node| Pubkey::new_from_array(*node)).collect(),
+        };
+        let bytes = transfer_v0(
+            Pubkey::new_unique(),
+            leaf_owner,
+            leaf_owner,
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            leaf_owner,
+            [0; 32],
+            [1; 32],
+            [2; 32],
+            0,
+            0,
+            true,
+            false,
+            &proof,
+            0,
+            &[],
+            std::slice::from_ref(&lookup_table),
+            &TransactionOptions::default(),
+        )
+        .unwrap();
+
+        let transaction: VersionedTransaction = bincode::deserialize(&bytes).unwrap();
+        let VersionedMessage::V0(message) = transaction.message else {
+            panic!("expected a v0 message");
+        };
+        assert_eq!(transaction.signatures.len(), 1);
+        assert!(lookup_table.addresses.iter().all(|address| !message.account_keys.contains(address)));
+        assert_eq!(message.address_table_lookups.len(), 1);
+        assert_eq!(message.address_table_lookups[0].account_key, lookup_table.key);
+        assert_eq!(message.address_table_lookups[0].readonly_indexes.len(), proof.len());
+    }
+
+    #[test]
+    fn v0_transfers_can_be_signed() {
+        use solana_sdk::signer::{keypair::Keypair, Signer};
+
+        let leaf_owner = Keypair::new();
+        let proof = [[1; 32], [2; 32]];
+        let lookup_table = AddressLookupTableAccount {
+            key: Pubkey::new_unique(),
+            addresses: proof.iter().map(|node| Pubkey::new_from_array(*node)).collect(),
+        };
+        let bytes = transfer_v0(
+            Pubkey::new_unique(),
+            leaf_owner.pubkey(),
+            leaf_owner.pubkey(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            leaf_owner.pubkey(),
+            [0; 32],
+            [1; 32],
+            [2; 32],
+            0,
+            0,
+            true,
+            false,
+            &proof,
+            0,
+            &[],
+            std::slice::from_ref(&lookup_table),
+            &TransactionOptions::default(),
+        )
+        .unwrap();
+
+        let blockhash = Hash::new_unique();
+        let bytes = crate::utils::sign_transaction(&bytes, &[&leaf_owner.to_bytes()], blockhash).unwrap();
+        let transaction: VersionedTransaction = bincode::deserialize(&bytes).unwrap();
+        assert!(matches!(transaction.message, VersionedMessage::V0(_)));
+        assert_eq!(*transaction.message.recent_blockhash(), blockhash);
+        assert!(transaction.verify_with_results().iter().all(|verified| *verified));
+        assert!(crate::utils::unsigned_accounts(&bytes).unwrap().is_empty());
+    }
+}
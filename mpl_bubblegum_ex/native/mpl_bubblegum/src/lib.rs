@@ -1,20 +1,54 @@
+#![allow(clippy::too_many_arguments)]
+
 mod types;
 mod instructions;
 mod utils;
 mod error;
+mod das;
+mod decode;
+mod rpc;
 
 use rustler::{Encoder, Env, NifResult, Term, Binary};
 use rustler::types::atom;
 use rustler::error::Error;
-use types::{ElixirMetadata, ElixirPubkey, ElixirHash};
+use types::{
+    ElixirAccountInfo, ElixirAssetProof, ElixirDecodedTransaction, ElixirMetadata, ElixirMintRequest, ElixirPubkey, ElixirHash, ElixirJson, ElixirMerkleTreeHeader, ElixirRpcFilter, ElixirTransactionOptions, ElixirTreeConfig,
+    ElixirSignatureStatus, ElixirTokenAmount, ElixirTransactionDetails, ElixirUpdateArgs,
+};
 use solana_sdk::{
+    address_lookup_table::{state::AddressLookupTable, AddressLookupTableAccount},
+    commitment_config::CommitmentConfig,
+    hash::Hash,
     pubkey::Pubkey as SolanaPubkey,
-    signature::{Keypair, Signer},
-    transaction::Transaction,
+    sanitize::Sanitize,
+    signature::{Keypair, Signature, Signer},
+    transaction::{Transaction, TransactionError, VersionedTransaction},
 };
-use solana_client::rpc_client::RpcClient;
+use solana_client::{
+    client_error::{ClientError, ClientErrorKind},
+    rpc_client::RpcClient,
+    rpc_config::{RpcProgramAccountsConfig, RpcSendTransactionConfig, RpcSimulateTransactionConfig, RpcTransactionConfig},
+    rpc_filter::RpcFilterType,
+    rpc_request::{RpcError, RpcRequest},
+    rpc_response::Response,
+};
+use solana_transaction_status::UiTransactionEncoding;
+use base64::{prelude::BASE64_STANDARD, Engine};
+use serde_json::json;
 use std::str::FromStr;
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
 use tokio::runtime::Runtime;
+use tokio::sync::Semaphore;
+
+/// Converts a byte vector into a 32-byte hash, naming the argument on failure.
+fn hash_array(bytes: Vec<u8>, name: &str) -> NifResult<[u8; 32]> {
+    let len = bytes.len();
+    bytes.try_into().map_err(|_| {
+        error::Error::InvalidParameter(format!("{} must be 32 bytes, got {}", name, len)).into()
+    })
+}
 
 #[rustler::nif]
 fn create_tree_config<'a>(
@@ -26,18 +60,82 @@ fn create_tree_config<'a>(
     max_depth: u32,
     max_buffer_size: u32,
     public: Option<bool>,
+    extra_accounts: Option<Vec<(ElixirPubkey, bool, bool)>>,
+    options: Option<ElixirTransactionOptions>,
 ) -> NifResult<Term<'a>> {
+    let extra_accounts = types::account_metas(extra_accounts.unwrap_or_default())?;
+
     match instructions::create_tree_config(
-        tree_config.into(),
-        merkle_tree.into(),
-        payer.into(),
-        tree_creator.into(),
+        tree_config.try_into()?,
+        merkle_tree.try_into()?,
+        payer.try_into()?,
+        tree_creator.try_into()?,
+        max_depth,
+        max_buffer_size,
+        public,
+        &extra_accounts,
+        &types::transaction_options(options)?,
+    ) {
+        Ok(transaction) => Ok((atom::ok(), transaction).encode(env)),
+        Err(err) => Ok((atom::error(), err).encode(env)),
+    }
+}
+
+#[rustler::nif(schedule = "DirtyIo")]
+fn check_tree_funding<'a>(
+    env: Env<'a>,
+    rpc_url: String,
+    payer: ElixirPubkey,
+    transaction_binary: Binary<'a>,
+    max_depth: u32,
+    max_buffer_size: u32,
+) -> NifResult<Term<'a>> {
+    // Create a runtime for async operations
+    let rt = Runtime::new()
+        .map_err(|e| Error::Term(Box::new(format!("Failed to create tokio runtime: {}", e))))?;
+
+    let result = rt.block_on(async {
+        let client = rpc::client(&rpc_url);
+        let balance = client
+            .get_balance(&payer.try_into()?)
+            .map_err(|e| error::Error::Rpc(format!("Failed to get payer balance: {}", e)))?;
+        instructions::check_tree_funding(balance, transaction_binary.as_slice(), max_depth, max_buffer_size)
+    });
+
+    match result {
+        Ok(required) => Ok((atom::ok(), required).encode(env)),
+        Err(err) => Ok((atom::error(), err).encode(env)),
+    }
+}
+
+#[rustler::nif]
+fn create_tree_config_instruction_only<'a>(
+    env: Env<'a>,
+    tree_config: ElixirPubkey,
+    merkle_tree: ElixirPubkey,
+    payer: ElixirPubkey,
+    tree_creator: ElixirPubkey,
+    max_depth: u32,
+    max_buffer_size: u32,
+    public: Option<bool>,
+    extra_accounts: Option<Vec<(ElixirPubkey, bool, bool)>>,
+    options: Option<ElixirTransactionOptions>,
+) -> NifResult<Term<'a>> {
+    let extra_accounts = types::account_metas(extra_accounts.unwrap_or_default())?;
+
+    match instructions::create_tree_config_instruction_only(
+        tree_config.try_into()?,
+        merkle_tree.try_into()?,
+        payer.try_into()?,
+        tree_creator.try_into()?,
         max_depth,
         max_buffer_size,
         public,
+        &extra_accounts,
+        &types::transaction_options(options)?,
     ) {
         Ok(transaction) => Ok((atom::ok(), transaction).encode(env)),
-        Err(err) => Ok((atom::error(), err.to_string()).encode(env)),
+        Err(err) => Ok((atom::error(), err).encode(env)),
     }
 }
 
@@ -51,18 +149,98 @@ fn mint_v1<'a>(
     payer: ElixirPubkey,
     tree_creator_or_delegate: ElixirPubkey,
     metadata: ElixirMetadata,
+    nonce: u64,
+    extra_accounts: Option<Vec<(ElixirPubkey, bool, bool)>>,
+    extra_instructions: Option<Vec<Binary<'a>>>,
+    options: Option<ElixirTransactionOptions>,
+) -> NifResult<Term<'a>> {
+    let extra_accounts = types::account_metas(extra_accounts.unwrap_or_default())?;
+    let extra_instructions = match types::instructions(&extra_instructions.unwrap_or_default()) {
+        Ok(extra_instructions) => extra_instructions,
+        Err(err) => return Ok((atom::error(), err).encode(env)),
+    };
+    let merkle_tree: SolanaPubkey = merkle_tree.try_into()?;
+
+    // The asset ID is only correct if `nonce` still equals the tree's
+    // num_minted when the transaction lands
+    let result = instructions::mint_v1(
+        tree_config.try_into()?,
+        leaf_owner.try_into()?,
+        leaf_delegate.try_into()?,
+        merkle_tree,
+        payer.try_into()?,
+        tree_creator_or_delegate.try_into()?,
+        metadata.try_into()?,
+        &extra_accounts,
+        &extra_instructions,
+        &types::transaction_options(options)?,
+    )
+    .and_then(|transaction| Ok((transaction, utils::get_asset_id(merkle_tree, nonce)?)));
+
+    match result {
+        Ok((transaction, asset_id)) => {
+            Ok((atom::ok(), transaction, ElixirPubkey::from(asset_id)).encode(env))
+        }
+        Err(err) => Ok((atom::error(), err).encode(env)),
+    }
+}
+
+#[rustler::nif]
+fn build_batch_mint<'a>(
+    env: Env<'a>,
+    payer: ElixirPubkey,
+    mints: Vec<ElixirMintRequest>,
+    options: Option<ElixirTransactionOptions>,
+) -> NifResult<Term<'a>> {
+    let mints = mints
+        .into_iter()
+        .map(TryInto::try_into)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    match instructions::batch_mint(payer.try_into()?, mints, &types::transaction_options(options)?) {
+        Ok(transaction) => Ok((atom::ok(), transaction).encode(env)),
+        Err(err) => Ok((atom::error(), err).encode(env)),
+    }
+}
+
+#[rustler::nif]
+fn mint_to_collection_v1<'a>(
+    env: Env<'a>,
+    tree_config: ElixirPubkey,
+    leaf_owner: ElixirPubkey,
+    leaf_delegate: ElixirPubkey,
+    merkle_tree: ElixirPubkey,
+    payer: ElixirPubkey,
+    tree_creator_or_delegate: ElixirPubkey,
+    collection_authority: ElixirPubkey,
+    collection_authority_record_pda: Option<ElixirPubkey>,
+    collection_mint: ElixirPubkey,
+    collection_metadata: ElixirPubkey,
+    collection_edition: ElixirPubkey,
+    metadata: ElixirMetadata,
+    extra_accounts: Option<Vec<(ElixirPubkey, bool, bool)>>,
+    options: Option<ElixirTransactionOptions>,
 ) -> NifResult<Term<'a>> {
-    match instructions::mint_v1(
-        tree_config.into(),
-        leaf_owner.into(),
-        leaf_delegate.into(),
-        merkle_tree.into(),
-        payer.into(),
-        tree_creator_or_delegate.into(),
+    let extra_accounts = types::account_metas(extra_accounts.unwrap_or_default())?;
+
+    match instructions::mint_to_collection_v1(
+        tree_config.try_into()?,
+        leaf_owner.try_into()?,
+        leaf_delegate.try_into()?,
+        merkle_tree.try_into()?,
+        payer.try_into()?,
+        tree_creator_or_delegate.try_into()?,
+        collection_authority.try_into()?,
+        collection_authority_record_pda.map(SolanaPubkey::try_from).transpose()?,
+        collection_mint.try_into()?,
+        collection_metadata.try_into()?,
+        collection_edition.try_into()?,
         metadata.try_into()?,
+        &extra_accounts,
+        &types::transaction_options(options)?,
     ) {
         Ok(transaction) => Ok((atom::ok(), transaction).encode(env)),
-        Err(err) => Ok((atom::error(), err.to_string()).encode(env)),
+        Err(err) => Ok((atom::error(), err).encode(env)),
     }
 }
 
@@ -74,189 +252,2309 @@ fn transfer<'a>(
     leaf_delegate: ElixirPubkey,
     new_leaf_owner: ElixirPubkey,
     merkle_tree: ElixirPubkey,
+    payer: ElixirPubkey,
     root: Vec<u8>,
     data_hash: Vec<u8>,
     creator_hash: Vec<u8>,
     nonce: u64,
     index: u32,
+    leaf_owner_is_signer: bool,
+    leaf_delegate_is_signer: bool,
+    proof: Option<Vec<Vec<u8>>>,
+    canopy_depth: Option<u32>,
+    extra_accounts: Option<Vec<(ElixirPubkey, bool, bool)>>,
+    max_depth: Option<u32>,
+    options: Option<ElixirTransactionOptions>,
 ) -> NifResult<Term<'a>> {
-    let root_array: [u8; 32] = root.try_into().map_err(|_| Error::Term(Box::new("root must be 32 bytes")))?;
-    let data_hash_array: [u8; 32] = data_hash.try_into().map_err(|_| Error::Term(Box::new("data_hash must be 32 bytes")))?;
-    let creator_hash_array: [u8; 32] = creator_hash.try_into().map_err(|_| Error::Term(Box::new("creator_hash must be 32 bytes")))?;
+    let root_array = hash_array(root, "root")?;
+    let data_hash_array = hash_array(data_hash, "data_hash")?;
+    let creator_hash_array = hash_array(creator_hash, "creator_hash")?;
+    let proof = types::proof_nodes(proof.unwrap_or_default())?;
+    let extra_accounts = types::account_metas(extra_accounts.unwrap_or_default())?;
+    if let Some(max_depth) = max_depth {
+        if let Err(err) = utils::check_leaf_index(index, max_depth) {
+            return Ok((atom::error(), err).encode(env));
+        }
+    }
 
     match instructions::transfer(
-        tree_config.into(),
-        leaf_owner.into(),
-        leaf_delegate.into(),
-        new_leaf_owner.into(),
-        merkle_tree.into(),
+        tree_config.try_into()?,
+        leaf_owner.try_into()?,
+        leaf_delegate.try_into()?,
+        new_leaf_owner.try_into()?,
+        merkle_tree.try_into()?,
+        payer.try_into()?,
         root_array,
         data_hash_array,
         creator_hash_array,
         nonce,
         index,
+        leaf_owner_is_signer,
+        leaf_delegate_is_signer,
+        &proof,
+        canopy_depth.unwrap_or(0),
+        &extra_accounts,
+        &types::transaction_options(options)?,
     ) {
         Ok(transaction) => Ok((atom::ok(), transaction).encode(env)),
-        Err(err) => Ok((atom::error(), err.to_string()).encode(env)),
+        Err(err) => Ok((atom::error(), err).encode(env)),
     }
 }
 
 #[rustler::nif]
-fn hash_metadata<'a>(env: Env<'a>, metadata: ElixirMetadata) -> NifResult<Term<'a>> {
-    match utils::hash_metadata(metadata.try_into()?) {
-        Ok(hash) => Ok((atom::ok(), ElixirHash::from(hash)).encode(env)),
-        Err(err) => Ok((atom::error(), err.to_string()).encode(env)),
+fn build_transfer_from_proof<'a>(
+    env: Env<'a>,
+    asset_proof: ElixirAssetProof,
+    leaf_owner: ElixirPubkey,
+    leaf_delegate: ElixirPubkey,
+    new_leaf_owner: ElixirPubkey,
+    payer: ElixirPubkey,
+    data_hash: ElixirHash,
+    creator_hash: ElixirHash,
+    canopy_depth: Option<u32>,
+    options: Option<ElixirTransactionOptions>,
+) -> NifResult<Term<'a>> {
+    let asset_proof = match instructions::AssetProof::try_from(asset_proof) {
+        Ok(asset_proof) => asset_proof,
+        Err(err) => return Ok((atom::error(), err).encode(env)),
+    };
+
+    match instructions::transfer_from_proof(
+        &asset_proof,
+        leaf_owner.try_into()?,
+        leaf_delegate.try_into()?,
+        new_leaf_owner.try_into()?,
+        payer.try_into()?,
+        data_hash.try_into()?,
+        creator_hash.try_into()?,
+        canopy_depth.unwrap_or(0),
+        &types::transaction_options(options)?,
+    ) {
+        Ok(transaction) => Ok((atom::ok(), transaction).encode(env)),
+        Err(err) => Ok((atom::error(), err).encode(env)),
     }
 }
 
 #[rustler::nif]
-fn hash_creators<'a>(env: Env<'a>, creators: Vec<types::ElixirCreator>) -> NifResult<Term<'a>> {
-    match utils::hash_creators(creators) {
-        Ok(hash) => Ok((atom::ok(), ElixirHash::from(hash)).encode(env)),
-        Err(err) => Ok((atom::error(), err.to_string()).encode(env)),
+fn delegate_and_transfer<'a>(
+    env: Env<'a>,
+    asset_proof: ElixirAssetProof,
+    leaf_owner: ElixirPubkey,
+    previous_leaf_delegate: ElixirPubkey,
+    new_leaf_delegate: ElixirPubkey,
+    new_leaf_owner: ElixirPubkey,
+    data_hash: ElixirHash,
+    creator_hash: ElixirHash,
+    canopy_depth: Option<u32>,
+    options: Option<ElixirTransactionOptions>,
+) -> NifResult<Term<'a>> {
+    let asset_proof = match instructions::AssetProof::try_from(asset_proof) {
+        Ok(asset_proof) => asset_proof,
+        Err(err) => return Ok((atom::error(), err).encode(env)),
+    };
+
+    match instructions::delegate_and_transfer(
+        &asset_proof,
+        leaf_owner.try_into()?,
+        previous_leaf_delegate.try_into()?,
+        new_leaf_delegate.try_into()?,
+        new_leaf_owner.try_into()?,
+        data_hash.try_into()?,
+        creator_hash.try_into()?,
+        canopy_depth.unwrap_or(0),
+        &types::transaction_options(options)?,
+    ) {
+        Ok(transaction) => Ok((atom::ok(), transaction).encode(env)),
+        Err(err) => Ok((atom::error(), err).encode(env)),
     }
 }
 
 #[rustler::nif]
-fn get_asset_id<'a>(env: Env<'a>, tree: ElixirPubkey, nonce: u64) -> NifResult<Term<'a>> {
-    match utils::get_asset_id(tree.into(), nonce) {
-        Ok(asset_id) => Ok((atom::ok(), ElixirPubkey::from(asset_id)).encode(env)),
-        Err(err) => Ok((atom::error(), err.to_string()).encode(env)),
+fn transfer_with_signers<'a>(
+    env: Env<'a>,
+    tree_config: ElixirPubkey,
+    leaf_owner: ElixirPubkey,
+    leaf_delegate: ElixirPubkey,
+    new_leaf_owner: ElixirPubkey,
+    merkle_tree: ElixirPubkey,
+    payer: ElixirPubkey,
+    root: Vec<u8>,
+    data_hash: Vec<u8>,
+    creator_hash: Vec<u8>,
+    nonce: u64,
+    index: u32,
+    signers: Vec<ElixirPubkey>,
+    proof: Option<Vec<Vec<u8>>>,
+    canopy_depth: Option<u32>,
+    extra_accounts: Option<Vec<(ElixirPubkey, bool, bool)>>,
+    options: Option<ElixirTransactionOptions>,
+) -> NifResult<Term<'a>> {
+    let root_array = hash_array(root, "root")?;
+    let data_hash_array = hash_array(data_hash, "data_hash")?;
+    let creator_hash_array = hash_array(creator_hash, "creator_hash")?;
+    let proof = types::proof_nodes(proof.unwrap_or_default())?;
+    let extra_accounts = types::account_metas(extra_accounts.unwrap_or_default())?;
+    let signers: Vec<SolanaPubkey> = signers.into_iter().map(SolanaPubkey::try_from).collect::<Result<_, _>>()?;
+
+    match instructions::transfer_with_signers(
+        tree_config.try_into()?,
+        leaf_owner.try_into()?,
+        leaf_delegate.try_into()?,
+        new_leaf_owner.try_into()?,
+        merkle_tree.try_into()?,
+        payer.try_into()?,
+        root_array,
+        data_hash_array,
+        creator_hash_array,
+        nonce,
+        index,
+        &signers,
+        &proof,
+        canopy_depth.unwrap_or(0),
+        &extra_accounts,
+        &types::transaction_options(options)?,
+    ) {
+        Ok(transaction) => Ok((atom::ok(), transaction).encode(env)),
+        Err(err) => Ok((atom::error(), err).encode(env)),
     }
 }
 
-#[rustler::nif]
-fn derive_pubkey_from_secret<'a>(env: Env<'a>, secret_key: Binary<'a>) -> NifResult<Term<'a>> {
-    let secret_key_bytes = secret_key.as_slice();
-    let keypair = Keypair::from_bytes(secret_key_bytes)
-        .map_err(|e| Error::Term(Box::new(format!("Invalid secret key: {}", e))))?;
-    let pubkey = keypair.pubkey();
-    Ok((atom::ok(), ElixirPubkey::from(pubkey)).encode(env))
+#[rustler::nif(schedule = "DirtyIo")]
+fn build_transfer_v0<'a>(
+    env: Env<'a>,
+    rpc_url: String,
+    tree_config: ElixirPubkey,
+    leaf_owner: ElixirPubkey,
+    leaf_delegate: ElixirPubkey,
+    new_leaf_owner: ElixirPubkey,
+    merkle_tree: ElixirPubkey,
+    payer: ElixirPubkey,
+    root: Vec<u8>,
+    data_hash: Vec<u8>,
+    creator_hash: Vec<u8>,
+    nonce: u64,
+    index: u32,
+    leaf_owner_is_signer: bool,
+    leaf_delegate_is_signer: bool,
+    proof: Option<Vec<Vec<u8>>>,
+    canopy_depth: Option<u32>,
+    extra_accounts: Option<Vec<(ElixirPubkey, bool, bool)>>,
+    options: Option<ElixirTransactionOptions>,
+    lookup_table: Option<ElixirPubkey>,
+) -> NifResult<Term<'a>> {
+    let root_array = hash_array(root, "root")?;
+    let data_hash_array = hash_array(data_hash, "data_hash")?;
+    let creator_hash_array = hash_array(creator_hash, "creator_hash")?;
+    let proof = types::proof_nodes(proof.unwrap_or_default())?;
+    let extra_accounts = types::account_metas(extra_accounts.unwrap_or_default())?;
+
+    let lookup_tables = match lookup_table {
+        Some(lookup_table) => match fetch_lookup_table(&rpc_url, lookup_table.try_into()?) {
+            Ok(lookup_table) => vec![lookup_table],
+            Err(err) => return Ok((atom::error(), err).encode(env)),
+        },
+        None => vec![],
+    };
+
+    match instructions::transfer_v0(
+        tree_config.try_into()?,
+        leaf_owner.try_into()?,
+        leaf_delegate.try_into()?,
+        new_leaf_owner.try_into()?,
+        merkle_tree.try_into()?,
+        payer.try_into()?,
+        root_array,
+        data_hash_array,
+        creator_hash_array,
+        nonce,
+        index,
+        leaf_owner_is_signer,
+        leaf_delegate_is_signer,
+        &proof,
+        canopy_depth.unwrap_or(0),
+        &extra_accounts,
+        &lookup_tables,
+        &types::transaction_options(options)?,
+    ) {
+        Ok(transaction) => Ok((atom::ok(), transaction).encode(env)),
+        Err(err) => Ok((atom::error(), err).encode(env)),
+    }
+}
+
+/// Fetches an address lookup table account and decodes its addresses.
+fn fetch_lookup_table(
+    rpc_url: &str,
+    key: SolanaPubkey,
+) -> Result<AddressLookupTableAccount, error::Error> {
+    let client = rpc::client(rpc_url);
+    let account = client
+        .get_account(&key)
+        .map_err(|e| error::Error::Rpc(format!("Failed to get lookup table {}: {}", key, e)))?;
+    let table = AddressLookupTable::deserialize(&account.data)
+        .map_err(|e| error::Error::Conversion(format!("Invalid lookup table {}: {}", key, e)))?;
+
+    Ok(AddressLookupTableAccount {
+        key,
+        addresses: table.addresses.to_vec(),
+    })
 }
 
 #[rustler::nif]
-fn sign_and_submit_transaction<'a>(
+fn delegate<'a>(
     env: Env<'a>,
-    transaction_binary: Binary<'a>,
-    secret_keys: Vec<Binary<'a>>, // Changed to accept a vector of secret keys
+    tree_config: ElixirPubkey,
+    leaf_owner: ElixirPubkey,
+    previous_leaf_delegate: ElixirPubkey,
+    new_leaf_delegate: ElixirPubkey,
+    merkle_tree: ElixirPubkey,
+    root: Vec<u8>,
+    data_hash: Vec<u8>,
+    creator_hash: Vec<u8>,
+    nonce: u64,
+    index: u32,
+    proof: Option<Vec<Vec<u8>>>,
+    canopy_depth: Option<u32>,
+    options: Option<ElixirTransactionOptions>,
 ) -> NifResult<Term<'a>> {
-    let rt = Runtime::new().map_err(|e| Error::Term(Box::new(format!("Failed to create runtime: {}", e))))?;
-    let result = rt.block_on(async {
-        let transaction_bytes = transaction_binary.as_slice();
-        let mut transaction: Transaction = bincode::deserialize(transaction_bytes)
-            .map_err(|e| format!("Failed to deserialize transaction: {}", e))?;
-
-        // Convert each secret key binary to a Keypair
-        let mut keypairs = Vec::new();
-        for secret_key in secret_keys {
-            let keypair = Keypair::from_bytes(secret_key.as_slice())
-                .map_err(|e| format!("Failed to create keypair: {}", e))?;
-            keypairs.push(keypair);
-        }
-        let keypair_refs: Vec<&Keypair> = keypairs.iter().collect();
-
-        let client = RpcClient::new("http://127.0.0.1:8899".to_string());
-        let recent_blockhash = client.get_latest_blockhash()
-            .map_err(|e| format!("Failed to get blockhash: {}", e))?;
-        transaction.sign(&keypair_refs, recent_blockhash);
-        let signature = transaction.signatures[0].to_string(); // Log signature for demo
-        println!("Transaction signed with signature: {}", signature);
-        let signature = client.send_and_confirm_transaction(&transaction)
-            .map_err(|e| format!("Failed to submit transaction: {}", e))?;
-        Ok::<String, String>(signature.to_string())
-    });
+    let proof = types::proof_nodes(proof.unwrap_or_default())?;
 
-    match result {
-        Ok(signature) => Ok((atom::ok(), signature).encode(env)),
+    match instructions::delegate(
+        tree_config.try_into()?,
+        leaf_owner.try_into()?,
+        previous_leaf_delegate.try_into()?,
+        new_leaf_delegate.try_into()?,
+        merkle_tree.try_into()?,
+        hash_array(root, "root")?,
+        hash_array(data_hash, "data_hash")?,
+        hash_array(creator_hash, "creator_hash")?,
+        nonce,
+        index,
+        &proof,
+        canopy_depth.unwrap_or(0),
+        &types::transaction_options(options)?,
+    ) {
+        Ok(transaction) => Ok((atom::ok(), transaction).encode(env)),
         Err(err) => Ok((atom::error(), err).encode(env)),
     }
 }
 
 #[rustler::nif]
-fn get_transaction_status<'a>(env: Env<'a>, signature: String) -> NifResult<Term<'a>> {
-    // Create a runtime for async operations
-    let rt = Runtime::new()
-        .map_err(|e| Error::Term(Box::new(format!("Failed to create tokio runtime: {}", e))))?;
-    
-    let result = rt.block_on(async {
-        // Connect to Solana Devnet to check the transaction status
-        let client = RpcClient::new("http://127.0.0.1:8899".to_string());
-        
-        // Parse the signature string
-        let signature = match solana_sdk::signature::Signature::from_str(&signature) {
-            Ok(sig) => sig,
-            Err(e) => return Err(format!("Invalid signature format: {}", e)),
-        };
-        
-        // Get the transaction status
-        match client.get_signature_status(&signature) {
-            Ok(status) => {
-                match status {
-                    Some(Ok(_)) => Ok("confirmed".to_string()),
-                    Some(Err(e)) => Ok(format!("failed: {}", e)),
-                    None => Ok("not_found".to_string()),
-                }
-            },
-            Err(e) => Err(format!("Failed to get transaction status: {}", e)),
-        }
-    });
-    
-    match result {
-        Ok(status) => Ok((atom::ok(), status).encode(env)),
+fn escrow_delegate<'a>(
+    env: Env<'a>,
+    tree_config: ElixirPubkey,
+    leaf_owner: ElixirPubkey,
+    escrow: ElixirPubkey,
+    merkle_tree: ElixirPubkey,
+    root: Vec<u8>,
+    data_hash: Vec<u8>,
+    creator_hash: Vec<u8>,
+    nonce: u64,
+    index: u32,
+    proof: Option<Vec<Vec<u8>>>,
+    canopy_depth: Option<u32>,
+    options: Option<ElixirTransactionOptions>,
+) -> NifResult<Term<'a>> {
+    let proof = types::proof_nodes(proof.unwrap_or_default())?;
+
+    match instructions::escrow_delegate(
+        tree_config.try_into()?,
+        leaf_owner.try_into()?,
+        escrow.try_into()?,
+        merkle_tree.try_into()?,
+        hash_array(root, "root")?,
+        hash_array(data_hash, "data_hash")?,
+        hash_array(creator_hash, "creator_hash")?,
+        nonce,
+        index,
+        &proof,
+        canopy_depth.unwrap_or(0),
+        &types::transaction_options(options)?,
+    ) {
+        Ok(transaction) => Ok((atom::ok(), transaction).encode(env)),
         Err(err) => Ok((atom::error(), err).encode(env)),
     }
 }
 
 #[rustler::nif]
-fn get_account_info<'a>(env: Env<'a>, pubkey: ElixirPubkey) -> NifResult<Term<'a>> {
-    // Create a runtime for async operations
-    let rt = Runtime::new()
-        .map_err(|e| Error::Term(Box::new(format!("Failed to create tokio runtime: {}", e))))?;
-    
-    let result = rt.block_on(async {
-        // Connect to Solana Devnet to fetch account info
-        let client = RpcClient::new("http://127.0.0.1:8899".to_string());
-        
-        // Convert ElixirPubkey to Solana Pubkey
-        let pubkey: SolanaPubkey = pubkey.into();
-        
-        // Get the account info
-        match client.get_account(&pubkey) {
-            Ok(account) => {
-                let account_data = {
-                    let mut map = std::collections::HashMap::new();
-                    map.insert("lamports", account.lamports.to_string());
-                    map.insert("owner", account.owner.to_string());
-                    map.insert("executable", account.executable.to_string());
-                    map.insert("rent_epoch", account.rent_epoch.to_string());
-                    map.insert("data_len", account.data.len().to_string());
-                    map
-                };
-                
-                Ok(account_data)
-            },
-            Err(e) => Err(format!("Failed to get account info: {}", e)),
-        }
-    });
-    
-    match result {
-        Ok(account_data) => Ok((atom::ok(), account_data).encode(env)),
+fn release_delegate<'a>(
+    env: Env<'a>,
+    tree_config: ElixirPubkey,
+    leaf_owner: ElixirPubkey,
+    escrow: ElixirPubkey,
+    merkle_tree: ElixirPubkey,
+    root: Vec<u8>,
+    data_hash: Vec<u8>,
+    creator_hash: Vec<u8>,
+    nonce: u64,
+    index: u32,
+    proof: Option<Vec<Vec<u8>>>,
+    canopy_depth: Option<u32>,
+    options: Option<ElixirTransactionOptions>,
+) -> NifResult<Term<'a>> {
+    let proof = types::proof_nodes(proof.unwrap_or_default())?;
+
+    match instructions::release_delegate(
+        tree_config.try_into()?,
+        leaf_owner.try_into()?,
+        escrow.try_into()?,
+        merkle_tree.try_into()?,
+        hash_array(root, "root")?,
+        hash_array(data_hash, "data_hash")?,
+        hash_array(creator_hash, "creator_hash")?,
+        nonce,
+        index,
+        &proof,
+        canopy_depth.unwrap_or(0),
+        &types::transaction_options(options)?,
+    ) {
+        Ok(transaction) => Ok((atom::ok(), transaction).encode(env)),
         Err(err) => Ok((atom::error(), err).encode(env)),
     }
 }
 
-rustler::init!(
+#[rustler::nif]
+fn burn<'a>(
+    env: Env<'a>,
+    tree_config: ElixirPubkey,
+    leaf_owner: ElixirPubkey,
+    leaf_delegate: ElixirPubkey,
+    merkle_tree: ElixirPubkey,
+    payer: ElixirPubkey,
+    root: Vec<u8>,
+    data_hash: Vec<u8>,
+    creator_hash: Vec<u8>,
+    nonce: u64,
+    index: u32,
+    leaf_owner_is_signer: bool,
+    leaf_delegate_is_signer: bool,
+    proof: Option<Vec<Vec<u8>>>,
+    canopy_depth: Option<u32>,
+    extra_accounts: Option<Vec<(ElixirPubkey, bool, bool)>>,
+    options: Option<ElixirTransactionOptions>,
+) -> NifResult<Term<'a>> {
+    let proof = types::proof_nodes(proof.unwrap_or_default())?;
+    let extra_accounts = types::account_metas(extra_accounts.unwrap_or_default())?;
+
+    match instructions::burn(
+        tree_config.try_into()?,
+        leaf_owner.try_into()?,
+        leaf_delegate.try_into()?,
+        merkle_tree.try_into()?,
+        payer.try_into()?,
+        hash_array(root, "root")?,
+        hash_array(data_hash, "data_hash")?,
+        hash_array(creator_hash, "creator_hash")?,
+        nonce,
+        index,
+        leaf_owner_is_signer,
+        leaf_delegate_is_signer,
+        &proof,
+        canopy_depth.unwrap_or(0),
+        &extra_accounts,
+        &types::transaction_options(options)?,
+    ) {
+        Ok(transaction) => Ok((atom::ok(), transaction).encode(env)),
+        Err(err) => Ok((atom::error(), err).encode(env)),
+    }
+}
+
+fn set_collection_verification<'a>(
+    env: Env<'a>,
+    verify: bool,
+    tree_config: ElixirPubkey,
+    leaf_owner: ElixirPubkey,
+    leaf_delegate: ElixirPubkey,
+    merkle_tree: ElixirPubkey,
+    payer: ElixirPubkey,
+    tree_creator_or_delegate: ElixirPubkey,
+    collection_authority: ElixirPubkey,
+    collection_authority_record_pda: Option<ElixirPubkey>,
+    collection_mint: ElixirPubkey,
+    collection_metadata: ElixirPubkey,
+    collection_edition: ElixirPubkey,
+    root: Vec<u8>,
+    data_hash: Vec<u8>,
+    creator_hash: Vec<u8>,
+    nonce: u64,
+    index: u32,
+    metadata: ElixirMetadata,
+    proof: Option<Vec<Vec<u8>>>,
+    options: Option<ElixirTransactionOptions>,
+) -> NifResult<Term<'a>> {
+    let proof = types::proof_nodes(proof.unwrap_or_default())?;
+
+    match instructions::set_collection_verification(
+        verify,
+        tree_config.try_into()?,
+        leaf_owner.try_into()?,
+        leaf_delegate.try_into()?,
+        merkle_tree.try_into()?,
+        payer.try_into()?,
+        tree_creator_or_delegate.try_into()?,
+        collection_authority.try_into()?,
+        collection_authority_record_pda.map(SolanaPubkey::try_from).transpose()?,
+        collection_mint.try_into()?,
+        collection_metadata.try_into()?,
+        collection_edition.try_into()?,
+        hash_array(root, "root")?,
+        hash_array(data_hash, "data_hash")?,
+        hash_array(creator_hash, "creator_hash")?,
+        nonce,
+        index,
+        metadata.try_into()?,
+        &proof,
+        &types::transaction_options(options)?,
+    ) {
+        Ok(transaction) => Ok((atom::ok(), transaction).encode(env)),
+        Err(err) => Ok((atom::error(), err).encode(env)),
+    }
+}
+
+#[rustler::nif]
+fn verify_collection<'a>(
+    env: Env<'a>,
+    tree_config: ElixirPubkey,
+    leaf_owner: ElixirPubkey,
+    leaf_delegate: ElixirPubkey,
+    merkle_tree: ElixirPubkey,
+    payer: ElixirPubkey,
+    tree_creator_or_delegate: ElixirPubkey,
+    collection_authority: ElixirPubkey,
+    collection_authority_record_pda: Option<ElixirPubkey>,
+    collection_mint: ElixirPubkey,
+    collection_metadata: ElixirPubkey,
+    collection_edition: ElixirPubkey,
+    root: Vec<u8>,
+    data_hash: Vec<u8>,
+    creator_hash: Vec<u8>,
+    nonce: u64,
+    index: u32,
+    metadata: ElixirMetadata,
+    proof: Option<Vec<Vec<u8>>>,
+    options: Option<ElixirTransactionOptions>,
+) -> NifResult<Term<'a>> {
+    set_collection_verification(
+        env, true, tree_config, leaf_owner, leaf_delegate, merkle_tree, payer,
+        tree_creator_or_delegate, collection_authority, collection_authority_record_pda,
+        collection_mint, collection_metadata, collection_edition, root, data_hash,
+        creator_hash, nonce, index, metadata, proof, options,
+    )
+}
+
+#[rustler::nif]
+fn unverify_collection<'a>(
+    env: Env<'a>,
+    tree_config: ElixirPubkey,
+    leaf_owner: ElixirPubkey,
+    leaf_delegate: ElixirPubkey,
+    merkle_tree: ElixirPubkey,
+    payer: ElixirPubkey,
+    tree_creator_or_delegate: ElixirPubkey,
+    collection_authority: ElixirPubkey,
+    collection_authority_record_pda: Option<ElixirPubkey>,
+    collection_mint: ElixirPubkey,
+    collection_metadata: ElixirPubkey,
+    collection_edition: ElixirPubkey,
+    root: Vec<u8>,
+    data_hash: Vec<u8>,
+    creator_hash: Vec<u8>,
+    nonce: u64,
+    index: u32,
+    metadata: ElixirMetadata,
+    proof: Option<Vec<Vec<u8>>>,
+    options: Option<ElixirTransactionOptions>,
+) -> NifResult<Term<'a>> {
+    set_collection_verification(
+        env, false, tree_config, leaf_owner, leaf_delegate, merkle_tree, payer,
+        tree_creator_or_delegate, collection_authority, collection_authority_record_pda,
+        collection_mint, collection_metadata, collection_edition, root, data_hash,
+        creator_hash, nonce, index, metadata, proof, options,
+    )
+}
+
+#[rustler::nif]
+fn set_and_verify_collection<'a>(
+    env: Env<'a>,
+    tree_config: ElixirPubkey,
+    leaf_owner: ElixirPubkey,
+    leaf_delegate: ElixirPubkey,
+    merkle_tree: ElixirPubkey,
+    payer: ElixirPubkey,
+    tree_creator_or_delegate: ElixirPubkey,
+    collection_authority: ElixirPubkey,
+    collection_authority_record_pda: Option<ElixirPubkey>,
+    new_collection: ElixirPubkey,
+    collection_metadata: ElixirPubkey,
+    collection_edition: ElixirPubkey,
+    root: Vec<u8>,
+    data_hash: Vec<u8>,
+    creator_hash: Vec<u8>,
+    nonce: u64,
+    index: u32,
+    metadata: ElixirMetadata,
+    proof: Option<Vec<Vec<u8>>>,
+    options: Option<ElixirTransactionOptions>,
+) -> NifResult<Term<'a>> {
+    let proof = types::proof_nodes(proof.unwrap_or_default())?;
+
+    match instructions::set_and_verify_collection(
+        tree_config.try_into()?,
+        leaf_owner.try_into()?,
+        leaf_delegate.try_into()?,
+        merkle_tree.try_into()?,
+        payer.try_into()?,
+        tree_creator_or_delegate.try_into()?,
+        collection_authority.try_into()?,
+        collection_authority_record_pda.map(SolanaPubkey::try_from).transpose()?,
+        new_collection.try_into()?,
+        collection_metadata.try_into()?,
+        collection_edition.try_into()?,
+        hash_array(root, "root")?,
+        hash_array(data_hash, "data_hash")?,
+        hash_array(creator_hash, "creator_hash")?,
+        nonce,
+        index,
+        metadata.try_into()?,
+        &proof,
+        &types::transaction_options(options)?,
+    ) {
+        Ok(transaction) => Ok((atom::ok(), transaction).encode(env)),
+        Err(err) => Ok((atom::error(), err).encode(env)),
+    }
+}
+
+#[rustler::nif]
+fn update_metadata<'a>(
+    env: Env<'a>,
+    tree_config: ElixirPubkey,
+    authority: ElixirPubkey,
+    collection_mint: Option<ElixirPubkey>,
+    collection_metadata: Option<ElixirPubkey>,
+    collection_authority_record_pda: Option<ElixirPubkey>,
+    leaf_owner: ElixirPubkey,
+    leaf_delegate: ElixirPubkey,
+    payer: ElixirPubkey,
+    merkle_tree: ElixirPubkey,
+    root: Vec<u8>,
+    nonce: u64,
+    index: u32,
+    current_metadata: ElixirMetadata,
+    update_args: ElixirUpdateArgs,
+    proof: Option<Vec<Vec<u8>>>,
+    options: Option<ElixirTransactionOptions>,
+) -> NifResult<Term<'a>> {
+    let proof = types::proof_nodes(proof.unwrap_or_default())?;
+
+    match instructions::update_metadata(
+        tree_config.try_into()?,
+        authority.try_into()?,
+        collection_mint.map(SolanaPubkey::try_from).transpose()?,
+        collection_metadata.map(SolanaPubkey::try_from).transpose()?,
+        collection_authority_record_pda.map(SolanaPubkey::try_from).transpose()?,
+        leaf_owner.try_into()?,
+        leaf_delegate.try_into()?,
+        payer.try_into()?,
+        merkle_tree.try_into()?,
+        hash_array(root, "root")?,
+        nonce,
+        index,
+        current_metadata.try_into()?,
+        update_args.try_into()?,
+        &proof,
+        &types::transaction_options(options)?,
+    ) {
+        Ok(transaction) => Ok((atom::ok(), transaction).encode(env)),
+        Err(err) => Ok((atom::error(), err).encode(env)),
+    }
+}
+
+#[rustler::nif]
+fn redeem<'a>(
+    env: Env<'a>,
+    tree_config: ElixirPubkey,
+    leaf_owner: ElixirPubkey,
+    leaf_delegate: ElixirPubkey,
+    merkle_tree: ElixirPubkey,
+    voucher: ElixirPubkey,
+    root: Vec<u8>,
+    data_hash: Vec<u8>,
+    creator_hash: Vec<u8>,
+    nonce: u64,
+    index: u32,
+    proof: Option<Vec<Vec<u8>>>,
+    options: Option<ElixirTransactionOptions>,
+) -> NifResult<Term<'a>> {
+    let proof = types::proof_nodes(proof.unwrap_or_default())?;
+
+    match instructions::redeem(
+        tree_config.try_into()?,
+        leaf_owner.try_into()?,
+        leaf_delegate.try_into()?,
+        merkle_tree.try_into()?,
+        voucher.try_into()?,
+        hash_array(root, "root")?,
+        hash_array(data_hash, "data_hash")?,
+        hash_array(creator_hash, "creator_hash")?,
+        nonce,
+        index,
+        &proof,
+        &types::transaction_options(options)?,
+    ) {
+        Ok(transaction) => Ok((atom::ok(), transaction).encode(env)),
+        Err(err) => Ok((atom::error(), err).encode(env)),
+    }
+}
+
+#[rustler::nif]
+fn cancel_redeem<'a>(
+    env: Env<'a>,
+    tree_config: ElixirPubkey,
+    leaf_owner: ElixirPubkey,
+    merkle_tree: ElixirPubkey,
+    voucher: ElixirPubkey,
+    root: Vec<u8>,
+    proof: Option<Vec<Vec<u8>>>,
+    options: Option<ElixirTransactionOptions>,
+) -> NifResult<Term<'a>> {
+    let proof = types::proof_nodes(proof.unwrap_or_default())?;
+
+    match instructions::cancel_redeem(
+        tree_config.try_into()?,
+        leaf_owner.try_into()?,
+        merkle_tree.try_into()?,
+        voucher.try_into()?,
+        hash_array(root, "root")?,
+        &proof,
+        &types::transaction_options(options)?,
+    ) {
+        Ok(transaction) => Ok((atom::ok(), transaction).encode(env)),
+        Err(err) => Ok((atom::error(), err).encode(env)),
+    }
+}
+
+#[rustler::nif]
+fn decompress_v1<'a>(
+    env: Env<'a>,
+    voucher: ElixirPubkey,
+    leaf_owner: ElixirPubkey,
+    token_account: ElixirPubkey,
+    mint: ElixirPubkey,
+    mint_authority: ElixirPubkey,
+    metadata_account: ElixirPubkey,
+    master_edition: ElixirPubkey,
+    associated_token_program: Option<ElixirPubkey>,
+    system_program: Option<ElixirPubkey>,
+    token_metadata_program: Option<ElixirPubkey>,
+    metadata: ElixirMetadata,
+    options: Option<ElixirTransactionOptions>,
+) -> NifResult<Term<'a>> {
+    match instructions::decompress_v1(
+        voucher.try_into()?,
+        leaf_owner.try_into()?,
+        token_account.try_into()?,
+        mint.try_into()?,
+        mint_authority.try_into()?,
+        metadata_account.try_into()?,
+        master_edition.try_into()?,
+        associated_token_program.map(SolanaPubkey::try_from).transpose()?,
+        system_program.map(SolanaPubkey::try_from).transpose()?,
+        token_metadata_program.map(SolanaPubkey::try_from).transpose()?,
+        metadata.try_into()?,
+        &types::transaction_options(options)?,
+    ) {
+        Ok(transaction) => Ok((atom::ok(), transaction).encode(env)),
+        Err(err) => Ok((atom::error(), err).encode(env)),
+    }
+}
+
+#[rustler::nif]
+fn set_decompressible_state<'a>(
+    env: Env<'a>,
+    tree_config: ElixirPubkey,
+    tree_creator: ElixirPubkey,
+    merkle_tree: ElixirPubkey,
+    decompressable: bool,
+    options: Option<ElixirTransactionOptions>,
+) -> NifResult<Term<'a>> {
+    match instructions::set_decompressible_state(
+        tree_config.try_into()?,
+        tree_creator.try_into()?,
+        merkle_tree.try_into()?,
+        decompressable,
+        &types::transaction_options(options)?,
+    ) {
+        Ok(transaction) => Ok((atom::ok(), transaction).encode(env)),
+        Err(err) => Ok((atom::error(), err).encode(env)),
+    }
+}
+
+#[rustler::nif]
+fn default_metadata(name: String, symbol: String, uri: String) -> ElixirMetadata {
+    ElixirMetadata::new(name, symbol, uri)
+}
+
+#[rustler::nif]
+fn metadata_to_borsh<'a>(env: Env<'a>, metadata: ElixirMetadata) -> NifResult<Term<'a>> {
+    match utils::metadata_to_borsh(&metadata.try_into()?) {
+        Ok(bytes) => Ok((atom::ok(), bytes).encode(env)),
+        Err(err) => Ok((atom::error(), err).encode(env)),
+    }
+}
+
+#[rustler::nif]
+fn metadata_from_borsh<'a>(env: Env<'a>, bytes: Binary<'a>) -> NifResult<Term<'a>> {
+    match utils::metadata_from_borsh(bytes.as_slice()) {
+        Ok(metadata) => Ok((atom::ok(), ElixirMetadata::from(metadata)).encode(env)),
+        Err(err) => Ok((atom::error(), err).encode(env)),
+    }
+}
+
+#[rustler::nif]
+fn hash_metadata<'a>(env: Env<'a>, metadata: ElixirMetadata) -> NifResult<Term<'a>> {
+    match utils::hash_metadata(metadata.try_into()?) {
+        Ok(hash) => Ok((atom::ok(), ElixirHash::from(hash)).encode(env)),
+        Err(err) => Ok((atom::error(), err).encode(env)),
+    }
+}
+
+#[rustler::nif]
+fn hash_metadata_batch<'a>(env: Env<'a>, metadatas: Vec<ElixirMetadata>) -> NifResult<Term<'a>> {
+    let mut hashes = Vec::with_capacity(metadatas.len());
+    for (index, metadata) in metadatas.into_iter().enumerate() {
+        // Report which entry was bad along with the reason
+        let reason = match metadata.try_into() {
+            Ok(metadata) => match utils::hash_metadata(metadata) {
+                Ok(hash) => {
+                    hashes.push(ElixirHash::from(hash));
+                    continue;
+                }
+                Err(err) => err.encode(env),
+            },
+            Err(Error::Term(reason)) => reason.encode(env),
+            Err(err) => return Err(err),
+        };
+        return Ok((atom::error(), (index, reason)).encode(env));
+    }
+    Ok((atom::ok(), hashes).encode(env))
+}
+
+#[rustler::nif]
+fn hash_creators<'a>(env: Env<'a>, creators: Vec<types::ElixirCreator>) -> NifResult<Term<'a>> {
+    match utils::hash_creators(creators) {
+        Ok(hash) => Ok((atom::ok(), ElixirHash::from(hash)).encode(env)),
+        Err(err) => Ok((atom::error(), err).encode(env)),
+    }
+}
+
+#[rustler::nif]
+fn compute_leaf_hash<'a>(
+    env: Env<'a>,
+    metadata: ElixirMetadata,
+    merkle_tree: ElixirPubkey,
+    leaf_owner: ElixirPubkey,
+    leaf_delegate: ElixirPubkey,
+    nonce: u64,
+) -> NifResult<Term<'a>> {
+    let metadata = metadata.try_into()?;
+
+    match utils::compute_leaf_hash(&metadata, merkle_tree.try_into()?, leaf_owner.try_into()?, leaf_delegate.try_into()?, nonce) {
+        Ok(hashes) => Ok((
+            atom::ok(),
+            ElixirHash::from(hashes.data_hash),
+            ElixirHash::from(hashes.creator_hash),
+            ElixirHash::from(hashes.leaf_hash),
+        )
+            .encode(env)),
+        Err(err) => Ok((atom::error(), err).encode(env)),
+    }
+}
+
+#[rustler::nif]
+fn compute_leaf_node<'a>(
+    env: Env<'a>,
+    asset_id: ElixirPubkey,
+    leaf_owner: ElixirPubkey,
+    leaf_delegate: ElixirPubkey,
+    nonce: u64,
+    index: u32,
+    data_hash: ElixirHash,
+    creator_hash: ElixirHash,
+) -> NifResult<Term<'a>> {
+    // V1 leaves are only ever appended by mints, so a leaf sits at its nonce
+    if u64::from(index) != nonce {
+        let err = error::Error::InvalidParameter(format!(
+            "leaf index {} does not match nonce {}",
+            index, nonce
+        ));
+        return Ok((atom::error(), err).encode(env));
+    }
+
+    let node = utils::compute_leaf_node(
+        asset_id.try_into()?,
+        leaf_owner.try_into()?,
+        leaf_delegate.try_into()?,
+        nonce,
+        data_hash.try_into()?,
+        creator_hash.try_into()?,
+    );
+    Ok((atom::ok(), ElixirHash::from(node)).encode(env))
+}
+
+#[rustler::nif]
+fn unsigned_with_digest<'a>(env: Env<'a>, transaction_binary: Binary<'a>) -> NifResult<Term<'a>> {
+    match utils::unsigned_with_digest(transaction_binary.as_slice()) {
+        Ok((transaction, digest)) => Ok((atom::ok(), transaction, ElixirHash::from(digest)).encode(env)),
+        Err(err) => Ok((atom::error(), err).encode(env)),
+    }
+}
+
+mod decode_atoms {
+    rustler::atoms! {
+        unknown_discriminator,
+    }
+}
+
+#[rustler::nif]
+fn decode_bubblegum_instruction<'a>(env: Env<'a>, data: Binary<'a>) -> NifResult<Term<'a>> {
+    match decode::decode_bubblegum_instruction(data.as_slice()) {
+        Ok(Some((name, args))) => {
+            let name = rustler::Atom::from_str(env, name)?;
+            Ok((atom::ok(), (name, ElixirJson(args))).encode(env))
+        }
+        Ok(None) => Ok((atom::error(), decode_atoms::unknown_discriminator()).encode(env)),
+        Err(err) => Ok((atom::error(), err).encode(env)),
+    }
+}
+
+#[rustler::nif]
+fn transaction_to_base64<'a>(env: Env<'a>, transaction_binary: Binary<'a>) -> NifResult<Term<'a>> {
+    match utils::transaction_to_base64(transaction_binary.as_slice()) {
+        Ok(encoded) => Ok((atom::ok(), encoded).encode(env)),
+        Err(err) => Ok((atom::error(), err).encode(env)),
+    }
+}
+
+#[rustler::nif]
+fn transaction_from_base64<'a>(env: Env<'a>, encoded: String) -> NifResult<Term<'a>> {
+    match utils::transaction_from_base64(&encoded) {
+        Ok(transaction) => Ok((atom::ok(), transaction).encode(env)),
+        Err(err) => Ok((atom::error(), err).encode(env)),
+    }
+}
+
+#[rustler::nif]
+fn decode_transaction<'a>(env: Env<'a>, transaction_binary: Binary<'a>) -> NifResult<Term<'a>> {
+    // Sanitizing rejects out-of-range account indexes before they are looked up
+    let result = bincode::deserialize::<Transaction>(transaction_binary.as_slice())
+        .map_err(|e| error::Error::Deserialization(format!("Failed to deserialize transaction: {}", e)))
+        .and_then(|transaction| {
+            transaction
+                .sanitize()
+                .map(|_| transaction)
+                .map_err(|e| error::Error::Deserialization(format!("Malformed transaction: {}", e)))
+        });
+
+    match result {
+        Ok(transaction) => Ok((atom::ok(), ElixirDecodedTransaction::from(&transaction)).encode(env)),
+        Err(err) => Ok((atom::error(), err).encode(env)),
+    }
+}
+
+#[rustler::nif]
+fn get_asset_id<'a>(env: Env<'a>, tree: ElixirPubkey, nonce: u64) -> NifResult<Term<'a>> {
+    match utils::get_asset_id(tree.try_into()?, nonce) {
+        Ok(asset_id) => Ok((atom::ok(), ElixirPubkey::from(asset_id)).encode(env)),
+        Err(err) => Ok((atom::error(), err).encode(env)),
+    }
+}
+
+#[rustler::nif]
+fn get_asset_id_batch<'a>(env: Env<'a>, tree: ElixirPubkey, nonces: Vec<u64>) -> NifResult<Term<'a>> {
+    let tree: SolanaPubkey = tree.try_into()?;
+    let asset_ids: Result<Vec<ElixirPubkey>, _> = nonces
+        .into_iter()
+        .map(|nonce| utils::get_asset_id(tree, nonce).map(ElixirPubkey::from))
+        .collect();
+
+    match asset_ids {
+        Ok(asset_ids) => Ok((atom::ok(), asset_ids).encode(env)),
+        Err(err) => Ok((atom::error(), err).encode(env)),
+    }
+}
+
+#[rustler::nif]
+fn bubblegum_program_id() -> ElixirPubkey {
+    mpl_bubblegum::ID.into()
+}
+
+#[rustler::nif]
+fn account_compression_program_id() -> ElixirPubkey {
+    spl_account_compression::ID.into()
+}
+
+#[rustler::nif]
+fn noop_program_id() -> ElixirPubkey {
+    spl_noop::ID.into()
+}
+
+#[rustler::nif]
+fn merkle_tree_rent<'a>(
+    env: Env<'a>,
+    max_depth: u32,
+    max_buffer_size: u32,
+    canopy_depth: u32,
+) -> NifResult<Term<'a>> {
+    match utils::merkle_tree_rent(max_depth, max_buffer_size, canopy_depth) {
+        Ok((space, lamports)) => Ok((atom::ok(), space, lamports).encode(env)),
+        Err(err) => Ok((atom::error(), err).encode(env)),
+    }
+}
+
+#[rustler::nif]
+fn tree_capacity<'a>(env: Env<'a>, max_depth: u32) -> NifResult<Term<'a>> {
+    match utils::tree_capacity(max_depth) {
+        Ok(capacity) => Ok((atom::ok(), capacity).encode(env)),
+        Err(err) => Ok((atom::error(), err).encode(env)),
+    }
+}
+
+#[rustler::nif]
+fn derive_tree_config_pda<'a>(env: Env<'a>, merkle_tree: ElixirPubkey) -> NifResult<Term<'a>> {
+    let (tree_config, bump) = utils::derive_tree_config_pda(&merkle_tree.try_into()?);
+    Ok((atom::ok(), ElixirPubkey::from(tree_config), bump).encode(env))
+}
+
+#[rustler::nif]
+fn derive_asset_pda<'a>(env: Env<'a>, tree: ElixirPubkey, nonce: u64) -> NifResult<Term<'a>> {
+    let (asset_id, bump) = utils::derive_asset_pda(&tree.try_into()?, nonce);
+    Ok((atom::ok(), ElixirPubkey::from(asset_id), bump).encode(env))
+}
+
+#[rustler::nif]
+fn derive_bubblegum_signer_pda<'a>(env: Env<'a>) -> NifResult<Term<'a>> {
+    let (bubblegum_signer, bump) = utils::derive_bubblegum_signer_pda(&mpl_bubblegum::ID);
+    Ok((atom::ok(), ElixirPubkey::from(bubblegum_signer), bump).encode(env))
+}
+
+#[rustler::nif]
+fn parse_tree_config<'a>(env: Env<'a>, data: Binary<'a>) -> NifResult<Term<'a>> {
+    match utils::parse_tree_config(data.as_slice()) {
+        Ok(tree_config) => Ok((atom::ok(), ElixirTreeConfig::from(tree_config)).encode(env)),
+        Err(err) => Ok((atom::error(), err).encode(env)),
+    }
+}
+
+#[rustler::nif]
+fn parse_merkle_tree_header<'a>(env: Env<'a>, data: Binary<'a>) -> NifResult<Term<'a>> {
+    match utils::parse_merkle_tree_header(data.as_slice()) {
+        Ok(header) => Ok((atom::ok(), ElixirMerkleTreeHeader::from(header)).encode(env)),
+        Err(err) => Ok((atom::error(), err).encode(env)),
+    }
+}
+
+#[cfg(feature = "benchmark")]
+#[rustler::nif(schedule = "DirtyCpu")]
+fn benchmark_hashing<'a>(env: Env<'a>, iterations: u64) -> NifResult<Term<'a>> {
+    use mpl_bubblegum::types::{Creator, MetadataArgs, TokenProgramVersion, TokenStandard};
+
+    let metadata = MetadataArgs {
+        name: "Benchmark NFT".to_string(),
+        symbol: "BNCH".to_string(),
+        uri: "https://example.com/benchmark.json".to_string(),
+        seller_fee_basis_points: 500,
+        primary_sale_happened: false,
+        is_mutable: true,
+        edition_nonce: None,
+        token_standard: Some(TokenStandard::NonFungible),
+        collection: None,
+        uses: None,
+        token_program_version: TokenProgramVersion::Original,
+        creators: vec![Creator {
+            address: SolanaPubkey::new_unique(),
+            verified: false,
+            share: 100,
+        }],
+    };
+
+    match utils::benchmark_hashing(&metadata, iterations) {
+        Ok((elapsed, hashes_per_second)) => {
+            Ok((atom::ok(), elapsed.as_micros() as u64, hashes_per_second).encode(env))
+        }
+        Err(err) => Ok((atom::error(), err).encode(env)),
+    }
+}
+
+#[cfg(not(feature = "benchmark"))]
+#[rustler::nif]
+fn benchmark_hashing<'a>(env: Env<'a>, _iterations: u64) -> NifResult<Term<'a>> {
+    let err = error::Error::InvalidParameter("benchmark feature is not enabled".to_string());
+    Ok((atom::error(), err).encode(env))
+}
+
+#[rustler::nif]
+fn derive_pubkey_from_secret<'a>(env: Env<'a>, secret_key: Binary<'a>) -> NifResult<Term<'a>> {
+    match utils::keypair_from_secret_key(secret_key.as_slice()) {
+        Ok(keypair) => Ok((atom::ok(), ElixirPubkey::from(keypair.pubkey())).encode(env)),
+        Err(err) => Ok((atom::error(), err).encode(env)),
+    }
+}
+
+#[rustler::nif]
+fn sign_message<'a>(env: Env<'a>, secret_key: Binary<'a>, message: Binary<'a>) -> NifResult<Term<'a>> {
+    match utils::keypair_from_secret_key(secret_key.as_slice()) {
+        Ok(keypair) => {
+            let signature = keypair.sign_message(message.as_slice());
+            Ok((atom::ok(), signature.as_ref().to_vec()).encode(env))
+        }
+        Err(err) => Ok((atom::error(), err).encode(env)),
+    }
+}
+
+#[rustler::nif]
+fn verify_signature<'a>(
+    env: Env<'a>,
+    pubkey: ElixirPubkey,
+    message: Binary<'a>,
+    signature: Binary<'a>,
+) -> NifResult<Term<'a>> {
+    let signature = match Signature::try_from(signature.as_slice()) {
+        Ok(signature) => signature,
+        Err(e) => {
+            let err = error::Error::InvalidParameter(format!("Invalid signature: {}", e));
+            return Ok((atom::error(), err).encode(env));
+        }
+    };
+    let valid = signature.verify(&pubkey.bytes, message.as_slice());
+    Ok((atom::ok(), valid).encode(env))
+}
+
+#[rustler::nif]
+fn pubkey_is_on_curve<'a>(env: Env<'a>, pubkey: ElixirPubkey) -> NifResult<Term<'a>> {
+    match SolanaPubkey::try_from(pubkey.bytes.as_slice()) {
+        Ok(pubkey) => Ok((atom::ok(), pubkey.is_on_curve()).encode(env)),
+        Err(_) => {
+            let err = error::Error::InvalidParameter(format!("public key must be 32 bytes, got {}", pubkey.bytes.len()));
+            Ok((atom::error(), err).encode(env))
+        }
+    }
+}
+
+#[rustler::nif]
+fn keypair_from_json<'a>(env: Env<'a>, json: String) -> NifResult<Term<'a>> {
+    match utils::keypair_from_json(&json) {
+        Ok(keypair) => Ok((atom::ok(), ElixirPubkey::from(keypair.pubkey())).encode(env)),
+        Err(err) => Ok((atom::error(), err).encode(env)),
+    }
+}
+
+#[rustler::nif]
+fn keypair_from_base58<'a>(env: Env<'a>, secret_base58: String) -> NifResult<Term<'a>> {
+    match utils::keypair_from_base58(&secret_base58) {
+        Ok(keypair) => Ok((atom::ok(), ElixirPubkey::from(keypair.pubkey()), keypair.to_bytes().to_vec()).encode(env)),
+        Err(err) => Ok((atom::error(), err).encode(env)),
+    }
+}
+
+#[rustler::nif(schedule = "DirtyIo")]
+fn sign_and_submit_transaction<'a>(
+    env: Env<'a>,
+    rpc_url: String,
+    transaction_binary: Binary<'a>,
+    secret_keys: Vec<Binary<'a>>, // Changed to accept a vector of secret keys
+    commitment: String,
+    max_retries: u32,
+    skip_preflight: bool,
+    preflight_commitment: Option<String>,
+) -> NifResult<Term<'a>> {
+    let commitment = match utils::parse_commitment(&commitment) {
+        Ok(commitment) => commitment,
+        Err(err) => return Ok((atom::error(), err).encode(env)),
+    };
+    // Simulate at the confirmation commitment unless told otherwise
+    let preflight_commitment = match preflight_commitment.as_deref().map(utils::parse_commitment) {
+        Some(Ok(preflight_commitment)) => preflight_commitment,
+        Some(Err(err)) => return Ok((atom::error(), err).encode(env)),
+        None => commitment,
+    };
+    let rt = Runtime::new().map_err(|e| Error::Term(Box::new(format!("Failed to create runtime: {}", e))))?;
+    let result = rt.block_on(async {
+        let transaction_bytes = transaction_binary.as_slice();
+        let transaction: VersionedTransaction = bincode::deserialize(transaction_bytes)
+            .map_err(|e| error::Error::Deserialization(format!("Failed to deserialize transaction: {}", e)))?;
+
+        // Convert each secret key binary (raw or CLI JSON) to a Keypair
+        let secret_keys: Vec<&[u8]> = secret_keys.iter().map(|key| key.as_slice()).collect();
+        let keypairs = utils::keypairs_from_secret_keys(&secret_keys)?;
+        let keypair_refs: Vec<&Keypair> = keypairs.iter().collect();
+
+        let client = rpc::client_with_commitment(&rpc_url, commitment);
+        let config = RpcSendTransactionConfig {
+            skip_preflight,
+            preflight_commitment: Some(preflight_commitment.commitment),
+            ..RpcSendTransactionConfig::default()
+        };
+
+        let mut attempt = 0;
+        loop {
+            match submit_once(&client, transaction.clone(), &keypair_refs, config).await {
+                Ok(signature) => return Ok(signature.to_string()),
+                Err(SubmitError::Final(err)) => return Err(err),
+                Err(SubmitError::Retryable(err)) if attempt >= max_retries => return Err(err),
+                Err(SubmitError::Retryable(_)) => {
+                    // The cached blockhash may be what expired, so fetch a new one
+                    invalidate_cached_blockhash(client.url().as_str());
+                    // Back off 500ms, 1s, 2s, ... before re-signing with a fresh blockhash
+                    tokio::time::sleep(Duration::from_millis(500 << attempt.min(6))).await;
+                    attempt += 1;
+                }
+            }
+        }
+    });
+
+    match result {
+        Ok(signature) => Ok((atom::ok(), signature).encode(env)),
+        Err(err) => Ok((atom::error(), err).encode(env)),
+    }
+}
+
+/// How long a fetched blockhash is reused for. Blockhashes stay valid for
+/// about 150 slots (~60s), so this leaves ample time to land the transaction.
+const BLOCKHASH_CACHE_TTL: Duration = Duration::from_secs(30);
+
+/// Latest blockhash per RPC URL, shared by every submission in the process.
+static BLOCKHASH_CACHE: Mutex<BTreeMap<String, (Hash, Instant)>> = Mutex::new(BTreeMap::new());
+
+/// Returns the cached blockhash for the client's RPC URL while it is fresh,
+/// fetching and caching a new one otherwise.
+fn cached_blockhash(client: &RpcClient) -> Result<Hash, error::Error> {
+    let url = client.url();
+    if let Some((blockhash, fetched_at)) = lock_blockhash_cache().get(&url) {
+        if fetched_at.elapsed() < BLOCKHASH_CACHE_TTL {
+            return Ok(*blockhash);
+        }
+    }
+
+    let blockhash = client.get_latest_blockhash()
+        .map_err(|e| error::Error::Rpc(format!("Failed to get blockhash: {}", e)))?;
+    lock_blockhash_cache().insert(url, (blockhash, Instant::now()));
+    Ok(blockhash)
+}
+
+fn invalidate_cached_blockhash(url: &str) {
+    lock_blockhash_cache().remove(url);
+}
+
+/// The cache only holds plain data, so a panic while it was locked cannot
+/// leave it inconsistent and the poison can be ignored.
+fn lock_blockhash_cache() -> MutexGuard<'static, BTreeMap<String, (Hash, Instant)>> {
+    BLOCKHASH_CACHE.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+#[rustler::nif]
+fn invalidate_blockhash_cache() -> rustler::Atom {
+    lock_blockhash_cache().clear();
+    atom::ok()
+}
+
+/// Why a submission attempt failed, which decides whether it may be retried.
+enum SubmitError {
+    /// Nothing reached the cluster, or what did can no longer land because its
+    /// blockhash expired, so re-signing with a fresh blockhash is safe.
+    Retryable(error::Error),
+    /// Retrying would not help or could execute the transaction twice: signing
+    /// failed, the transaction failed on-chain, or its status is unknown.
+    Final(error::Error),
+}
+
+/// Signs the transaction with a recent blockhash, sends it and waits until it
+/// reaches the client's commitment or the blockhash expires.
+async fn submit_once(
+    client: &RpcClient,
+    mut transaction: VersionedTransaction,
+    keypairs: &[&Keypair],
+    config: RpcSendTransactionConfig,
+) -> Result<Signature, SubmitError> {
+    let recent_blockhash = cached_blockhash(client).map_err(SubmitError::Retryable)?;
+    utils::sign_versioned_transaction(&mut transaction, keypairs, recent_blockhash, false)
+        .map_err(SubmitError::Final)?;
+    log::debug!("Transaction signed with signature: {}", transaction.signatures[0]);
+
+    let signature = match client.send_transaction_with_config(&transaction, config) {
+        Ok(signature) => signature,
+        Err(e) => match classify_send_error(&e) {
+            Some(kind) => return Err(kind(error::Error::Rpc(format!("Failed to submit transaction: {}", e)))),
+            // The request may still have reached the leader, so wait for this
+            // signature to land or expire before signing again
+            None => transaction.signatures[0],
+        },
+    };
+
+    // From here on the transaction may land, so only an expired blockhash allows a retry
+    loop {
+        let status = client.get_signature_status_with_commitment(&signature, client.commitment())
+            .map_err(|e| SubmitError::Final(error::Error::Rpc(format!(
+                "Failed to get the status of transaction {}: {}",
+                signature, e
+            ))))?;
+        match status {
+            Some(Ok(())) => return Ok(signature),
+            Some(Err(e)) => {
+                return Err(SubmitError::Final(error::Error::Rpc(format!("Transaction {} failed: {}", signature, e))))
+            }
+            None => {
+                let valid = client.is_blockhash_valid(&recent_blockhash, CommitmentConfig::processed())
+                    .map_err(|e| SubmitError::Final(error::Error::Rpc(format!(
+                        "Failed to check the blockhash of transaction {}: {}",
+                        signature, e
+                    ))))?;
+                if !valid {
+                    return Err(SubmitError::Retryable(error::Error::Rpc(format!(
+                        "Transaction {} expired before confirmation",
+                        signature
+                    ))));
+                }
+                tokio::time::sleep(Duration::from_millis(500)).await;
+            }
+        }
+    }
+}
+
+/// Tells whether a failed send may be retried, or returns `None` when the
+/// transaction could have reached the cluster despite the error.
+fn classify_send_error(err: &ClientError) -> Option<fn(error::Error) -> SubmitError> {
+    match (err.kind(), err.get_transaction_error()) {
+        // Nothing was sent
+        (ClientErrorKind::Reqwest(e), _) if e.is_connect() => Some(SubmitError::Retryable),
+        // Preflight rejected it before forwarding, and only a stale blockhash
+        // is fixed by re-signing
+        (_, Some(TransactionError::BlockhashNotFound)) => Some(SubmitError::Retryable),
+        (_, Some(_)) => Some(SubmitError::Final),
+        // The node answered and refused the transaction
+        (ClientErrorKind::RpcError(RpcError::RpcResponseError { .. }), None) => Some(SubmitError::Final),
+        _ => None,
+    }
+}
+
+#[rustler::nif(schedule = "DirtyIo")]
+fn send_raw_transaction<'a>(
+    env: Env<'a>,
+    rpc_url: String,
+    transaction_binary: Binary<'a>,
+    skip_preflight: bool,
+) -> NifResult<Term<'a>> {
+    let transaction = match utils::signed_transaction(transaction_binary.as_slice()) {
+        Ok(transaction) => transaction,
+        Err(err) => return Ok((atom::error(), err).encode(env)),
+    };
+
+    // Create a runtime for async operations
+    let rt = Runtime::new()
+        .map_err(|e| Error::Term(Box::new(format!("Failed to create tokio runtime: {}", e))))?;
+
+    let result = rt.block_on(async {
+        let client = rpc::client(&rpc_url);
+        let config = RpcSendTransactionConfig {
+            skip_preflight,
+            ..RpcSendTransactionConfig::default()
+        };
+        client
+            .send_transaction_with_config(&transaction, config)
+            .map_err(|e| error::Error::Rpc(format!("Failed to submit transaction: {}", e)))
+    });
+
+    match result {
+        Ok(signature) => Ok((atom::ok(), signature.to_string()).encode(env)),
+        Err(err) => Ok((atom::error(), err).encode(env)),
+    }
+}
+
+#[rustler::nif(schedule = "DirtyIo")]
+fn refresh_and_submit<'a>(
+    env: Env<'a>,
+    rpc_url: String,
+    transaction_binary: Binary<'a>,
+    secret_keys: Vec<Binary<'a>>,
+) -> NifResult<Term<'a>> {
+    // Create a runtime for async operations
+    let rt = Runtime::new()
+        .map_err(|e| Error::Term(Box::new(format!("Failed to create tokio runtime: {}", e))))?;
+
+    let result = rt.block_on(async {
+        let client = rpc::client(&rpc_url);
+        // The input is assumed stale, so skip the cache as well
+        invalidate_cached_blockhash(client.url().as_str());
+        let recent_blockhash = cached_blockhash(&client)?;
+
+        let secret_keys: Vec<&[u8]> = secret_keys.iter().map(|key| key.as_slice()).collect();
+        let transaction = utils::resign_transaction(transaction_binary.as_slice(), &secret_keys, recent_blockhash)?;
+        client
+            .send_transaction(&transaction)
+            .map_err(|e| error::Error::Rpc(format!("Failed to submit transaction: {}", e)))
+    });
+
+    match result {
+        Ok(signature) => Ok((atom::ok(), signature.to_string()).encode(env)),
+        Err(err) => Ok((atom::error(), err).encode(env)),
+    }
+}
+
+#[rustler::nif(schedule = "DirtyIo")]
+fn submit_transactions_batch<'a>(
+    env: Env<'a>,
+    rpc_url: String,
+    signed_transactions: Vec<Binary<'a>>,
+) -> NifResult<Term<'a>> {
+    // Keeps bursts within what public RPC endpoints tolerate
+    const MAX_IN_FLIGHT: usize = 20;
+
+    // Decode up front, as binaries cannot leave the calling thread
+    let transactions: Vec<Result<VersionedTransaction, error::Error>> = signed_transactions
+        .iter()
+        .map(|binary| utils::signed_transaction(binary.as_slice()))
+        .collect();
+
+    // The pooled client's connections live on the shared runtime
+    let rt = rpc::runtime()
+        .map_err(|e| Error::Term(Box::new(format!("Failed to create tokio runtime: {}", e))))?;
+
+    let results = rt.block_on(async {
+        let client = rpc::nonblocking_client(&rpc_url);
+        let in_flight = Arc::new(Semaphore::new(MAX_IN_FLIGHT));
+
+        let handles: Vec<_> = transactions
+            .into_iter()
+            .map(|transaction| {
+                let client = Arc::clone(&client);
+                let in_flight = Arc::clone(&in_flight);
+                tokio::spawn(async move {
+                    let transaction = transaction?;
+                    let _permit = in_flight
+                        .acquire_owned()
+                        .await
+                        .map_err(|e| error::Error::Rpc(format!("Failed to schedule submission: {}", e)))?;
+                    client
+                        .send_transaction(&transaction)
+                        .await
+                        .map(|signature| signature.to_string())
+                        .map_err(|e| error::Error::Rpc(format!("Failed to submit transaction: {}", e)))
+                })
+            })
+            .collect();
+
+        // Awaiting the handles in order keeps the results in input order
+        let mut results = Vec::with_capacity(handles.len());
+        for handle in handles {
+            results.push(handle.await.unwrap_or_else(|e| {
+                Err(error::Error::Rpc(format!("Submission task failed: {}", e)))
+            }));
+        }
+        results
+    });
+
+    Ok((atom::ok(), results).encode(env))
+}
+
+#[rustler::nif]
+fn sign_transaction<'a>(
+    env: Env<'a>,
+    transaction_binary: Binary<'a>,
+    secret_keys: Vec<Binary<'a>>,
+    recent_blockhash: String,
+) -> NifResult<Term<'a>> {
+    let recent_blockhash = match Hash::from_str(&recent_blockhash) {
+        Ok(hash) => hash,
+        Err(e) => {
+            let err = error::Error::InvalidParameter(format!("Invalid blockhash: {}", e));
+            return Ok((atom::error(), err).encode(env));
+        }
+    };
+    let secret_keys: Vec<&[u8]> = secret_keys.iter().map(|key| key.as_slice()).collect();
+
+    match utils::sign_transaction(transaction_binary.as_slice(), &secret_keys, recent_blockhash) {
+        Ok(transaction) => Ok((atom::ok(), transaction).encode(env)),
+        Err(err) => Ok((atom::error(), err).encode(env)),
+    }
+}
+
+#[rustler::nif]
+fn prepend_priority_fee<'a>(
+    env: Env<'a>,
+    transaction_binary: Binary<'a>,
+    compute_unit_limit: u32,
+    compute_unit_price: u64,
+) -> NifResult<Term<'a>> {
+    match instructions::prepend_priority_fee(transaction_binary.as_slice(), compute_unit_limit, compute_unit_price) {
+        Ok(transaction) => Ok((atom::ok(), transaction).encode(env)),
+        Err(err) => Ok((atom::error(), err).encode(env)),
+    }
+}
+
+#[rustler::nif]
+fn partial_sign_transaction<'a>(
+    env: Env<'a>,
+    transaction_binary: Binary<'a>,
+    secret_keys: Vec<Binary<'a>>,
+    recent_blockhash: String,
+) -> NifResult<Term<'a>> {
+    let recent_blockhash = match Hash::from_str(&recent_blockhash) {
+        Ok(hash) => hash,
+        Err(e) => {
+            let err = error::Error::InvalidParameter(format!("Invalid blockhash: {}", e));
+            return Ok((atom::error(), err).encode(env));
+        }
+    };
+    let secret_keys: Vec<&[u8]> = secret_keys.iter().map(|key| key.as_slice()).collect();
+
+    match utils::partial_sign_transaction(transaction_binary.as_slice(), &secret_keys, recent_blockhash) {
+        Ok(transaction) => Ok((atom::ok(), transaction).encode(env)),
+        Err(err) => Ok((atom::error(), err).encode(env)),
+    }
+}
+
+mod signature_atoms {
+    rustler::atoms! {
+        unsigned_accounts,
+    }
+}
+
+#[rustler::nif]
+fn verify_transaction_signatures<'a>(env: Env<'a>, transaction_binary: Binary<'a>) -> NifResult<Term<'a>> {
+    match utils::unsigned_accounts(transaction_binary.as_slice()) {
+        Ok(unsigned) if unsigned.is_empty() => Ok((atom::ok(), true).encode(env)),
+        Ok(unsigned) => {
+            let unsigned: Vec<ElixirPubkey> = unsigned.into_iter().map(ElixirPubkey::from).collect();
+            Ok((atom::error(), (signature_atoms::unsigned_accounts(), unsigned)).encode(env))
+        }
+        Err(err) => Ok((atom::error(), err).encode(env)),
+    }
+}
+
+#[rustler::nif(schedule = "DirtyIo")]
+fn get_transaction_status<'a>(env: Env<'a>, signature: String, commitment: String) -> NifResult<Term<'a>> {
+    let commitment = match utils::parse_commitment(&commitment) {
+        Ok(commitment) => commitment,
+        Err(err) => return Ok((atom::error(), err).encode(env)),
+    };
+
+    // Create a runtime for async operations
+    let rt = Runtime::new()
+        .map_err(|e| Error::Term(Box::new(format!("Failed to create tokio runtime: {}", e))))?;
+    
+    let result = rt.block_on(async {
+        // Connect to Solana Devnet to check the transaction status
+        let client = rpc::client_with_commitment("http://127.0.0.1:8899", commitment);
+        
+        // Parse the signature string
+        let signature = match solana_sdk::signature::Signature::from_str(&signature) {
+            Ok(sig) => sig,
+            Err(e) => return Err(error::Error::InvalidParameter(format!("Invalid signature format: {}", e))),
+        };
+        
+        // Get the transaction status
+        match client.get_signature_status(&signature) {
+            Ok(status) => {
+                match status {
+                    Some(Ok(_)) => Ok("confirmed".to_string()),
+                    Some(Err(e)) => Ok(format!("failed: {}", e)),
+                    None => Ok("not_found".to_string()),
+                }
+            },
+            Err(e) => Err(error::Error::Rpc(format!("Failed to get transaction status: {}", e))),
+        }
+    });
+    
+    match result {
+        Ok(status) => Ok((atom::ok(), status).encode(env)),
+        Err(err) => Ok((atom::error(), err).encode(env)),
+    }
+}
+
+#[rustler::nif(schedule = "DirtyIo")]
+fn get_transaction<'a>(
+    env: Env<'a>,
+    rpc_url: String,
+    signature: String,
+    commitment: String,
+) -> NifResult<Term<'a>> {
+    let commitment = match utils::parse_commitment(&commitment) {
+        Ok(commitment) => commitment,
+        Err(err) => return Ok((atom::error(), err).encode(env)),
+    };
+    let signature = match Signature::from_str(&signature) {
+        Ok(signature) => signature,
+        Err(e) => {
+            let err = error::Error::InvalidParameter(format!("Invalid signature format: {}", e));
+            return Ok((atom::error(), err).encode(env));
+        }
+    };
+
+    // Create a runtime for async operations
+    let rt = Runtime::new()
+        .map_err(|e| Error::Term(Box::new(format!("Failed to create tokio runtime: {}", e))))?;
+
+    let result = rt.block_on(async {
+        let client = rpc::client(&rpc_url);
+        let config = RpcTransactionConfig {
+            encoding: Some(UiTransactionEncoding::Json),
+            commitment: Some(commitment),
+            max_supported_transaction_version: Some(0),
+        };
+        client
+            .get_transaction_with_config(&signature, config)
+            .map(ElixirTransactionDetails::from)
+            .map_err(|e| error::Error::Rpc(format!("Failed to get transaction: {}", e)))
+    });
+
+    match result {
+        Ok(details) => Ok((atom::ok(), details).encode(env)),
+        Err(err) => Ok((atom::error(), err).encode(env)),
+    }
+}
+
+#[rustler::nif(schedule = "DirtyIo")]
+fn get_account_info<'a>(env: Env<'a>, pubkey: ElixirPubkey, commitment: String) -> NifResult<Term<'a>> {
+    let commitment = match utils::parse_commitment(&commitment) {
+        Ok(commitment) => commitment,
+        Err(err) => return Ok((atom::error(), err).encode(env)),
+    };
+
+    // Create a runtime for async operations
+    let rt = Runtime::new()
+        .map_err(|e| Error::Term(Box::new(format!("Failed to create tokio runtime: {}", e))))?;
+    
+    let result = rt.block_on(async {
+        // Connect to Solana Devnet to fetch account info
+        let client = rpc::client_with_commitment("http://127.0.0.1:8899", commitment);
+        
+        // Convert ElixirPubkey to Solana Pubkey
+        let pubkey: SolanaPubkey = pubkey.try_into()?;
+        
+        // Get the account info
+        match client.get_account(&pubkey) {
+            Ok(account) => Ok(ElixirAccountInfo::from(account)),
+            Err(e) => Err(error::Error::Rpc(format!("Failed to get account info: {}", e))),
+        }
+    });
+    
+    match result {
+        Ok(account_data) => Ok((atom::ok(), account_data).encode(env)),
+        Err(err) => Ok((atom::error(), err).encode(env)),
+    }
+}
+
+#[rustler::nif(schedule = "DirtyIo")]
+fn batch_get_account_info<'a>(
+    env: Env<'a>,
+    rpc_url: String,
+    pubkeys: Vec<ElixirPubkey>,
+) -> NifResult<Term<'a>> {
+    // getMultipleAccounts accepts at most this many keys per request
+    const MAX_ACCOUNTS: usize = 100;
+
+    if pubkeys.len() > MAX_ACCOUNTS {
+        let err = error::Error::InvalidParameter(format!(
+            "at most {} accounts can be fetched at once, got {}",
+            MAX_ACCOUNTS,
+            pubkeys.len()
+        ));
+        return Ok((atom::error(), err).encode(env));
+    }
+
+    // Create a runtime for async operations
+    let rt = Runtime::new()
+        .map_err(|e| Error::Term(Box::new(format!("Failed to create tokio runtime: {}", e))))?;
+
+    let result = rt.block_on(async {
+        let client = rpc::client(&rpc_url);
+        let pubkeys: Vec<SolanaPubkey> = pubkeys.into_iter().map(SolanaPubkey::try_from).collect::<Result<_, _>>()?;
+
+        client
+            .get_multiple_accounts(&pubkeys)
+            .map(|accounts| {
+                accounts
+                    .into_iter()
+                    .map(|account| account.map(ElixirAccountInfo::from))
+                    .collect::<Vec<_>>()
+            })
+            .map_err(|e| error::Error::Rpc(format!("Failed to get multiple accounts: {}", e)))
+    });
+
+    match result {
+        Ok(accounts) => Ok((atom::ok(), accounts).encode(env)),
+        Err(err) => Ok((atom::error(), err).encode(env)),
+    }
+}
+
+#[rustler::nif(schedule = "DirtyIo")]
+fn get_program_accounts<'a>(
+    env: Env<'a>,
+    rpc_url: String,
+    program_id: ElixirPubkey,
+    filters: Vec<ElixirRpcFilter>,
+) -> NifResult<Term<'a>> {
+    // Create a runtime for async operations
+    let rt = Runtime::new()
+        .map_err(|e| Error::Term(Box::new(format!("Failed to create tokio runtime: {}", e))))?;
+
+    let result = rt.block_on(async {
+        let client = rpc::client(&rpc_url);
+        let filters: Vec<RpcFilterType> = filters.into_iter().map(|filter| filter.0).collect();
+        let config = RpcProgramAccountsConfig {
+            filters: (!filters.is_empty()).then_some(filters),
+            ..RpcProgramAccountsConfig::default()
+        };
+
+        client
+            .get_program_accounts_with_config(&program_id.try_into()?, config)
+            .map(|accounts| {
+                accounts
+                    .into_iter()
+                    .map(|(pubkey, account)| (ElixirPubkey::from(pubkey), ElixirAccountInfo::from(account)))
+                    .collect::<Vec<_>>()
+            })
+            .map_err(|e| error::Error::Rpc(format!("Failed to get program accounts: {}", e)))
+    });
+
+    match result {
+        Ok(accounts) => Ok((atom::ok(), accounts).encode(env)),
+        Err(err) => Ok((atom::error(), err).encode(env)),
+    }
+}
+
+#[rustler::nif(schedule = "DirtyIo")]
+fn get_signature_statuses<'a>(
+    env: Env<'a>,
+    rpc_url: String,
+    signatures: Vec<String>,
+) -> NifResult<Term<'a>> {
+    // getSignatureStatuses accepts at most this many signatures per request
+    const MAX_SIGNATURES: usize = 256;
+
+    if signatures.len() > MAX_SIGNATURES {
+        let err = error::Error::InvalidParameter(format!(
+            "at most {} signatures can be queried at once, got {}",
+            MAX_SIGNATURES,
+            signatures.len()
+        ));
+        return Ok((atom::error(), err).encode(env));
+    }
+
+    let signatures = match signatures
+        .iter()
+        .map(|signature| {
+            Signature::from_str(signature).map_err(|e| {
+                error::Error::InvalidParameter(format!("Invalid signature format: {}", e))
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()
+    {
+        Ok(signatures) => signatures,
+        Err(err) => return Ok((atom::error(), err).encode(env)),
+    };
+
+    // Create a runtime for async operations
+    let rt = Runtime::new()
+        .map_err(|e| Error::Term(Box::new(format!("Failed to create tokio runtime: {}", e))))?;
+
+    let result = rt.block_on(async {
+        let client = rpc::client(&rpc_url);
+        client
+            .get_signature_statuses(&signatures)
+            .map(|response| {
+                response
+                    .value
+                    .into_iter()
+                    .map(|status| status.map(ElixirSignatureStatus::from))
+                    .collect::<Vec<_>>()
+            })
+            .map_err(|e| error::Error::Rpc(format!("Failed to get signature statuses: {}", e)))
+    });
+
+    match result {
+        Ok(statuses) => Ok((atom::ok(), statuses).encode(env)),
+        Err(err) => Ok((atom::error(), err).encode(env)),
+    }
+}
+
+#[rustler::nif(schedule = "DirtyIo")]
+fn is_blockhash_valid<'a>(env: Env<'a>, blockhash: String, rpc_url: String) -> NifResult<Term<'a>> {
+    // Create a runtime for async operations
+    let rt = Runtime::new()
+        .map_err(|e| Error::Term(Box::new(format!("Failed to create tokio runtime: {}", e))))?;
+
+    let result = rt.block_on(async {
+        let client = rpc::client(&rpc_url);
+
+        // Parse the blockhash string
+        let blockhash = Hash::from_str(&blockhash)
+            .map_err(|e| error::Error::InvalidParameter(format!("Invalid blockhash: {}", e)))?;
+
+        client
+            .is_blockhash_valid(&blockhash, client.commitment())
+            .map_err(|e| error::Error::Rpc(format!("Failed to check blockhash validity: {}", e)))
+    });
+
+    match result {
+        Ok(valid) => Ok((atom::ok(), valid).encode(env)),
+        Err(err) => Ok((atom::error(), err).encode(env)),
+    }
+}
+
+#[rustler::nif(schedule = "DirtyIo")]
+fn get_nonce_blockhash<'a>(env: Env<'a>, rpc_url: String, nonce_account: ElixirPubkey) -> NifResult<Term<'a>> {
+    let rt = Runtime::new()
+        .map_err(|e| Error::Term(Box::new(format!("Failed to create tokio runtime: {}", e))))?;
+
+    let result = rt.block_on(async {
+        let client = rpc::client(&rpc_url);
+        let account = client
+            .get_account(&nonce_account.try_into()?)
+            .map_err(|e| error::Error::Rpc(format!("Failed to get nonce account: {}", e)))?;
+        if account.owner != solana_sdk::system_program::ID {
+            return Err(error::Error::InvalidParameter(
+                "Nonce account is not owned by the system program".to_string(),
+            ));
+        }
+        utils::nonce_blockhash(&account.data)
+    });
+
+    match result {
+        Ok(blockhash) => Ok((atom::ok(), blockhash.to_string()).encode(env)),
+        Err(err) => Ok((atom::error(), err).encode(env)),
+    }
+}
+
+#[rustler::nif(schedule = "DirtyIo")]
+fn get_tree_num_minted<'a>(env: Env<'a>, rpc_url: String, tree_config: ElixirPubkey) -> NifResult<Term<'a>> {
+    let rt = Runtime::new()
+        .map_err(|e| Error::Term(Box::new(format!("Failed to create tokio runtime: {}", e))))?;
+
+    let result = rt.block_on(async {
+        let client = rpc::client(&rpc_url);
+        let account = client
+            .get_account(&tree_config.try_into()?)
+            .map_err(|e| error::Error::Rpc(format!("Failed to get tree config account: {}", e)))?;
+        utils::parse_tree_config(&account.data).map(|tree_config| tree_config.num_minted)
+    });
+
+    match result {
+        Ok(num_minted) => Ok((atom::ok(), num_minted).encode(env)),
+        Err(err) => Ok((atom::error(), err).encode(env)),
+    }
+}
+
+#[rustler::nif(schedule = "DirtyIo")]
+fn remaining_capacity<'a>(env: Env<'a>, rpc_url: String, tree_config: ElixirPubkey) -> NifResult<Term<'a>> {
+    let rt = Runtime::new()
+        .map_err(|e| Error::Term(Box::new(format!("Failed to create tokio runtime: {}", e))))?;
+
+    let result = rt.block_on(async {
+        let client = rpc::client(&rpc_url);
+        let account = client
+            .get_account(&tree_config.try_into()?)
+            .map_err(|e| error::Error::Rpc(format!("Failed to get tree config account: {}", e)))?;
+        utils::parse_tree_config(&account.data)
+            .map(|tree_config| tree_config.total_mint_capacity.saturating_sub(tree_config.num_minted))
+    });
+
+    match result {
+        Ok(remaining) => Ok((atom::ok(), remaining).encode(env)),
+        Err(err) => Ok((atom::error(), err).encode(env)),
+    }
+}
+
+#[rustler::nif(schedule = "DirtyIo")]
+fn get_token_account_balance<'a>(env: Env<'a>, rpc_url: String, token_account: ElixirPubkey) -> NifResult<Term<'a>> {
+    let rt = Runtime::new()
+        .map_err(|e| Error::Term(Box::new(format!("Failed to create tokio runtime: {}", e))))?;
+
+    let result = rt.block_on(async {
+        let client = rpc::client(&rpc_url);
+        let balance = client
+            .get_token_account_balance(&token_account.try_into()?)
+            .map_err(|e| error::Error::Rpc(format!("Failed to get token account balance: {}", e)))?;
+        let amount = balance
+            .amount
+            .parse()
+            .map_err(|e| error::Error::Conversion(format!("Invalid token amount {}: {}", balance.amount, e)))?;
+        Ok::<_, error::Error>(ElixirTokenAmount {
+            amount,
+            decimals: balance.decimals,
+            ui_amount_string: balance.ui_amount_string,
+        })
+    });
+
+    match result {
+        Ok(balance) => Ok((atom::ok(), balance).encode(env)),
+        Err(err) => Ok((atom::error(), err).encode(env)),
+    }
+}
+
+#[rustler::nif(schedule = "DirtyIo")]
+fn estimate_fee<'a>(env: Env<'a>, rpc_url: String, transaction_binary: Binary<'a>) -> NifResult<Term<'a>> {
+    let transaction: Transaction = match bincode::deserialize(transaction_binary.as_slice()) {
+        Ok(transaction) => transaction,
+        Err(e) => {
+            let err = error::Error::Deserialization(format!("Failed to deserialize transaction: {}", e));
+            return Ok((atom::error(), err).encode(env));
+        }
+    };
+
+    // Create a runtime for async operations
+    let rt = Runtime::new()
+        .map_err(|e| Error::Term(Box::new(format!("Failed to create tokio runtime: {}", e))))?;
+
+    let result = rt.block_on(async {
+        let client = rpc::client(&rpc_url);
+        // The builders leave the blockhash unset, and the RPC node only prices
+        // messages whose blockhash it still knows
+        let mut message = transaction.message;
+        message.recent_blockhash = cached_blockhash(&client)?;
+        let message = bincode::serialize(&message)
+            .map_err(|e| error::Error::Conversion(format!("Failed to serialize message: {}", e)))?;
+
+        // Called directly rather than through get_fee_for_message, which turns
+        // an expired blockhash into an error instead of a null fee
+        client
+            .send::<Response<Option<u64>>>(
+                RpcRequest::GetFeeForMessage,
+                json!([BASE64_STANDARD.encode(message), client.commitment()]),
+            )
+            .map(|response| response.value)
+            .map_err(|e| error::Error::Rpc(format!("Failed to get fee for message: {}", e)))
+    });
+
+    match result {
+        Ok(fee) => Ok((atom::ok(), fee).encode(env)),
+        Err(err) => Ok((atom::error(), err).encode(env)),
+    }
+}
+
+#[rustler::nif(schedule = "DirtyIo")]
+fn estimate_compute_units<'a>(env: Env<'a>, rpc_url: String, transaction_binary: Binary<'a>) -> NifResult<Term<'a>> {
+    let transaction: Transaction = match bincode::deserialize(transaction_binary.as_slice()) {
+        Ok(transaction) => transaction,
+        Err(e) => {
+            let err = error::Error::Deserialization(format!("Failed to deserialize transaction: {}", e));
+            return Ok((atom::error(), err).encode(env));
+        }
+    };
+
+    // Create a runtime for async operations
+    let rt = Runtime::new()
+        .map_err(|e| Error::Term(Box::new(format!("Failed to create tokio runtime: {}", e))))?;
+
+    let result = rt.block_on(async {
+        let client = rpc::client(&rpc_url);
+        // Unsigned transactions built without a blockhash simulate as-is
+        let config = RpcSimulateTransactionConfig {
+            sig_verify: false,
+            replace_recent_blockhash: true,
+            ..RpcSimulateTransactionConfig::default()
+        };
+        let response = client
+            .simulate_transaction_with_config(&transaction, config)
+            .map_err(|e| error::Error::Rpc(format!("Failed to simulate transaction: {}", e)))?;
+
+        // A failed simulation stops early, so its unit count would undercount
+        Ok::<_, error::Error>(match response.value.err {
+            Some(_) => None,
+            None => response.value.units_consumed,
+        })
+    });
+
+    match result {
+        Ok(units) => Ok((atom::ok(), units).encode(env)),
+        Err(err) => Ok((atom::error(), err).encode(env)),
+    }
+}
+
+#[rustler::nif(schedule = "DirtyIo")]
+fn get_recent_prioritization_fees<'a>(
+    env: Env<'a>,
+    rpc_url: String,
+    accounts: Vec<ElixirPubkey>,
+) -> NifResult<Term<'a>> {
+    // Create a runtime for async operations
+    let rt = Runtime::new()
+        .map_err(|e| Error::Term(Box::new(format!("Failed to create tokio runtime: {}", e))))?;
+
+    let accounts: Vec<SolanaPubkey> = accounts.into_iter().map(SolanaPubkey::try_from).collect::<Result<_, _>>()?;
+    let result = rt.block_on(async {
+        let client = rpc::client(&rpc_url);
+        client
+            .get_recent_prioritization_fees(&accounts)
+            .map_err(|e| error::Error::Rpc(format!("Failed to get recent prioritization fees: {}", e)))
+    });
+
+    match result {
+        Ok(fees) => {
+            let fees: Vec<(u64, u64)> = fees.into_iter().map(|fee| (fee.slot, fee.prioritization_fee)).collect();
+            Ok((atom::ok(), fees).encode(env))
+        }
+        Err(err) => Ok((atom::error(), err).encode(env)),
+    }
+}
+
+#[rustler::nif(schedule = "DirtyIo")]
+fn get_slot<'a>(env: Env<'a>, rpc_url: String, commitment: String) -> NifResult<Term<'a>> {
+    let commitment = match utils::parse_commitment(&commitment) {
+        Ok(commitment) => commitment,
+        Err(err) => return Ok((atom::error(), err).encode(env)),
+    };
+
+    // Create a runtime for async operations
+    let rt = Runtime::new()
+        .map_err(|e| Error::Term(Box::new(format!("Failed to create tokio runtime: {}", e))))?;
+
+    let result = rt.block_on(async {
+        let client = rpc::client_with_commitment(&rpc_url, commitment);
+        client
+            .get_slot()
+            .map_err(|e| error::Error::Rpc(format!("Failed to get slot: {}", e)))
+    });
+
+    match result {
+        Ok(value) => Ok((atom::ok(), value).encode(env)),
+        Err(err) => Ok((atom::error(), err).encode(env)),
+    }
+}
+
+#[rustler::nif(schedule = "DirtyIo")]
+fn get_block_height<'a>(env: Env<'a>, rpc_url: String, commitment: String) -> NifResult<Term<'a>> {
+    let commitment = match utils::parse_commitment(&commitment) {
+        Ok(commitment) => commitment,
+        Err(err) => return Ok((atom::error(), err).encode(env)),
+    };
+
+    // Create a runtime for async operations
+    let rt = Runtime::new()
+        .map_err(|e| Error::Term(Box::new(format!("Failed to create tokio runtime: {}", e))))?;
+
+    let result = rt.block_on(async {
+        let client = rpc::client_with_commitment(&rpc_url, commitment);
+        client
+            .get_block_height()
+            .map_err(|e| error::Error::Rpc(format!("Failed to get block height: {}", e)))
+    });
+
+    match result {
+        Ok(value) => Ok((atom::ok(), value).encode(env)),
+        Err(err) => Ok((atom::error(), err).encode(env)),
+    }
+}
+
+#[rustler::nif(schedule = "DirtyIo")]
+fn get_health<'a>(env: Env<'a>, rpc_url: String) -> NifResult<Term<'a>> {
+    // Create a runtime for async operations
+    let rt = Runtime::new()
+        .map_err(|e| Error::Term(Box::new(format!("Failed to create tokio runtime: {}", e))))?;
+
+    let result = rt.block_on(async {
+        let client = rpc::client(&rpc_url);
+        client
+            .get_health()
+            .map_err(|e| error::Error::Rpc(format!("Node is unhealthy: {}", e)))
+    });
+
+    match result {
+        Ok(()) => Ok((atom::ok(), atom::ok()).encode(env)),
+        Err(err) => Ok((atom::error(), err).encode(env)),
+    }
+}
+
+#[rustler::nif(schedule = "DirtyIo")]
+fn get_first_available_block<'a>(env: Env<'a>, rpc_url: String) -> NifResult<Term<'a>> {
+    // Create a runtime for async operations
+    let rt = Runtime::new()
+        .map_err(|e| Error::Term(Box::new(format!("Failed to create tokio runtime: {}", e))))?;
+
+    let result = rt.block_on(async {
+        let client = rpc::client(&rpc_url);
+        client
+            .get_first_available_block()
+            .map_err(|e| error::Error::Rpc(format!("Failed to get first available block: {}", e)))
+    });
+
+    match result {
+        Ok(slot) => Ok((atom::ok(), slot).encode(env)),
+        Err(err) => Ok((atom::error(), err).encode(env)),
+    }
+}
+
+#[rustler::nif(schedule = "DirtyIo")]
+fn request_airdrop<'a>(
+    env: Env<'a>,
+    rpc_url: String,
+    pubkey: ElixirPubkey,
+    lamports: u64,
+) -> NifResult<Term<'a>> {
+    // Genesis hash of mainnet-beta, which has no faucet
+    const MAINNET_GENESIS_HASH: &str = "5eykt4UsFv8P8NJdTREpY1vzqKqZKvdpKuc147dw2N9d";
+    // How long to wait for the airdrop to confirm
+    const CONFIRM_TIMEOUT: Duration = Duration::from_secs(30);
+
+    // Create a runtime for async operations
+    let rt = Runtime::new()
+        .map_err(|e| Error::Term(Box::new(format!("Failed to create tokio runtime: {}", e))))?;
+
+    let result = rt.block_on(async {
+        let client = rpc::client(&rpc_url);
+        let pubkey: SolanaPubkey = pubkey.try_into()?;
+
+        let genesis_hash = client.get_genesis_hash()
+            .map_err(|e| error::Error::Rpc(format!("Failed to get genesis hash: {}", e)))?;
+        if genesis_hash.to_string() == MAINNET_GENESIS_HASH {
+            return Err(error::Error::InvalidParameter("Airdrops are not available on mainnet".to_string()));
+        }
+
+        let signature = client.request_airdrop(&pubkey, lamports)
+            .map_err(|e| error::Error::Rpc(format!("Failed to request airdrop: {}", e)))?;
+
+        let started = std::time::Instant::now();
+        loop {
+            let status = client.get_signature_status(&signature)
+                .map_err(|e| error::Error::Rpc(format!("Failed to get airdrop status: {}", e)))?;
+            match status {
+                Some(Ok(())) => return Ok(signature.to_string()),
+                Some(Err(e)) => return Err(error::Error::Rpc(format!("Airdrop failed: {}", e))),
+                None if started.elapsed() >= CONFIRM_TIMEOUT => {
+                    return Err(error::Error::Rpc(format!("Airdrop {} was not confirmed in time", signature)));
+                }
+                None => tokio::time::sleep(Duration::from_millis(500)).await,
+            }
+        }
+    });
+
+    match result {
+        Ok(signature) => Ok((atom::ok(), signature).encode(env)),
+        Err(err) => Ok((atom::error(), err).encode(env)),
+    }
+}
+
+#[rustler::nif(schedule = "DirtyIo")]
+fn min_balance_for_rent_exemption<'a>(env: Env<'a>, rpc_url: String, space: u64) -> NifResult<Term<'a>> {
+    // Create a runtime for async operations
+    let rt = Runtime::new()
+        .map_err(|e| Error::Term(Box::new(format!("Failed to create tokio runtime: {}", e))))?;
+
+    let result = rt.block_on(async {
+        let client = rpc::client(&rpc_url);
+        client
+            .get_minimum_balance_for_rent_exemption(space as usize)
+            .map_err(|e| error::Error::Rpc(format!("Failed to get minimum balance for rent exemption: {}", e)))
+    });
+
+    match result {
+        Ok(lamports) => Ok((atom::ok(), lamports).encode(env)),
+        Err(err) => Ok((atom::error(), err).encode(env)),
+    }
+}
+
+#[rustler::nif(schedule = "DirtyIo")]
+fn get_asset<'a>(env: Env<'a>, das_url: String, asset_id: ElixirPubkey) -> NifResult<Term<'a>> {
+    // Create a runtime for async operations
+    let rt = Runtime::new()
+        .map_err(|e| Error::Term(Box::new(format!("Failed to create tokio runtime: {}", e))))?;
+
+    let result = rt.block_on(das::get_asset(&das_url, &asset_id.try_into()?));
+
+    match result {
+        Ok(asset) => Ok((atom::ok(), ElixirJson(asset)).encode(env)),
+        Err(err) => Ok((atom::error(), err).encode(env)),
+    }
+}
+
+#[rustler::nif(schedule = "DirtyIo")]
+fn check_asset_owner<'a>(
+    env: Env<'a>,
+    das_url: String,
+    asset_id: ElixirPubkey,
+    leaf_owner: ElixirPubkey,
+) -> NifResult<Term<'a>> {
+    // Create a runtime for async operations
+    let rt = Runtime::new()
+        .map_err(|e| Error::Term(Box::new(format!("Failed to create tokio runtime: {}", e))))?;
+
+    let asset_id: SolanaPubkey = asset_id.try_into()?;
+    let leaf_owner: SolanaPubkey = leaf_owner.try_into()?;
+    let result = rt.block_on(async {
+        let asset = das::get_asset(&das_url, &asset_id).await?;
+        let owner = das::asset_owner(&asset)?;
+        if owner != leaf_owner {
+            return Err(error::Error::InvalidParameter(format!(
+                "asset {} is owned by {}, not {}",
+                asset_id, owner, leaf_owner
+            )));
+        }
+        Ok(())
+    });
+
+    match result {
+        Ok(()) => Ok(atom::ok().encode(env)),
+        Err(err) => Ok((atom::error(), err).encode(env)),
+    }
+}
+
+#[rustler::nif(schedule = "DirtyIo")]
+fn get_asset_proof<'a>(env: Env<'a>, das_url: String, asset_id: ElixirPubkey) -> NifResult<Term<'a>> {
+    // Create a runtime for async operations
+    let rt = Runtime::new()
+        .map_err(|e| Error::Term(Box::new(format!("Failed to create tokio runtime: {}", e))))?;
+
+    let result = rt
+        .block_on(das::get_asset_proof(&das_url, &asset_id.try_into()?))
+        .and_then(ElixirAssetProof::try_from);
+
+    match result {
+        Ok(proof) => Ok((atom::ok(), proof).encode(env)),
+        Err(err) => Ok((atom::error(), err).encode(env)),
+    }
+}
+
+#[rustler::nif(schedule = "DirtyIo")]
+fn get_asset_by_index<'a>(
+    env: Env<'a>,
+    das_url: String,
+    tree: ElixirPubkey,
+    leaf_index: u64,
+) -> NifResult<Term<'a>> {
+    // Create a runtime for async operations
+    let rt = Runtime::new()
+        .map_err(|e| Error::Term(Box::new(format!("Failed to create tokio runtime: {}", e))))?;
+
+    let result = rt.block_on(async {
+        // The leaf nonce equals its index for assets minted by bubblegum
+        let asset_id = utils::get_asset_id(tree.try_into()?, leaf_index)?;
+        das::get_asset(&das_url, &asset_id).await
+    });
+
+    match result {
+        Ok(asset) => Ok((atom::ok(), ElixirJson(asset)).encode(env)),
+        Err(err) => Ok((atom::error(), err).encode(env)),
+    }
+}
+
+rustler::init!(
     "Elixir.MplBubblegum.Native",
     [
         create_tree_config,
+        create_tree_config_instruction_only,
+        check_tree_funding,
         mint_v1,
+        build_batch_mint,
+        mint_to_collection_v1,
         transfer,
+        transfer_with_signers,
+        build_transfer_from_proof,
+        delegate_and_transfer,
+        build_transfer_v0,
+        delegate,
+        escrow_delegate,
+        release_delegate,
+        burn,
+        verify_collection,
+        unverify_collection,
+        set_and_verify_collection,
+        update_metadata,
+        redeem,
+        cancel_redeem,
+        decompress_v1,
+        set_decompressible_state,
+        default_metadata,
+        metadata_to_borsh,
+        metadata_from_borsh,
         hash_metadata,
+        hash_metadata_batch,
         hash_creators,
+        compute_leaf_hash,
+        compute_leaf_node,
+        unsigned_with_digest,
+        decode_transaction,
+        decode_bubblegum_instruction,
+        transaction_to_base64,
+        transaction_from_base64,
         get_asset_id,
+        get_asset_id_batch,
+        bubblegum_program_id,
+        account_compression_program_id,
+        noop_program_id,
+        merkle_tree_rent,
+        tree_capacity,
+        derive_tree_config_pda,
+        derive_asset_pda,
+        derive_bubblegum_signer_pda,
+        parse_tree_config,
+        parse_merkle_tree_header,
         sign_and_submit_transaction,
+        invalidate_blockhash_cache,
+        send_raw_transaction,
+        refresh_and_submit,
+        submit_transactions_batch,
+        sign_transaction,
+        partial_sign_transaction,
+        verify_transaction_signatures,
+        prepend_priority_fee,
         get_transaction_status,
+        get_transaction,
         get_account_info,
-        derive_pubkey_from_secret
+        batch_get_account_info,
+        get_program_accounts,
+        get_signature_statuses,
+        is_blockhash_valid,
+        get_nonce_blockhash,
+        get_tree_num_minted,
+        remaining_capacity,
+        get_token_account_balance,
+        min_balance_for_rent_exemption,
+        estimate_fee,
+        estimate_compute_units,
+        get_recent_prioritization_fees,
+        get_slot,
+        get_block_height,
+        get_health,
+        get_first_available_block,
+        request_airdrop,
+        get_asset,
+        check_asset_owner,
+        get_asset_proof,
+        get_asset_by_index,
+        derive_pubkey_from_secret,
+        keypair_from_json,
+        keypair_from_base58,
+        sign_message,
+        verify_signature,
+        pubkey_is_on_curve,
+        benchmark_hashing
     ]
 );
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_client::rpc_request::RpcResponseErrorData;
+
+    fn send_error_kind(kind: ClientErrorKind) -> Option<&'static str> {
+        classify_send_error(&kind.into()).map(|kind| match kind(error::Error::Rpc(String::new())) {
+            SubmitError::Retryable(_) => "retryable",
+            SubmitError::Final(_) => "final",
+        })
+    }
+
+    #[test]
+    fn only_sends_that_cannot_have_landed_are_retried() {
+        let stale = ClientErrorKind::TransactionError(TransactionError::BlockhashNotFound);
+        let underfunded = ClientErrorKind::TransactionError(TransactionError::InsufficientFundsForFee);
+        let refused = ClientErrorKind::RpcError(RpcError::RpcResponseError {
+            code: -32005,
+            message: "Node is unhealthy".to_string(),
+            data: RpcResponseErrorData::Empty,
+        });
+        let timeout = ClientErrorKind::Io(std::io::Error::from(std::io::ErrorKind::TimedOut));
+
+        assert_eq!(send_error_kind(stale), Some("retryable"));
+        assert_eq!(send_error_kind(underfunded), Some("final"));
+        assert_eq!(send_error_kind(refused), Some("final"));
+        assert_eq!(send_error_kind(timeout), None);
+    }
+}
@@ -0,0 +1,66 @@
+use solana_client::{nonblocking::rpc_client::RpcClient as NonblockingRpcClient, rpc_client::RpcClient};
+use solana_sdk::commitment_config::CommitmentConfig;
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock, Mutex, PoisonError};
+use tokio::runtime::Runtime;
+
+/// A client's commitment is fixed at construction, hence part of the key.
+type ClientKey = (String, CommitmentConfig);
+
+/// Clients shared across NIF calls so their HTTP connections are kept alive.
+static CLIENTS: LazyLock<Mutex<HashMap<ClientKey, Arc<RpcClient>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Returns the shared client for the URL with the default (finalized) commitment.
+pub fn client(url: &str) -> Arc<RpcClient> {
+    client_with_commitment(url, CommitmentConfig::default())
+}
+
+/// Returns the shared client for the URL and commitment, creating it on first use.
+pub fn client_with_commitment(url: &str, commitment: CommitmentConfig) -> Arc<RpcClient> {
+    // Clients hold no state a panic could corrupt, so the poison can be ignored
+    let mut clients = CLIENTS.lock().unwrap_or_else(PoisonError::into_inner);
+    clients
+        .entry((url.to_string(), commitment))
+        .or_insert_with(|| Arc::new(RpcClient::new_with_commitment(url.to_string(), commitment)))
+        .clone()
+}
+
+/// Nonblocking clients shared across NIF calls, for concurrent submissions.
+static NONBLOCKING_CLIENTS: LazyLock<Mutex<HashMap<String, Arc<NonblockingRpcClient>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// A nonblocking client's connections are driven by the runtime that opened
+/// them, so pooled clients are only used from this long-lived one.
+static RUNTIME: LazyLock<std::io::Result<Runtime>> = LazyLock::new(Runtime::new);
+
+/// Returns the runtime to drive the shared nonblocking clients on.
+pub fn runtime() -> Result<&'static Runtime, &'static std::io::Error> {
+    RUNTIME.as_ref()
+}
+
+/// Returns the shared nonblocking client for the URL with the default
+/// (finalized) commitment. Only use it on [`runtime`].
+pub fn nonblocking_client(url: &str) -> Arc<NonblockingRpcClient> {
+    let mut clients = NONBLOCKING_CLIENTS.lock().unwrap_or_else(PoisonError::into_inner);
+    clients
+        .entry(url.to_string())
+        .or_insert_with(|| Arc::new(NonblockingRpcClient::new(url.to_string())))
+        .clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clients_are_shared_per_url_and_commitment() {
+        let url = "http://127.0.0.1:8899";
+        let confirmed = client_with_commitment(url, CommitmentConfig::confirmed());
+
+        assert!(Arc::ptr_eq(&confirmed, &client_with_commitment(url, CommitmentConfig::confirmed())));
+        assert!(!Arc::ptr_eq(&confirmed, &client(url)));
+        assert_eq!(client(url).commitment(), CommitmentConfig::finalized());
+        assert!(Arc::ptr_eq(&nonblocking_client(url), &nonblocking_client(url)));
+    }
+}
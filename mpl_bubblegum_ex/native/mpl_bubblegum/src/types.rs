@@ -1,7 +1,17 @@
-use rustler::{NifStruct, Error};
-use solana_sdk::pubkey::Pubkey;
-use mpl_bubblegum::types::{MetadataArgs, Creator, TokenProgramVersion, TokenStandard, Collection, Uses};
+use rustler::{Atom, Binary, Decoder, Encoder, Env, NifResult, NifStruct, Error, Term};
+use rustler::types::atom;
+use serde_json::Value;
+use solana_client::rpc_filter::{Memcmp, RpcFilterType};
+use solana_sdk::{account::Account, instruction::{AccountMeta, Instruction}, pubkey::Pubkey, transaction::Transaction};
+use base64::{prelude::BASE64_STANDARD, Engine};
+use mpl_bubblegum::accounts::TreeConfig;
+use mpl_bubblegum::types::{DecompressibleState, MetadataArgs, Creator, TokenProgramVersion, TokenStandard, Collection, Uses, UpdateArgs};
+use solana_transaction_status::{
+    EncodedConfirmedTransactionWithStatusMeta, TransactionConfirmationStatus, TransactionStatus,
+};
 use std::convert::{TryFrom, TryInto};
+use crate::utils::MerkleTreeHeader;
+use crate::instructions::{AssetProof, DurableNonce, MintRequest, ProgramIds, TransactionOptions};
 
 #[derive(NifStruct, Debug, Clone)]
 #[module = "MplBubblegum.Types.Pubkey"]
@@ -9,11 +19,14 @@ pub struct ElixirPubkey {
     pub bytes: Vec<u8>,
 }
 
-impl From<ElixirPubkey> for Pubkey {
-    fn from(pubkey: ElixirPubkey) -> Self {
-        let mut bytes = [0u8; 32];
-        bytes.copy_from_slice(&pubkey.bytes);
-        Pubkey::new_from_array(bytes)
+impl TryFrom<ElixirPubkey> for Pubkey {
+    type Error = crate::error::Error;
+
+    fn try_from(pubkey: ElixirPubkey) -> Result<Self, Self::Error> {
+        let len = pubkey.bytes.len();
+        Pubkey::try_from(pubkey.bytes).map_err(|_| {
+            crate::error::Error::InvalidParameter(format!("pubkey must be 32 bytes, got {}", len))
+        })
     }
 }
 
@@ -25,17 +38,70 @@ impl From<Pubkey> for ElixirPubkey {
     }
 }
 
+/// Converts `{pubkey, is_signer, is_writable}` tuples into account metas,
+/// rejecting any pubkey that is not 32 bytes.
+pub fn account_metas(
+    accounts: Vec<(ElixirPubkey, bool, bool)>,
+) -> Result<Vec<AccountMeta>, crate::error::Error> {
+    accounts
+        .into_iter()
+        .map(|(pubkey, is_signer, is_writable)| {
+            if pubkey.bytes.len() != 32 {
+                return Err(crate::error::Error::InvalidParameter(format!(
+                    "account pubkey must be 32 bytes, got {}",
+                    pubkey.bytes.len()
+                )));
+            }
+            let pubkey = Pubkey::try_from(pubkey)?;
+            Ok(if is_writable {
+                AccountMeta::new(pubkey, is_signer)
+            } else {
+                AccountMeta::new_readonly(pubkey, is_signer)
+            })
+        })
+        .collect()
+}
+
+/// Deserializes bincode-serialized instructions, e.g. ones appended to a mint.
+pub fn instructions(binaries: &[Binary]) -> Result<Vec<Instruction>, crate::error::Error> {
+    binaries
+        .iter()
+        .enumerate()
+        .map(|(index, binary)| {
+            bincode::deserialize(binary.as_slice()).map_err(|e| {
+                crate::error::Error::Deserialization(format!("Failed to deserialize instruction {}: {}", index, e))
+            })
+        })
+        .collect()
+}
+
+/// Converts proof nodes into 32-byte arrays, rejecting nodes of any other length.
+pub fn proof_nodes(proof: Vec<Vec<u8>>) -> Result<Vec<[u8; 32]>, crate::error::Error> {
+    proof
+        .into_iter()
+        .map(|node| {
+            let len = node.len();
+            node.try_into().map_err(|_| {
+                crate::error::Error::InvalidParameter(format!("proof node must be 32 bytes, got {}", len))
+            })
+        })
+        .collect()
+}
+
 #[derive(NifStruct, Debug, Clone)]
 #[module = "MplBubblegum.Types.Hash"]
 pub struct ElixirHash {
     pub bytes: Vec<u8>,
 }
 
-impl From<ElixirHash> for [u8; 32] {
-    fn from(hash: ElixirHash) -> Self {
-        let mut bytes = [0u8; 32];
-        bytes.copy_from_slice(&hash.bytes);
-        bytes
+impl TryFrom<ElixirHash> for [u8; 32] {
+    type Error = crate::error::Error;
+
+    fn try_from(hash: ElixirHash) -> Result<Self, Self::Error> {
+        let len = hash.bytes.len();
+        hash.bytes.try_into().map_err(|_| {
+            crate::error::Error::InvalidParameter(format!("hash must be 32 bytes, got {}", len))
+        })
     }
 }
 
@@ -60,7 +126,7 @@ impl TryFrom<ElixirCreator> for Creator {
 
     fn try_from(creator: ElixirCreator) -> Result<Self, Self::Error> {
         Ok(Creator {
-            address: creator.address.into(),
+            address: creator.address.try_into()?,
             verified: creator.verified,
             share: creator.share,
         })
@@ -90,11 +156,20 @@ impl TryFrom<ElixirCollection> for Collection {
     fn try_from(collection: ElixirCollection) -> Result<Self, Self::Error> {
         Ok(Collection {
             verified: collection.verified,
-            key: collection.key.into(),
+            key: collection.key.try_into()?,
         })
     }
 }
 
+impl From<Collection> for ElixirCollection {
+    fn from(collection: Collection) -> Self {
+        ElixirCollection {
+            verified: collection.verified,
+            key: ElixirPubkey::from(collection.key),
+        }
+    }
+}
+
 #[derive(NifStruct, Debug, Clone)]
 #[module = "MplBubblegum.Types.Uses"]
 pub struct ElixirUses {
@@ -122,6 +197,22 @@ impl TryFrom<ElixirUses> for Uses {
     }
 }
 
+impl From<Uses> for ElixirUses {
+    fn from(uses: Uses) -> Self {
+        ElixirUses {
+            use_method: uses.use_method as u8,
+            remaining: uses.remaining,
+            total: uses.total,
+        }
+    }
+}
+
+pub const MAX_NAME_LENGTH: usize = 32;
+pub const MAX_SYMBOL_LENGTH: usize = 10;
+pub const MAX_URI_LENGTH: usize = 200;
+pub const MAX_SELLER_FEE_BASIS_POINTS: u16 = 10_000;
+pub const MAX_CREATOR_LIMIT: usize = 5;
+
 #[derive(NifStruct, Debug, Clone)]
 #[module = "MplBubblegum.Types.Metadata"]
 pub struct ElixirMetadata {
@@ -139,10 +230,55 @@ pub struct ElixirMetadata {
     pub creators: Vec<ElixirCreator>,
 }
 
+impl ElixirMetadata {
+    /// Metadata with the given name, symbol and URI, no royalties or creators,
+    /// mutable and owned by the original token program.
+    pub fn new(name: String, symbol: String, uri: String) -> Self {
+        ElixirMetadata {
+            name,
+            symbol,
+            uri,
+            seller_fee_basis_points: 0,
+            primary_sale_happened: false,
+            is_mutable: true,
+            edition_nonce: None,
+            token_standard: None,
+            collection: None,
+            uses: None,
+            token_program_version: 0,
+            creators: vec![],
+        }
+    }
+}
+
 impl TryFrom<ElixirMetadata> for MetadataArgs {
     type Error = Error;
 
     fn try_from(metadata: ElixirMetadata) -> Result<Self, Self::Error> {
+        // Limits enforced by Token Metadata
+        for (field, value, max) in [
+            ("name", &metadata.name, MAX_NAME_LENGTH),
+            ("symbol", &metadata.symbol, MAX_SYMBOL_LENGTH),
+            ("uri", &metadata.uri, MAX_URI_LENGTH),
+        ] {
+            if value.len() > max {
+                return Err(crate::error::Error::InvalidParameter(format!(
+                    "{} must be at most {} bytes, got {}",
+                    field,
+                    max,
+                    value.len()
+                ))
+                .into());
+            }
+        }
+        if metadata.seller_fee_basis_points > MAX_SELLER_FEE_BASIS_POINTS {
+            return Err(crate::error::Error::InvalidParameter(format!(
+                "seller_fee_basis_points must be at most {}, got {}",
+                MAX_SELLER_FEE_BASIS_POINTS, metadata.seller_fee_basis_points
+            ))
+            .into());
+        }
+
         let token_program_version = match metadata.token_program_version {
             0 => TokenProgramVersion::Original,
             1 => TokenProgramVersion::Token2022,
@@ -173,11 +309,20 @@ impl TryFrom<ElixirMetadata> for MetadataArgs {
             None
         };
 
+        if metadata.creators.len() > MAX_CREATOR_LIMIT {
+            return Err(crate::error::Error::InvalidParameter(format!(
+                "creators must have at most {} entries, got {}",
+                MAX_CREATOR_LIMIT,
+                metadata.creators.len()
+            ))
+            .into());
+        }
         let creators = metadata
             .creators
             .into_iter()
             .map(|c| c.try_into())
             .collect::<Result<Vec<_>, _>>()?;
+        crate::utils::validate_creator_shares(&creators)?;
 
         Ok(MetadataArgs {
             name: metadata.name,
@@ -195,3 +340,473 @@ impl TryFrom<ElixirMetadata> for MetadataArgs {
         })
     }
 }
+
+impl From<MetadataArgs> for ElixirMetadata {
+    fn from(metadata: MetadataArgs) -> Self {
+        ElixirMetadata {
+            name: metadata.name,
+            symbol: metadata.symbol,
+            uri: metadata.uri,
+            seller_fee_basis_points: metadata.seller_fee_basis_points,
+            primary_sale_happened: metadata.primary_sale_happened,
+            is_mutable: metadata.is_mutable,
+            edition_nonce: metadata.edition_nonce,
+            token_standard: metadata.token_standard.map(|ts| ts as u8),
+            collection: metadata.collection.map(Into::into),
+            uses: metadata.uses.map(Into::into),
+            token_program_version: metadata.token_program_version as u8,
+            creators: metadata.creators.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+#[derive(NifStruct, Debug, Clone)]
+#[module = "MplBubblegum.Types.UpdateArgs"]
+pub struct ElixirUpdateArgs {
+    pub name: Option<String>,
+    pub symbol: Option<String>,
+    pub uri: Option<String>,
+    pub creators: Option<Vec<ElixirCreator>>,
+    pub seller_fee_basis_points: Option<u16>,
+    pub primary_sale_happened: Option<bool>,
+    pub is_mutable: Option<bool>,
+}
+
+impl TryFrom<ElixirUpdateArgs> for UpdateArgs {
+    type Error = Error;
+
+    fn try_from(args: ElixirUpdateArgs) -> Result<Self, Self::Error> {
+        let creators = if let Some(creators) = args.creators {
+            Some(
+                creators
+                    .into_iter()
+                    .map(|c| c.try_into())
+                    .collect::<Result<Vec<_>, _>>()?,
+            )
+        } else {
+            None
+        };
+
+        Ok(UpdateArgs {
+            name: args.name,
+            symbol: args.symbol,
+            uri: args.uri,
+            creators,
+            seller_fee_basis_points: args.seller_fee_basis_points,
+            primary_sale_happened: args.primary_sale_happened,
+            is_mutable: args.is_mutable,
+        })
+    }
+}
+
+#[derive(NifStruct, Debug, Clone)]
+#[module = "MplBubblegum.Types.MintRequest"]
+pub struct ElixirMintRequest {
+    pub tree_config: ElixirPubkey,
+    pub leaf_owner: ElixirPubkey,
+    pub leaf_delegate: ElixirPubkey,
+    pub merkle_tree: ElixirPubkey,
+    pub tree_creator_or_delegate: ElixirPubkey,
+    pub metadata: ElixirMetadata,
+}
+
+impl TryFrom<ElixirMintRequest> for MintRequest {
+    type Error = Error;
+
+    fn try_from(mint: ElixirMintRequest) -> Result<Self, Self::Error> {
+        Ok(MintRequest {
+            tree_config: mint.tree_config.try_into()?,
+            leaf_owner: mint.leaf_owner.try_into()?,
+            leaf_delegate: mint.leaf_delegate.try_into()?,
+            merkle_tree: mint.merkle_tree.try_into()?,
+            tree_creator_or_delegate: mint.tree_creator_or_delegate.try_into()?,
+            metadata: mint.metadata.try_into()?,
+        })
+    }
+}
+
+#[derive(NifStruct, Debug, Clone)]
+#[module = "MplBubblegum.Types.TransactionOptions"]
+pub struct ElixirTransactionOptions {
+    pub compute_unit_limit: Option<u32>,
+    pub compute_unit_price: Option<u64>,
+    pub signers: Option<Vec<ElixirPubkey>>,
+    pub bubblegum_program_id: Option<ElixirPubkey>,
+    pub compression_program_id: Option<ElixirPubkey>,
+    pub noop_program_id: Option<ElixirPubkey>,
+    pub memo: Option<String>,
+    pub nonce_account: Option<ElixirPubkey>,
+    pub nonce_authority: Option<ElixirPubkey>,
+    pub placeholder_fee_payer: Option<bool>,
+}
+
+impl TryFrom<ElixirTransactionOptions> for TransactionOptions {
+    type Error = crate::error::Error;
+
+    fn try_from(options: ElixirTransactionOptions) -> Result<Self, Self::Error> {
+        let defaults = ProgramIds::default();
+        let program_id = |program_id: Option<ElixirPubkey>, default| program_id.map_or(Ok(default), Pubkey::try_from);
+        let program_ids = ProgramIds {
+            bubblegum: program_id(options.bubblegum_program_id, defaults.bubblegum)?,
+            compression: program_id(options.compression_program_id, defaults.compression)?,
+            noop: program_id(options.noop_program_id, defaults.noop)?,
+        };
+        let durable_nonce = match options.nonce_account {
+            Some(account) => Some(DurableNonce {
+                account: account.try_into()?,
+                authority: options.nonce_authority.map(Pubkey::try_from).transpose()?,
+            }),
+            None => None,
+        };
+
+        Ok(TransactionOptions {
+            compute_unit_limit: options.compute_unit_limit,
+            compute_unit_price: options.compute_unit_price,
+            signers: options
+                .signers
+                .unwrap_or_default()
+                .into_iter()
+                .map(Pubkey::try_from)
+                .collect::<Result<_, _>>()?,
+            program_ids,
+            memo: options.memo,
+            durable_nonce,
+            placeholder_fee_payer: options.placeholder_fee_payer.unwrap_or(false),
+        })
+    }
+}
+
+/// Converts the optional transaction options of a NIF, falling back to the
+/// defaults when none are given.
+pub fn transaction_options(options: Option<ElixirTransactionOptions>) -> Result<TransactionOptions, crate::error::Error> {
+    Ok(options.map(TransactionOptions::try_from).transpose()?.unwrap_or_default())
+}
+
+#[derive(NifStruct, Debug, Clone)]
+#[module = "MplBubblegum.Types.TreeConfig"]
+pub struct ElixirTreeConfig {
+    pub tree_creator: ElixirPubkey,
+    pub tree_delegate: ElixirPubkey,
+    pub total_mint_capacity: u64,
+    pub num_minted: u64,
+    pub is_public: bool,
+    pub is_decompressible: bool,
+}
+
+impl From<TreeConfig> for ElixirTreeConfig {
+    fn from(tree_config: TreeConfig) -> Self {
+        ElixirTreeConfig {
+            tree_creator: tree_config.tree_creator.into(),
+            tree_delegate: tree_config.tree_delegate.into(),
+            total_mint_capacity: tree_config.total_mint_capacity,
+            num_minted: tree_config.num_minted,
+            is_public: tree_config.is_public,
+            is_decompressible: tree_config.is_decompressible == DecompressibleState::Enabled,
+        }
+    }
+}
+
+#[derive(NifStruct, Debug, Clone)]
+#[module = "MplBubblegum.Types.MerkleTreeHeader"]
+pub struct ElixirMerkleTreeHeader {
+    pub max_depth: u32,
+    pub max_buffer_size: u32,
+    pub authority: ElixirPubkey,
+    pub creation_slot: u64,
+}
+
+impl From<MerkleTreeHeader> for ElixirMerkleTreeHeader {
+    fn from(header: MerkleTreeHeader) -> Self {
+        ElixirMerkleTreeHeader {
+            max_depth: header.max_depth,
+            max_buffer_size: header.max_buffer_size,
+            authority: header.authority.into(),
+            creation_slot: header.creation_slot,
+        }
+    }
+}
+
+#[derive(NifStruct, Debug, Clone)]
+#[module = "MplBubblegum.Types.TokenAmount"]
+pub struct ElixirTokenAmount {
+    /// Raw amount in base units.
+    pub amount: u64,
+    pub decimals: u8,
+    /// Amount divided by `10^decimals`, formatted without rounding.
+    pub ui_amount_string: String,
+}
+
+#[derive(NifStruct, Debug, Clone)]
+#[module = "MplBubblegum.Types.SignatureStatus"]
+pub struct ElixirSignatureStatus {
+    pub slot: u64,
+    /// Confirmations so far, or `None` once the slot is rooted.
+    pub confirmations: Option<u64>,
+    /// `:processed`, `:confirmed` or `:finalized`.
+    pub confirmation_status: Atom,
+    pub err: Option<String>,
+}
+
+mod confirmation_atoms {
+    rustler::atoms! {
+        processed,
+        confirmed,
+        finalized,
+    }
+}
+
+impl From<TransactionStatus> for ElixirSignatureStatus {
+    fn from(status: TransactionStatus) -> Self {
+        let confirmation_status = match status.confirmation_status() {
+            TransactionConfirmationStatus::Processed => confirmation_atoms::processed(),
+            TransactionConfirmationStatus::Confirmed => confirmation_atoms::confirmed(),
+            TransactionConfirmationStatus::Finalized => confirmation_atoms::finalized(),
+        };
+        ElixirSignatureStatus {
+            slot: status.slot,
+            confirmations: status.confirmations.map(|c| c as u64),
+            confirmation_status,
+            err: status.err.map(|e| e.to_string()),
+        }
+    }
+}
+
+#[derive(NifStruct, Debug, Clone)]
+#[module = "MplBubblegum.Types.AccountInfo"]
+pub struct ElixirAccountInfo {
+    pub lamports: u64,
+    pub owner: ElixirPubkey,
+    pub executable: bool,
+    pub rent_epoch: u64,
+    pub data: Vec<u8>,
+}
+
+impl From<Account> for ElixirAccountInfo {
+    fn from(account: Account) -> Self {
+        ElixirAccountInfo {
+            lamports: account.lamports,
+            owner: account.owner.into(),
+            executable: account.executable,
+            rent_epoch: account.rent_epoch,
+            data: account.data,
+        }
+    }
+}
+
+#[derive(NifStruct, Debug, Clone)]
+#[module = "MplBubblegum.Types.DecodedInstruction"]
+pub struct ElixirDecodedInstruction {
+    pub program_id: String,
+    pub accounts: Vec<String>,
+    pub data: String,
+}
+
+#[derive(NifStruct, Debug, Clone)]
+#[module = "MplBubblegum.Types.DecodedTransaction"]
+pub struct ElixirDecodedTransaction {
+    pub fee_payer: Option<String>,
+    pub recent_blockhash: String,
+    pub num_signatures: u64,
+    pub instructions: Vec<ElixirDecodedInstruction>,
+}
+
+impl From<&Transaction> for ElixirDecodedTransaction {
+    fn from(transaction: &Transaction) -> Self {
+        let message = &transaction.message;
+        let key = |index: u8| message.account_keys[index as usize].to_string();
+        ElixirDecodedTransaction {
+            fee_payer: message.account_keys.first().map(ToString::to_string),
+            recent_blockhash: message.recent_blockhash.to_string(),
+            num_signatures: transaction.signatures.len() as u64,
+            instructions: message
+                .instructions
+                .iter()
+                .map(|ix| ElixirDecodedInstruction {
+                    program_id: key(ix.program_id_index),
+                    accounts: ix.accounts.iter().map(|&index| key(index)).collect(),
+                    data: BASE64_STANDARD.encode(&ix.data),
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(NifStruct, Debug, Clone)]
+#[module = "MplBubblegum.Types.TransactionDetails"]
+pub struct ElixirTransactionDetails {
+    pub slot: u64,
+    pub err: Option<String>,
+    pub log_messages: Vec<String>,
+    pub compute_units_consumed: Option<u64>,
+    pub fee: Option<u64>,
+}
+
+impl From<EncodedConfirmedTransactionWithStatusMeta> for ElixirTransactionDetails {
+    fn from(transaction: EncodedConfirmedTransactionWithStatusMeta) -> Self {
+        let meta = transaction.transaction.meta;
+        ElixirTransactionDetails {
+            slot: transaction.slot,
+            err: meta.as_ref().and_then(|meta| meta.err.as_ref()).map(ToString::to_string),
+            log_messages: meta
+                .as_ref()
+                .and_then(|meta| Option::from(meta.log_messages.clone()))
+                .unwrap_or_default(),
+            compute_units_consumed: meta
+                .as_ref()
+                .and_then(|meta| Option::from(meta.compute_units_consumed.clone())),
+            fee: meta.as_ref().map(|meta| meta.fee),
+        }
+    }
+}
+
+/// The merkle proof of a compressed asset as returned by the DAS
+/// `getAssetProof` method, with every hash and node as a base58 string.
+#[derive(NifStruct, Debug, Clone)]
+#[module = "MplBubblegum.Types.AssetProof"]
+pub struct ElixirAssetProof {
+    pub root: String,
+    pub proof: Vec<String>,
+    pub node_index: u64,
+    pub leaf: String,
+    pub tree_id: String,
+}
+
+impl TryFrom<Value> for ElixirAssetProof {
+    type Error = crate::error::Error;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        let missing = |field: &str| {
+            crate::error::Error::Das(format!("getAssetProof response has no valid {}", field))
+        };
+        let base58 = |field: &str| {
+            value
+                .get(field)
+                .and_then(Value::as_str)
+                .filter(|s| s.parse::<Pubkey>().is_ok())
+                .map(str::to_string)
+                .ok_or_else(|| missing(field))
+        };
+
+        let proof = value
+            .get("proof")
+            .and_then(Value::as_array)
+            .ok_or_else(|| missing("proof"))?
+            .iter()
+            .map(|node| {
+                node.as_str()
+                    .filter(|s| s.parse::<Pubkey>().is_ok())
+                    .map(str::to_string)
+                    .ok_or_else(|| missing("proof node"))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(ElixirAssetProof {
+            root: base58("root")?,
+            proof,
+            node_index: value
+                .get("node_index")
+                .and_then(Value::as_u64)
+                .ok_or_else(|| missing("node_index"))?,
+            leaf: base58("leaf")?,
+            tree_id: base58("tree_id")?,
+        })
+    }
+}
+
+impl TryFrom<ElixirAssetProof> for AssetProof {
+    type Error = crate::error::Error;
+
+    fn try_from(asset_proof: ElixirAssetProof) -> Result<Self, Self::Error> {
+        let decode = |field: &str, value: &str| {
+            value.parse::<Pubkey>().map_err(|e| {
+                crate::error::Error::InvalidParameter(format!("Invalid {} in asset proof: {}", field, e))
+            })
+        };
+
+        Ok(AssetProof {
+            tree: decode("tree_id", &asset_proof.tree_id)?,
+            root: decode("root", &asset_proof.root)?.to_bytes(),
+            proof: asset_proof
+                .proof
+                .iter()
+                .map(|node| decode("proof node", node).map(|node| node.to_bytes()))
+                .collect::<Result<_, _>>()?,
+            node_index: asset_proof.node_index,
+            leaf: decode("leaf", &asset_proof.leaf)?.to_bytes(),
+        })
+    }
+}
+
+/// Wraps a JSON value so it can be returned to Elixir as native terms:
+/// objects become maps with string keys, arrays become lists and null becomes nil.
+pub struct ElixirJson(pub Value);
+
+impl Encoder for ElixirJson {
+    fn encode<'a>(&self, env: Env<'a>) -> Term<'a> {
+        encode_json(&self.0, env)
+    }
+}
+
+fn encode_json<'a>(value: &Value, env: Env<'a>) -> Term<'a> {
+    match value {
+        Value::Null => atom::nil().encode(env),
+        Value::Bool(b) => b.encode(env),
+        Value::Number(n) => {
+            if let Some(i) = n.as_i64() {
+                i.encode(env)
+            } else if let Some(u) = n.as_u64() {
+                u.encode(env)
+            } else {
+                n.as_f64().unwrap_or_default().encode(env)
+            }
+        }
+        Value::String(s) => s.encode(env),
+        Value::Array(items) => items
+            .iter()
+            .map(|item| encode_json(item, env))
+            .collect::<Vec<Term<'a>>>()
+            .encode(env),
+        Value::Object(entries) => entries.iter().fold(Term::map_new(env), |map, (key, item)| {
+            map.map_put(key.as_str(), encode_json(item, env)).unwrap_or(map)
+        }),
+    }
+}
+
+mod filter_atoms {
+    rustler::atoms! {
+        memcmp,
+        data_size,
+    }
+}
+
+/// A `getProgramAccounts` filter given as `{:memcmp, offset, bytes}` or
+/// `{:data_size, size}`.
+pub struct ElixirRpcFilter(pub RpcFilterType);
+
+impl<'a> Decoder<'a> for ElixirRpcFilter {
+    fn decode(term: Term<'a>) -> NifResult<Self> {
+        let invalid = || {
+            crate::error::Error::InvalidParameter(
+                "filters must be {:memcmp, offset, bytes} or {:data_size, size} tuples".to_string(),
+            )
+        };
+        let elements = rustler::types::tuple::get_tuple(term).map_err(|_| invalid())?;
+
+        match elements.as_slice() {
+            [tag, offset, bytes] if tag.decode::<Atom>().ok() == Some(filter_atoms::memcmp()) => {
+                let offset: usize = offset.decode().map_err(|_| invalid())?;
+                let bytes = bytes
+                    .decode::<Binary>()
+                    .map(|binary| binary.as_slice().to_vec())
+                    .or_else(|_| bytes.decode::<Vec<u8>>())
+                    .map_err(|_| invalid())?;
+                Ok(ElixirRpcFilter(RpcFilterType::Memcmp(Memcmp::new_raw_bytes(offset, bytes))))
+            }
+            [tag, size] if tag.decode::<Atom>().ok() == Some(filter_atoms::data_size()) => {
+                let size: u64 = size.decode().map_err(|_| invalid())?;
+                Ok(ElixirRpcFilter(RpcFilterType::DataSize(size)))
+            }
+            _ => Err(invalid().into()),
+        }
+    }
+}
@@ -1,11 +1,30 @@
-use solana_sdk::pubkey::Pubkey;
+use solana_sdk::{
+    bs58,
+    commitment_config::CommitmentConfig,
+    hash::{hash as sha256, Hash},
+    nonce,
+    pubkey::Pubkey,
+    rent::Rent,
+    signature::Signature,
+    signature::Keypair,
+    signer::Signer,
+    signer::keypair::keypair_from_seed,
+    transaction::{Transaction, VersionedTransaction},
+};
+use base64::{prelude::BASE64_STANDARD, Engine};
 use mpl_bubblegum::{
+    accounts::TreeConfig,
     hash::{hash_metadata as bubblegum_hash_metadata, hash_creators as bubblegum_hash_creators},
     utils::get_asset_id as bubblegum_get_asset_id,
-    types::{MetadataArgs, Creator},
+    types::{MetadataArgs, Creator, LeafSchema},
+    ID as BUBBLEGUM_ID,
 };
+use spl_account_compression::state::CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1;
+use borsh::{BorshDeserialize, BorshSerialize};
 use crate::{error::Error, types::ElixirCreator};
 use std::convert::TryInto;
+#[cfg(feature = "benchmark")]
+use std::time::{Duration, Instant};
 
 /// Computes the hash of NFT metadata.
 pub fn hash_metadata(metadata: MetadataArgs) -> Result<[u8; 32], Error> {
@@ -13,6 +32,31 @@ pub fn hash_metadata(metadata: MetadataArgs) -> Result<[u8; 32], Error> {
         .map_err(|e| Error::Bubblegum(format!("Failed to hash metadata: {}", e)))
 }
 
+/// Serializes metadata into the borsh bytes that `hash_metadata` hashes.
+pub fn metadata_to_borsh(metadata: &MetadataArgs) -> Result<Vec<u8>, Error> {
+    Ok(metadata.try_to_vec()?)
+}
+
+/// Deserializes metadata from its borsh bytes, rejecting trailing bytes.
+pub fn metadata_from_borsh(bytes: &[u8]) -> Result<MetadataArgs, Error> {
+    MetadataArgs::try_from_slice(bytes)
+        .map_err(|e| Error::Deserialization(format!("Failed to deserialize metadata: {}", e)))
+}
+
+/// Checks that creator shares add up to 100, as the bubblegum program requires
+/// for any non-empty creator list.
+pub fn validate_creator_shares(creators: &[Creator]) -> Result<(), Error> {
+    let total: u32 = creators.iter().map(|c| c.share as u32).sum();
+    if creators.is_empty() || total == 100 {
+        Ok(())
+    } else {
+        Err(Error::InvalidParameter(format!(
+            "Creator shares must sum to 100, got {}",
+            total
+        )))
+    }
+}
+
 /// Computes the hash of NFT creators.
 pub fn hash_creators(creators: Vec<ElixirCreator>) -> Result<[u8; 32], Error> {
     let creators: Result<Vec<Creator>, _> = creators
@@ -21,11 +65,676 @@ pub fn hash_creators(creators: Vec<ElixirCreator>) -> Result<[u8; 32], Error> {
         .collect();
 
     let creators = creators.map_err(|e| Error::Conversion(format!("Failed to convert creators: {:?}", e)))?;
+    validate_creator_shares(&creators)?;
 
     Ok(bubblegum_hash_creators(&creators))
 }
 
+/// Parses a commitment level, defaulting to `confirmed` when the string is empty.
+pub fn parse_commitment(commitment: &str) -> Result<CommitmentConfig, Error> {
+    match commitment {
+        "" | "confirmed" => Ok(CommitmentConfig::confirmed()),
+        "processed" => Ok(CommitmentConfig::processed()),
+        "finalized" => Ok(CommitmentConfig::finalized()),
+        other => Err(Error::InvalidParameter(format!(
+            "Invalid commitment: {}; expected processed, confirmed or finalized",
+            other
+        ))),
+    }
+}
+
+/// Reads the stored blockhash of a durable nonce account, which durable nonce
+/// transactions must be signed against instead of a recent blockhash.
+pub fn nonce_blockhash(data: &[u8]) -> Result<Hash, Error> {
+    let versions: nonce::state::Versions = bincode::deserialize(data)
+        .map_err(|e| Error::Conversion(format!("Failed to deserialize nonce account: {}", e)))?;
+    match versions.state() {
+        nonce::State::Initialized(data) => Ok(data.blockhash()),
+        nonce::State::Uninitialized => Err(Error::InvalidParameter(
+            "Nonce account is not initialized".to_string(),
+        )),
+    }
+}
+
+/// Hashes that make up a leaf of a bubblegum tree.
+pub struct LeafHashes {
+    pub data_hash: [u8; 32],
+    pub creator_hash: [u8; 32],
+    pub leaf_hash: [u8; 32],
+}
+
+/// Computes the data hash, creator hash and leaf hash of a leaf the same way
+/// the bubblegum program does. The asset id in the leaf is derived from the
+/// tree and nonce.
+pub fn compute_leaf_hash(
+    metadata: &MetadataArgs,
+    merkle_tree: Pubkey,
+    leaf_owner: Pubkey,
+    leaf_delegate: Pubkey,
+    nonce: u64,
+) -> Result<LeafHashes, Error> {
+    let data_hash = bubblegum_hash_metadata(metadata)
+        .map_err(|e| Error::Bubblegum(format!("Failed to hash metadata: {}", e)))?;
+    let creator_hash = bubblegum_hash_creators(&metadata.creators);
+
+    let leaf = LeafSchema::V1 {
+        id: bubblegum_get_asset_id(&merkle_tree, nonce),
+        owner: leaf_owner,
+        delegate: leaf_delegate,
+        nonce,
+        data_hash,
+        creator_hash,
+    };
+
+    Ok(LeafHashes {
+        data_hash,
+        creator_hash,
+        leaf_hash: leaf.hash(),
+    })
+}
+
+/// Computes the node hash of a `LeafSchema::V1` leaf from its fields, as stored
+/// in the merkle tree. Unlike `compute_leaf_hash` the data and creator hashes
+/// are taken as-is, so a leaf reported by DAS can be checked against its proof.
+pub fn compute_leaf_node(
+    asset_id: Pubkey,
+    owner: Pubkey,
+    delegate: Pubkey,
+    nonce: u64,
+    data_hash: [u8; 32],
+    creator_hash: [u8; 32],
+) -> [u8; 32] {
+    LeafSchema::V1 {
+        id: asset_id,
+        owner,
+        delegate,
+        nonce,
+        data_hash,
+        creator_hash,
+    }
+    .hash()
+}
+
+/// Checks that a leaf index fits in a tree of the given depth, catching
+/// off-by-one mistakes before they surface as proof failures on-chain.
+pub fn check_leaf_index(index: u32, max_depth: u32) -> Result<(), Error> {
+    let capacity = tree_capacity(max_depth)?;
+    if u64::from(index) >= capacity {
+        return Err(Error::InvalidParameter(format!(
+            "leaf index {} does not fit in a tree of depth {} ({} leaves)",
+            index, max_depth, capacity
+        )));
+    }
+    Ok(())
+}
+
+/// Returns the number of leaves a tree of the given depth can hold.
+pub fn tree_capacity(max_depth: u32) -> Result<u64, Error> {
+    if max_depth == 0 || max_depth > 30 {
+        return Err(Error::InvalidParameter(format!("Invalid max_depth: {}", max_depth)));
+    }
+    Ok(1u64 << max_depth)
+}
+
+/// Derives the tree config PDA of a merkle tree and its bump seed, seeded
+/// with the merkle tree address.
+pub fn derive_tree_config_pda(merkle_tree: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[merkle_tree.as_ref()], &BUBBLEGUM_ID)
+}
+
+/// Derives the PDA the bubblegum program signs with when it CPIs into token
+/// metadata for collection operations, seeded with `"collection_cpi"`.
+pub fn derive_bubblegum_signer_pda(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"collection_cpi"], program_id)
+}
+
+/// Estimates the rent-exempt balance of a merkle tree account with the default rent.
+pub fn merkle_tree_rent(max_depth: u32, max_buffer_size: u32, canopy_depth: u32) -> Result<(usize, u64), Error> {
+    if max_depth == 0 || max_depth > 30 || canopy_depth > max_depth {
+        return Err(Error::InvalidParameter(format!(
+            "Invalid tree dimensions: max_depth {}, canopy_depth {}",
+            max_depth, canopy_depth
+        )));
+    }
+
+    let space = crate::instructions::get_merkle_tree_size(max_depth, max_buffer_size, canopy_depth);
+    Ok((space, Rent::default().minimum_balance(space)))
+}
+
 /// Computes the asset ID of an asset given its tree and nonce values.
 pub fn get_asset_id(tree: Pubkey, nonce: u64) -> Result<Pubkey, Error> {
     Ok(bubblegum_get_asset_id(&tree, nonce))
 }
+
+/// Derives the asset ID PDA of a leaf and its bump seed, seeded with
+/// `"asset"`, the tree address and the little-endian nonce.
+pub fn derive_asset_pda(tree: &Pubkey, nonce: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"asset", tree.as_ref(), &nonce.to_le_bytes()], &BUBBLEGUM_ID)
+}
+
+/// Parses a keypair from the Solana CLI JSON format (an array of 64 integers),
+/// as written to disk by `solana-keygen`.
+pub fn keypair_from_json(json: &str) -> Result<Keypair, Error> {
+    let bytes: Vec<u8> = serde_json::from_str(json)
+        .map_err(|e| Error::InvalidParameter(format!("Invalid keypair JSON: {}", e)))?;
+
+    if bytes.len() != 64 {
+        return Err(Error::InvalidParameter(format!(
+            "Keypair JSON must contain exactly 64 bytes, got {}",
+            bytes.len()
+        )));
+    }
+
+    Keypair::from_bytes(&bytes)
+        .map_err(|e| Error::InvalidParameter(format!("Failed to create keypair: {}", e)))
+}
+
+/// Builds a keypair from a base58-encoded 64-byte secret key, the format
+/// wallets such as Phantom export private keys in.
+pub fn keypair_from_base58(secret_base58: &str) -> Result<Keypair, Error> {
+    let bytes = bs58::decode(secret_base58.trim())
+        .into_vec()
+        .map_err(|e| Error::InvalidParameter(format!("Invalid base58 secret key: {}", e)))?;
+
+    if bytes.len() != 64 {
+        return Err(Error::InvalidParameter(format!(
+            "secret key must be 64 bytes, got {}",
+            bytes.len()
+        )));
+    }
+
+    Keypair::from_bytes(&bytes)
+        .map_err(|e| Error::InvalidParameter(format!("Failed to create keypair: {}", e)))
+}
+
+/// Builds a keypair from a raw 64-byte secret key, a 32-byte seed (the first
+/// half of the secret key) or the Solana CLI JSON representation.
+pub fn keypair_from_secret_key(secret_key: &[u8]) -> Result<Keypair, Error> {
+    // Raw keys and seeds may themselves start with `[`, so only other lengths can be JSON
+    match secret_key.len() {
+        64 => Keypair::from_bytes(secret_key)
+            .map_err(|e| Error::InvalidParameter(format!("Failed to create keypair: {}", e))),
+        32 => keypair_from_seed(secret_key)
+            .map_err(|e| Error::InvalidParameter(format!("Failed to create keypair from seed: {}", e))),
+        _ if secret_key.first() == Some(&b'[') => {
+            let json = std::str::from_utf8(secret_key)
+                .map_err(|e| Error::InvalidParameter(format!("Invalid keypair JSON: {}", e)))?;
+            keypair_from_json(json)
+        }
+        len => Err(Error::InvalidParameter(format!(
+            "secret key must be 64 bytes, got {}",
+            len
+        ))),
+    }
+}
+
+/// Builds keypairs from raw or JSON-encoded secret keys.
+pub fn keypairs_from_secret_keys(secret_keys: &[&[u8]]) -> Result<Vec<Keypair>, Error> {
+    secret_keys
+        .iter()
+        .map(|secret_key| keypair_from_secret_key(secret_key))
+        .collect()
+}
+
+/// Signs a serialized transaction with the given keypairs and blockhash
+/// without submitting it, returning the serialized signed transaction.
+pub fn sign_transaction(
+    transaction_bytes: &[u8],
+    secret_keys: &[&[u8]],
+    recent_blockhash: Hash,
+) -> Result<Vec<u8>, Error> {
+    let transaction = resign_transaction(transaction_bytes, secret_keys, recent_blockhash)?;
+
+    bincode::serialize(&transaction)
+        .map_err(|e| Error::Conversion(format!("Failed to serialize transaction: {}", e)))
+}
+
+/// Signs a serialized legacy or v0 transaction against the given blockhash
+/// with every required signer. Signatures made against an older blockhash are
+/// discarded, so a transaction whose blockhash expired can be signed again as-is.
+pub fn resign_transaction(
+    transaction_bytes: &[u8],
+    secret_keys: &[&[u8]],
+    recent_blockhash: Hash,
+) -> Result<VersionedTransaction, Error> {
+    let mut transaction: VersionedTransaction = bincode::deserialize(transaction_bytes)
+        .map_err(|e| Error::Deserialization(format!("Failed to deserialize transaction: {}", e)))?;
+
+    let keypairs = keypairs_from_secret_keys(secret_keys)?;
+    let keypair_refs: Vec<&Keypair> = keypairs.iter().collect();
+    sign_versioned_transaction(&mut transaction, &keypair_refs, recent_blockhash, false)?;
+
+    Ok(transaction)
+}
+
+/// Adds the signatures of the given keypairs to a serialized legacy or v0
+/// transaction, leaving the other signature slots as they are. Every round
+/// must use the same blockhash, as changing it clears the signatures
+/// collected so far.
+pub fn partial_sign_transaction(
+    transaction_bytes: &[u8],
+    secret_keys: &[&[u8]],
+    recent_blockhash: Hash,
+) -> Result<Vec<u8>, Error> {
+    let mut transaction: VersionedTransaction = bincode::deserialize(transaction_bytes)
+        .map_err(|e| Error::Deserialization(format!("Failed to deserialize transaction: {}", e)))?;
+
+    let keypairs = keypairs_from_secret_keys(secret_keys)?;
+    let keypair_refs: Vec<&Keypair> = keypairs.iter().collect();
+    sign_versioned_transaction(&mut transaction, &keypair_refs, recent_blockhash, true)?;
+
+    bincode::serialize(&transaction)
+        .map_err(|e| Error::Conversion(format!("Failed to serialize transaction: {}", e)))
+}
+
+/// Signs a legacy or v0 transaction the way `Transaction::try_partial_sign`
+/// signs a legacy one: a new blockhash clears the existing signatures, and
+/// every keypair must be one of the required signers. Unless `partial` is
+/// set, every required signature must be present afterwards.
+pub fn sign_versioned_transaction(
+    transaction: &mut VersionedTransaction,
+    keypairs: &[&Keypair],
+    recent_blockhash: Hash,
+    partial: bool,
+) -> Result<(), Error> {
+    let num_required_signatures = transaction.message.header().num_required_signatures as usize;
+    if *transaction.message.recent_blockhash() != recent_blockhash {
+        transaction.message.set_recent_blockhash(recent_blockhash);
+        transaction.signatures.clear();
+    }
+    transaction.signatures.resize(num_required_signatures, Signature::default());
+
+    let message_data = transaction.message.serialize();
+    let signers: Vec<Pubkey> = transaction.message.static_account_keys().iter().take(num_required_signatures).copied().collect();
+    for keypair in keypairs {
+        let position = signers.iter().position(|signer| *signer == keypair.pubkey()).ok_or_else(|| {
+            Error::InvalidParameter(format!("Failed to sign transaction: {} is not a signer", keypair.pubkey()))
+        })?;
+        transaction.signatures[position] = keypair.sign_message(&message_data);
+    }
+
+    if !partial && transaction.signatures.iter().any(|signature| *signature == Signature::default()) {
+        return Err(Error::InvalidParameter("Failed to sign transaction: not enough signers".to_string()));
+    }
+    Ok(())
+}
+
+/// Returns the required signers of a serialized transaction whose signature
+/// is missing or does not verify against the message.
+pub fn unsigned_accounts(transaction_bytes: &[u8]) -> Result<Vec<Pubkey>, Error> {
+    let transaction: VersionedTransaction = bincode::deserialize(transaction_bytes)
+        .map_err(|e| Error::Deserialization(format!("Failed to deserialize transaction: {}", e)))?;
+
+    let num_required_signatures = transaction.message.header().num_required_signatures as usize;
+    let results = transaction.verify_with_results();
+    let unsigned = transaction
+        .message
+        .static_account_keys()
+        .iter()
+        .take(num_required_signatures)
+        .enumerate()
+        .filter(|(index, _)| !results.get(*index).copied().unwrap_or(false))
+        .map(|(_, key)| *key)
+        .collect();
+
+    Ok(unsigned)
+}
+
+/// Deserializes a legacy or versioned transaction, checking that every
+/// required signature is present so it can be sent as is.
+pub fn signed_transaction(transaction_bytes: &[u8]) -> Result<VersionedTransaction, Error> {
+    let transaction: VersionedTransaction = bincode::deserialize(transaction_bytes)
+        .map_err(|e| Error::Deserialization(format!("Failed to deserialize transaction: {}", e)))?;
+
+    let num_required_signatures = transaction.message.header().num_required_signatures as usize;
+    let is_signed = transaction.signatures.len() == num_required_signatures
+        && transaction.signatures.iter().all(|signature| *signature != Signature::default());
+    if !is_signed {
+        return Err(Error::InvalidParameter("Transaction is not fully signed".to_string()));
+    }
+    Ok(transaction)
+}
+
+/// Encodes a serialized legacy or versioned transaction as base64, the format
+/// wallet adapters exchange transactions in.
+pub fn transaction_to_base64(transaction_bytes: &[u8]) -> Result<String, Error> {
+    bincode::deserialize::<VersionedTransaction>(transaction_bytes)
+        .map_err(|e| Error::Deserialization(format!("Failed to deserialize transaction: {}", e)))?;
+    Ok(BASE64_STANDARD.encode(transaction_bytes))
+}
+
+/// Decodes a base64 transaction returned by a wallet adapter into its
+/// serialized form, checking that it is a legacy or versioned transaction.
+pub fn transaction_from_base64(encoded: &str) -> Result<Vec<u8>, Error> {
+    let transaction_bytes = BASE64_STANDARD
+        .decode(encoded)
+        .map_err(|e| Error::InvalidParameter(format!("Invalid base64: {}", e)))?;
+    bincode::deserialize::<VersionedTransaction>(&transaction_bytes)
+        .map_err(|e| Error::Deserialization(format!("Failed to deserialize transaction: {}", e)))?;
+    Ok(transaction_bytes)
+}
+
+/// Hashes the given metadata `iterations` times and returns the elapsed time
+/// together with the achieved hashes per second.
+#[cfg(feature = "benchmark")]
+pub fn benchmark_hashing(metadata: &MetadataArgs, iterations: u64) -> Result<(Duration, f64), Error> {
+    if iterations == 0 {
+        return Err(Error::InvalidParameter("iterations must be greater than 0".to_string()));
+    }
+
+    let start = Instant::now();
+    for _ in 0..iterations {
+        bubblegum_hash_metadata(metadata)
+            .map_err(|e| Error::Bubblegum(format!("Failed to hash metadata: {}", e)))?;
+    }
+    let elapsed = start.elapsed();
+
+    let hashes_per_second = iterations as f64 / elapsed.as_secs_f64().max(f64::EPSILON);
+    Ok((elapsed, hashes_per_second))
+}
+
+/// Verifies that a serialized transaction is unsigned and returns it together
+/// with the SHA-256 digest of its serialized message.
+pub fn unsigned_with_digest(transaction_bytes: &[u8]) -> Result<(Vec<u8>, [u8; 32]), Error> {
+    let transaction: Transaction = bincode::deserialize(transaction_bytes)
+        .map_err(|e| Error::Deserialization(format!("Failed to deserialize transaction: {}", e)))?;
+
+    if transaction.signatures.iter().any(|sig| *sig != Signature::default()) {
+        return Err(Error::InvalidParameter("Transaction is already signed".to_string()));
+    }
+
+    let digest = sha256(&transaction.message_data()).to_bytes();
+    let transaction_bytes = bincode::serialize(&transaction)
+        .map_err(|e| Error::Conversion(format!("Failed to serialize transaction: {}", e)))?;
+
+    Ok((transaction_bytes, digest))
+}
+
+/// The header of a concurrent merkle tree account.
+pub struct MerkleTreeHeader {
+    pub max_depth: u32,
+    pub max_buffer_size: u32,
+    pub authority: Pubkey,
+    pub creation_slot: u64,
+}
+
+/// Reads the header at the start of a concurrent merkle tree account. The V1
+/// header is laid out as the account type, the header version, then
+/// max_buffer_size (u32), max_depth (u32), authority and creation_slot (u64).
+pub fn parse_merkle_tree_header(data: &[u8]) -> Result<MerkleTreeHeader, Error> {
+    const ACCOUNT_TYPE_CONCURRENT_MERKLE_TREE: u8 = 1;
+    const HEADER_VERSION_V1: u8 = 0;
+
+    if data.len() < CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1 {
+        return Err(Error::Conversion(format!(
+            "Merkle tree account data must be at least {} bytes, got {}",
+            CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1,
+            data.len()
+        )));
+    }
+    if data[0] != ACCOUNT_TYPE_CONCURRENT_MERKLE_TREE {
+        return Err(Error::Conversion(
+            "Account is not an initialized concurrent merkle tree".to_string(),
+        ));
+    }
+    if data[1] != HEADER_VERSION_V1 {
+        return Err(Error::Conversion(format!(
+            "Unsupported merkle tree header version {}",
+            data[1]
+        )));
+    }
+
+    let u32_at = |offset: usize| u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap());
+    Ok(MerkleTreeHeader {
+        max_buffer_size: u32_at(2),
+        max_depth: u32_at(6),
+        authority: Pubkey::new_from_array(data[10..42].try_into().unwrap()),
+        creation_slot: u64::from_le_bytes(data[42..50].try_into().unwrap()),
+    })
+}
+
+/// Anchor discriminator of the tree config account, the first 8 bytes of
+/// `sha256("account:TreeConfig")`.
+const TREE_CONFIG_DISCRIMINATOR: [u8; 8] = [122, 245, 175, 248, 171, 34, 0, 207];
+
+/// Deserializes the data of a tree config account, including its 8-byte
+/// discriminator. `TreeConfig::from_bytes` accepts any long enough buffer, so
+/// the size and discriminator are checked first.
+pub fn parse_tree_config(data: &[u8]) -> Result<TreeConfig, Error> {
+    if data.len() < TreeConfig::LEN {
+        return Err(Error::Conversion(format!(
+            "tree config account must be at least {} bytes, got {}",
+            TreeConfig::LEN,
+            data.len()
+        )));
+    }
+    if data[..8] != TREE_CONFIG_DISCRIMINATOR {
+        return Err(Error::Conversion("account is not a tree config".to_string()));
+    }
+
+    TreeConfig::from_bytes(data)
+        .map_err(|e| Error::Conversion(format!("Failed to deserialize tree config: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use spl_account_compression::state::ConcurrentMerkleTreeHeader;
+
+    #[test]
+    fn merkle_tree_header_matches_account_compression_layout() {
+        let authority = Pubkey::new_unique();
+        let mut header =
+            ConcurrentMerkleTreeHeader::try_from_slice(&[0; CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1]).unwrap();
+        header.initialize(14, 64, &authority, 42);
+        let mut data = header.try_to_vec().unwrap();
+        data.extend_from_slice(&[0; 128]); // The tree follows the header
+
+        let parsed = parse_merkle_tree_header(&data).unwrap();
+        assert_eq!(parsed.max_depth, 14);
+        assert_eq!(parsed.max_buffer_size, 64);
+        assert_eq!(parsed.authority, authority);
+        assert_eq!(parsed.creation_slot, 42);
+    }
+
+    #[test]
+    fn merkle_tree_header_rejects_uninitialized_accounts() {
+        let data = [0; CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1];
+        assert!(matches!(parse_merkle_tree_header(&data), Err(Error::Conversion(_))));
+    }
+
+    #[test]
+    fn raw_secret_keys_starting_with_a_bracket_are_not_json() {
+        let keypair = keypair_from_seed(&[b'['; 32]).unwrap();
+        let secret_key = keypair.to_bytes();
+        assert_eq!(secret_key[0], b'[');
+        assert_eq!(keypair_from_secret_key(&secret_key).unwrap().to_bytes(), secret_key);
+    }
+
+    #[test]
+    fn seeds_starting_with_a_bracket_are_not_json() {
+        let seed = [b'['; 32];
+        let keypair = keypair_from_secret_key(&seed).unwrap();
+        assert_eq!(keypair.to_bytes(), keypair_from_seed(&seed).unwrap().to_bytes());
+    }
+
+    #[test]
+    fn tree_config_discriminator_is_checked() {
+        assert_eq!(TREE_CONFIG_DISCRIMINATOR, sha256(b"account:TreeConfig").to_bytes()[..8]);
+
+        let mut data = vec![0; TreeConfig::LEN];
+        data[..8].copy_from_slice(&TREE_CONFIG_DISCRIMINATOR);
+        data[72..80].copy_from_slice(&16_384u64.to_le_bytes());
+        assert_eq!(parse_tree_config(&data).unwrap().total_mint_capacity, 16_384);
+
+        // A token account is 165 bytes, long enough to deserialize as a tree config
+        let mut token_account = vec![7; 165];
+        token_account[..8].copy_from_slice(&[1; 8]);
+        assert!(matches!(parse_tree_config(&token_account), Err(Error::Conversion(_))));
+        assert!(matches!(parse_tree_config(&data[..TreeConfig::LEN - 1]), Err(Error::Conversion(_))));
+    }
+
+    #[test]
+    fn partial_signatures_accumulate_across_rounds() {
+        use solana_sdk::{signer::Signer, system_instruction};
+
+        let (payer, recipient) = (Keypair::new(), Keypair::new());
+        let instruction = system_instruction::transfer(&recipient.pubkey(), &payer.pubkey(), 1);
+        let transaction = Transaction::new_with_payer(&[instruction], Some(&payer.pubkey()));
+        let bytes = bincode::serialize(&transaction).unwrap();
+        let blockhash = Hash::new_unique();
+
+        let bytes = partial_sign_transaction(&bytes, &[&payer.to_bytes()], blockhash).unwrap();
+        let transaction: Transaction = bincode::deserialize(&bytes).unwrap();
+        assert!(!transaction.is_signed());
+
+        let bytes = partial_sign_transaction(&bytes, &[&recipient.to_bytes()], blockhash).unwrap();
+        let transaction: Transaction = bincode::deserialize(&bytes).unwrap();
+        assert!(transaction.is_signed());
+        assert!(transaction.verify().is_ok());
+    }
+
+    #[test]
+    fn unsigned_accounts_lists_missing_signers() {
+        use solana_sdk::{signer::Signer, system_instruction};
+
+        let (payer, recipient) = (Keypair::new(), Keypair::new());
+        let instruction = system_instruction::transfer(&recipient.pubkey(), &payer.pubkey(), 1);
+        let transaction = Transaction::new_with_payer(&[instruction], Some(&payer.pubkey()));
+        let bytes = bincode::serialize(&transaction).unwrap();
+        let blockhash = Hash::new_unique();
+
+        let bytes = partial_sign_transaction(&bytes, &[&payer.to_bytes()], blockhash).unwrap();
+        assert_eq!(unsigned_accounts(&bytes).unwrap(), vec![recipient.pubkey()]);
+
+        let bytes = partial_sign_transaction(&bytes, &[&recipient.to_bytes()], blockhash).unwrap();
+        assert!(unsigned_accounts(&bytes).unwrap().is_empty());
+    }
+
+    #[test]
+    fn signed_transaction_accepts_only_complete_signatures() {
+        use solana_sdk::{signer::Signer, system_instruction};
+
+        let (payer, recipient) = (Keypair::new(), Keypair::new());
+        let instruction = system_instruction::transfer(&recipient.pubkey(), &payer.pubkey(), 1);
+        let transaction = Transaction::new_with_payer(&[instruction], Some(&payer.pubkey()));
+        let bytes = bincode::serialize(&transaction).unwrap();
+        let blockhash = Hash::new_unique();
+
+        let bytes = partial_sign_transaction(&bytes, &[&payer.to_bytes()], blockhash).unwrap();
+        assert!(matches!(signed_transaction(&bytes), Err(Error::InvalidParameter(_))));
+
+        let bytes = partial_sign_transaction(&bytes, &[&recipient.to_bytes()], blockhash).unwrap();
+        assert!(signed_transaction(&bytes).is_ok());
+        assert!(matches!(signed_transaction(&[1, 2, 3]), Err(Error::Deserialization(_))));
+    }
+
+    #[test]
+    fn base64_transactions_round_trip() {
+        use solana_sdk::{signer::Signer, system_instruction};
+
+        let payer = Keypair::new();
+        let instruction = system_instruction::transfer(&payer.pubkey(), &Pubkey::new_unique(), 1);
+        let transaction = Transaction::new_signed_with_payer(&[instruction], Some(&payer.pubkey()), &[&payer], Hash::new_unique());
+        let bytes = bincode::serialize(&transaction).unwrap();
+
+        let encoded = transaction_to_base64(&bytes).unwrap();
+        assert_eq!(transaction_from_base64(&encoded).unwrap(), bytes);
+        assert!(matches!(transaction_from_base64("not base64!"), Err(Error::InvalidParameter(_))));
+        assert!(matches!(transaction_from_base64("AAAA"), Err(Error::Deserialization(_))));
+    }
+
+    #[test]
+    fn asset_pda_matches_asset_id() {
+        let tree = Pubkey::new_unique();
+        let (asset_id, bump) = derive_asset_pda(&tree, 42);
+        assert_eq!(asset_id, get_asset_id(tree, 42).unwrap());
+        assert_eq!(
+            Pubkey::create_program_address(&[b"asset", tree.as_ref(), &42u64.to_le_bytes(), &[bump]], &BUBBLEGUM_ID),
+            Ok(asset_id)
+        );
+    }
+
+    #[test]
+    fn expired_transactions_can_be_signed_again() {
+        use solana_sdk::{signer::Signer, system_instruction};
+
+        let payer = Keypair::new();
+        let instruction = system_instruction::transfer(&payer.pubkey(), &Pubkey::new_unique(), 1);
+        let stale = Transaction::new_signed_with_payer(&[instruction], Some(&payer.pubkey()), &[&payer], Hash::new_unique());
+        let bytes = bincode::serialize(&stale).unwrap();
+
+        let fresh_blockhash = Hash::new_unique();
+        let transaction = resign_transaction(&bytes, &[&payer.to_bytes()], fresh_blockhash).unwrap();
+        assert_eq!(*transaction.message.recent_blockhash(), fresh_blockhash);
+        assert_ne!(transaction.signatures, stale.signatures);
+        assert!(transaction.verify_with_results().iter().all(|verified| *verified));
+    }
+
+    #[test]
+    fn leaf_index_must_fit_in_the_tree() {
+        assert!(check_leaf_index(16_383, 14).is_ok());
+        assert!(matches!(check_leaf_index(16_384, 14), Err(Error::InvalidParameter(_))));
+        assert!(matches!(check_leaf_index(0, 31), Err(Error::InvalidParameter(_))));
+    }
+
+    #[test]
+    fn tree_capacity_doubles_with_each_level() {
+        assert_eq!(tree_capacity(14).unwrap(), 16_384);
+        assert_eq!(tree_capacity(30).unwrap(), 1 << 30);
+        assert!(matches!(tree_capacity(0), Err(Error::InvalidParameter(_))));
+    }
+
+    #[test]
+    fn metadata_borsh_round_trips() {
+        let metadata = MetadataArgs {
+            name: "Test NFT".to_string(),
+            symbol: "TNFT".to_string(),
+            uri: "https://example.com/test.json".to_string(),
+            seller_fee_basis_points: 500,
+            primary_sale_happened: false,
+            is_mutable: true,
+            edition_nonce: Some(1),
+            token_standard: Some(mpl_bubblegum::types::TokenStandard::NonFungible),
+            collection: None,
+            uses: None,
+            token_program_version: mpl_bubblegum::types::TokenProgramVersion::Original,
+            creators: vec![Creator { address: Pubkey::new_unique(), verified: false, share: 100 }],
+        };
+
+        let mut bytes = metadata_to_borsh(&metadata).unwrap();
+        assert_eq!(metadata_from_borsh(&bytes).unwrap(), metadata);
+        bytes.push(0);
+        assert!(matches!(metadata_from_borsh(&bytes), Err(Error::Deserialization(_))));
+    }
+
+    #[test]
+    fn leaf_node_matches_leaf_hash() {
+        let metadata = MetadataArgs {
+            name: "Test NFT".to_string(),
+            symbol: "TNFT".to_string(),
+            uri: "https://example.com/test.json".to_string(),
+            seller_fee_basis_points: 500,
+            primary_sale_happened: false,
+            is_mutable: true,
+            edition_nonce: None,
+            token_standard: None,
+            collection: None,
+            uses: None,
+            token_program_version: mpl_bubblegum::types::TokenProgramVersion::Original,
+            creators: vec![],
+        };
+        let (tree, owner, delegate) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let hashes = compute_leaf_hash(&metadata, tree, owner, delegate, 7).unwrap();
+
+        let node = compute_leaf_node(
+            get_asset_id(tree, 7).unwrap(),
+            owner,
+            delegate,
+            7,
+            hashes.data_hash,
+            hashes.creator_hash,
+        );
+        assert_eq!(node, hashes.leaf_hash);
+    }
+}
@@ -4,7 +4,7 @@ defmodule MplBubblegumTest do
   use ExUnit.Case
   doctest MplBubblegum
 
-  alias MplBubblegum.Types.{Pubkey, Metadata, Creator, Hash}
+  alias MplBubblegum.Types.{Pubkey, Metadata, Creator, Hash, TreeConfig}
 
   # Helper to generate a valid keypair (secret + public key)
   defp generate_keypair do
@@ -101,13 +101,15 @@ defmodule MplBubblegumTest do
         merkle_tree: merkle_tree,
         payer: payer,
         tree_creator_or_delegate: tree_creator_or_delegate,
-        metadata: metadata
+        metadata: metadata,
+        nonce: 0
       }
 
-      assert {:ok, transaction} = MplBubblegum.mint_v1(params)
+      assert {:ok, transaction, asset_id} = MplBubblegum.mint_v1(params)
       transaction_binary = :binary.list_to_bin(transaction)
       assert is_binary(transaction_binary)
       assert byte_size(transaction_binary) > 0
+      assert {:ok, ^asset_id} = MplBubblegum.get_asset_id(merkle_tree, 0)
     end
 
     test "fails with invalid metadata" do
@@ -128,7 +130,8 @@ defmodule MplBubblegumTest do
         merkle_tree: merkle_tree,
         payer: payer,
         tree_creator_or_delegate: tree_creator_or_delegate,
-        metadata: invalid_metadata
+        metadata: invalid_metadata,
+        nonce: 0
       }
 
       # Rust NIF may crash or return an error
@@ -165,6 +168,7 @@ defmodule MplBubblegumTest do
         leaf_delegate: leaf_delegate,
         new_leaf_owner: new_leaf_owner,
         merkle_tree: merkle_tree,
+        payer: leaf_owner,
         root: root,
         data_hash: data_hash,
         creator_hash: creator_hash,
@@ -197,6 +201,7 @@ defmodule MplBubblegumTest do
         leaf_delegate: leaf_delegate,
         new_leaf_owner: new_leaf_owner,
         merkle_tree: merkle_tree,
+        payer: leaf_owner,
         root: invalid_root,
         data_hash: data_hash,
         creator_hash: creator_hash,
@@ -206,14 +211,40 @@ defmodule MplBubblegumTest do
 
       assert {:error, "Invalid hash format"} = MplBubblegum.transfer(params)
     end
+
+    test "rejects an index outside the tree" do
+      {_, tree_config} = generate_keypair()
+      {_, leaf_owner} = generate_keypair()
+      {_, new_leaf_owner} = generate_keypair()
+      {_, merkle_tree} = generate_keypair()
+      hash = %Hash{bytes: :binary.list_to_bin(List.duplicate(0, 32))}
+
+      params = %{
+        tree_config: tree_config,
+        leaf_owner: leaf_owner,
+        leaf_delegate: leaf_owner,
+        new_leaf_owner: new_leaf_owner,
+        merkle_tree: merkle_tree,
+        payer: leaf_owner,
+        root: hash,
+        data_hash: hash,
+        creator_hash: hash,
+        nonce: 16_384,
+        index: 16_384,
+        max_depth: 14
+      }
+
+      assert {:error, {:invalid_parameter, _}} = MplBubblegum.transfer(params)
+      assert {:ok, _} = MplBubblegum.transfer(%{params | max_depth: 15})
+    end
   end
 
   describe "sign_and_submit_transaction/2" do
     test "signs transaction successfully, even if submission fails" do
       {payer_secret, payer} = generate_keypair()
       {tree_creator_secret, tree_creator} = generate_keypair()
-      {tree_config_secret, tree_config} = generate_keypair()
       {merkle_tree_secret, merkle_tree} = generate_keypair()
+      {:ok, tree_config, _bump} = MplBubblegum.derive_tree_config_pda(merkle_tree)
 
       params = %{
         tree_config: tree_config,
@@ -228,15 +259,16 @@ defmodule MplBubblegumTest do
       {:ok, transaction} = MplBubblegum.create_tree_config(params)
       transaction_binary = :binary.list_to_bin(transaction)
 
-      secret_keys = [payer_secret, tree_creator_secret, tree_config_secret, merkle_tree_secret]
+      secret_keys = [payer_secret, tree_creator_secret, merkle_tree_secret]
       result = MplBubblegum.sign_and_submit_transaction(transaction_binary, secret_keys)
 
       case result do
         {:ok, signature} ->
           assert is_binary(signature)
           assert byte_size(signature) == 88
-        {:error, reason} ->
-          # Broaden error check since submission fails without validator
+        {:error, {kind, reason}} ->
+          # Submission fails without a validator
+          assert is_atom(kind)
           assert is_binary(reason)
       end
     end
@@ -264,7 +296,7 @@ defmodule MplBubblegumTest do
       result = MplBubblegum.sign_and_submit_transaction(transaction_binary, secret_keys)
 
       case result do
-        {:error, reason} ->
+        {:error, {_kind, reason}} ->
           # Rust NIF might not return "NotEnoughSigners" cleanly
           assert String.contains?(reason, "NotEnoughSigners") or
                  String.contains?(reason, "signature error") or
@@ -274,4 +306,318 @@ defmodule MplBubblegumTest do
       end
     end
   end
-end
\ No newline at end of file
+
+  describe "sign_transaction/3" do
+    test "signs a transaction offline without a validator" do
+      {public, private} = :crypto.generate_key(:eddsa, :ed25519)
+      payer_secret = private <> public
+      payer = %Pubkey{bytes: :binary.bin_to_list(public)}
+      {_, tree_config} = generate_keypair()
+      {_, merkle_tree} = generate_keypair()
+
+      params = %{
+        tree_config: tree_config,
+        leaf_owner: payer,
+        leaf_delegate: payer,
+        merkle_tree: merkle_tree,
+        payer: payer,
+        tree_creator_or_delegate: payer,
+        metadata: generate_metadata(payer),
+        nonce: 0
+      }
+
+      {:ok, transaction, _asset_id} = MplBubblegum.mint_v1(params)
+      blockhash = Base58.encode(:crypto.strong_rand_bytes(32))
+
+      assert {:ok, signed} = MplBubblegum.sign_transaction(transaction, [payer_secret], blockhash)
+      assert length(signed) == length(transaction)
+      refute signed == transaction
+    end
+
+    test "fails with an invalid blockhash" do
+      {_, payer} = generate_keypair()
+      {:ok, transaction} =
+        MplBubblegum.create_tree_config(%{
+          tree_config: payer,
+          merkle_tree: payer,
+          payer: payer,
+          tree_creator: payer,
+          max_depth: 14,
+          max_buffer_size: 64,
+          public: true
+        })
+
+      assert {:error, {:invalid_parameter, reason}} =
+               MplBubblegum.sign_transaction(transaction, [], "not-a-blockhash")
+
+      assert String.contains?(reason, "Invalid blockhash")
+    end
+  end
+
+  describe "pubkey validation" do
+    test "raises an invalid_parameter error for pubkeys that are not 32 bytes" do
+      tree = %Pubkey{bytes: List.duplicate(1, 31)}
+
+      assert_raise ErlangError, ~r/pubkey must be 32 bytes, got 31/, fn ->
+        MplBubblegum.get_asset_id(tree, 0)
+      end
+    end
+  end
+
+  describe "keypair_from_json/1" do
+    test "returns the pubkey of a solana-keygen keypair" do
+      {public, private} = :crypto.generate_key(:eddsa, :ed25519)
+      json = Jason.encode!(:binary.bin_to_list(private <> public))
+
+      assert {:ok, %Pubkey{bytes: bytes}} = MplBubblegum.keypair_from_json(json)
+      assert :binary.list_to_bin(bytes) == public
+    end
+
+    test "fails when the array is not 64 bytes" do
+      json = Jason.encode!(List.duplicate(1, 32))
+
+      assert {:error, {:invalid_parameter, reason}} = MplBubblegum.keypair_from_json(json)
+      assert String.contains?(reason, "got 32")
+    end
+  end
+
+  describe "parse_tree_config/1" do
+    test "decodes a tree config account" do
+      creator = :crypto.strong_rand_bytes(32)
+      delegate = :crypto.strong_rand_bytes(32)
+
+      data =
+        <<122, 245, 175, 248, 171, 34, 0, 207>> <>
+          creator <>
+          delegate <>
+          <<16_384::little-64, 42::little-64, 1, 1>> <>
+          :binary.copy(<<0>>, 6)
+
+      assert {:ok, %TreeConfig{} = tree_config} = MplBubblegum.parse_tree_config(data)
+      assert :binary.list_to_bin(tree_config.tree_creator.bytes) == creator
+      assert :binary.list_to_bin(tree_config.tree_delegate.bytes) == delegate
+      assert tree_config.total_mint_capacity == 16_384
+      assert tree_config.num_minted == 42
+      assert tree_config.is_public
+      refute tree_config.is_decompressible
+    end
+
+    test "fails on truncated data" do
+      assert {:error, {:conversion, _reason}} = MplBubblegum.parse_tree_config(<<0, 1, 2>>)
+    end
+
+    test "fails on accounts of another type" do
+      assert {:error, {:conversion, _reason}} = MplBubblegum.parse_tree_config(:binary.copy(<<0>>, 165))
+    end
+  end
+
+  describe "program ids" do
+    test "match the canonical deployments" do
+      assert Pubkey.to_base58(MplBubblegum.bubblegum_program_id()) ==
+               "BGUMAp9Gq7iTEuizy4pqaxsTyUCBK68MDfK752saRPUY"
+
+      assert Pubkey.to_base58(MplBubblegum.account_compression_program_id()) ==
+               "cmtDvXumGCrqC1Age74AVPhSRVXJMd8PJS91L8KbNCK"
+
+      assert Pubkey.to_base58(MplBubblegum.noop_program_id()) ==
+               "noopb9bkMVfRPU8AsbpTUg8AQkHtKwMYZiFUjNRtMmV"
+    end
+  end
+
+  describe "hash_creators/1" do
+    test "rejects shares that do not sum to 100" do
+      {_, first} = generate_keypair()
+      {_, second} = generate_keypair()
+
+      creators = [
+        %Creator{address: first, verified: false, share: 60},
+        %Creator{address: second, verified: false, share: 30}
+      ]
+
+      assert {:error, {:invalid_parameter, reason}} = MplBubblegum.hash_creators(creators)
+      assert String.contains?(reason, "got 90")
+    end
+  end
+
+  describe "hash_metadata/1" do
+    test "rejects a name longer than 32 bytes" do
+      {_, payer} = generate_keypair()
+      metadata = %{generate_metadata(payer) | name: String.duplicate("a", 33)}
+
+      assert {:error, {:invalid_parameter, reason}} = MplBubblegum.hash_metadata(metadata)
+      assert String.contains?(reason, "name")
+    end
+
+    test "rejects more than 5 creators" do
+      {_, payer} = generate_keypair()
+
+      creators =
+        for share <- [20, 20, 20, 20, 10, 10] do
+          {_, address} = generate_keypair()
+          %Creator{address: address, verified: false, share: share}
+        end
+
+      metadata = %{generate_metadata(payer) | creators: creators}
+
+      assert {:error, {:invalid_parameter, reason}} = MplBubblegum.hash_metadata(metadata)
+      assert String.contains?(reason, "got 6")
+    end
+  end
+
+  describe "default_metadata/3" do
+    test "builds metadata that can be hashed" do
+      metadata = MplBubblegum.default_metadata("Test NFT", "TNFT", "https://example.com/test.json")
+
+      assert %Metadata{seller_fee_basis_points: 0, is_mutable: true, creators: []} = metadata
+      assert {:ok, _hash} = MplBubblegum.hash_metadata(metadata)
+    end
+  end
+
+  describe "metadata_to_borsh/1" do
+    test "round-trips through metadata_from_borsh/1" do
+      {_, payer} = generate_keypair()
+      metadata = generate_metadata(payer)
+
+      assert {:ok, bytes} = MplBubblegum.metadata_to_borsh(metadata)
+      assert {:ok, decoded} = MplBubblegum.metadata_from_borsh(bytes)
+      assert MplBubblegum.hash_metadata(decoded) == MplBubblegum.hash_metadata(metadata)
+    end
+  end
+
+  describe "hash_metadata_batch/1" do
+    test "hashes each metadata like hash_metadata/1" do
+      {_, payer} = generate_keypair()
+      metadata = generate_metadata(payer)
+      {:ok, hash} = MplBubblegum.hash_metadata(metadata)
+
+      assert {:ok, [^hash, ^hash]} = MplBubblegum.hash_metadata_batch([metadata, metadata])
+    end
+
+    test "reports the index of invalid metadata" do
+      {_, payer} = generate_keypair()
+      metadata = generate_metadata(payer)
+      invalid = %{metadata | name: String.duplicate("a", 33)}
+
+      assert {:error, {1, {:invalid_parameter, _}}} = MplBubblegum.hash_metadata_batch([metadata, invalid])
+    end
+  end
+
+  describe "sign_message/2" do
+    test "produces a signature that verifies against the signer's pubkey" do
+      {public, private} = :crypto.generate_key(:eddsa, :ed25519)
+      pubkey = %Pubkey{bytes: :binary.bin_to_list(public)}
+
+      assert {:ok, signature} = MplBubblegum.sign_message(private <> public, "login:nonce-123")
+      assert byte_size(signature) == 64
+      assert {:ok, true} = MplBubblegum.verify_signature(pubkey, "login:nonce-123", signature)
+      assert {:ok, false} = MplBubblegum.verify_signature(pubkey, "login:nonce-456", signature)
+    end
+
+    test "accepts a raw secret key that starts with a bracket" do
+      {public, private} = :crypto.generate_key(:eddsa, :ed25519, String.duplicate("[", 32))
+      pubkey = %Pubkey{bytes: :binary.bin_to_list(public)}
+
+      assert {:ok, signature} = MplBubblegum.sign_message(private <> public, "login:nonce-123")
+      assert {:ok, true} = MplBubblegum.verify_signature(pubkey, "login:nonce-123", signature)
+    end
+
+    test "accepts the 32-byte seed in place of the full secret key" do
+      {public, private} = :crypto.generate_key(:eddsa, :ed25519)
+      pubkey = %Pubkey{bytes: :binary.bin_to_list(public)}
+
+      assert {:ok, signature} = MplBubblegum.sign_message(private, "login:nonce-123")
+      assert {:ok, true} = MplBubblegum.verify_signature(pubkey, "login:nonce-123", signature)
+    end
+
+    test "accepts a seed that starts with a bracket" do
+      seed = String.duplicate("[", 32)
+      {public, _private} = :crypto.generate_key(:eddsa, :ed25519, seed)
+      pubkey = %Pubkey{bytes: :binary.bin_to_list(public)}
+
+      assert {:ok, signature} = MplBubblegum.sign_message(seed, "login:nonce-123")
+      assert {:ok, true} = MplBubblegum.verify_signature(pubkey, "login:nonce-123", signature)
+    end
+  end
+
+  describe "pubkey_is_on_curve/1" do
+    test "tells wallet addresses apart from PDAs" do
+      {public, _private} = :crypto.generate_key(:eddsa, :ed25519)
+      {_, merkle_tree} = generate_keypair()
+      {:ok, tree_config, _bump} = MplBubblegum.derive_tree_config_pda(merkle_tree)
+
+      assert {:ok, true} = MplBubblegum.pubkey_is_on_curve(%Pubkey{bytes: :binary.bin_to_list(public)})
+      assert {:ok, false} = MplBubblegum.pubkey_is_on_curve(tree_config)
+    end
+  end
+
+  describe "keypair_from_base58/1" do
+    test "loads a wallet-exported secret key" do
+      {public, private} = :crypto.generate_key(:eddsa, :ed25519)
+
+      assert {:ok, pubkey, secret_key} = MplBubblegum.keypair_from_base58(Base58.encode(private <> public))
+      assert pubkey.bytes == :binary.bin_to_list(public)
+      assert secret_key == private <> public
+    end
+
+    test "rejects secret keys that are not 64 bytes" do
+      {_public, private} = :crypto.generate_key(:eddsa, :ed25519)
+
+      assert {:error, {:invalid_parameter, "secret key must be 64 bytes, got 32"}} =
+               MplBubblegum.keypair_from_base58(Base58.encode(private))
+    end
+  end
+
+  describe "build_batch_mint/1" do
+    test "rejects a batch that does not fit in one transaction" do
+      {_, payer} = generate_keypair()
+
+      mints =
+        for _ <- 1..10 do
+          {_, tree_config} = generate_keypair()
+          {_, leaf_owner} = generate_keypair()
+          {_, merkle_tree} = generate_keypair()
+
+          %MplBubblegum.Types.MintRequest{
+            tree_config: tree_config,
+            leaf_owner: leaf_owner,
+            leaf_delegate: leaf_owner,
+            merkle_tree: merkle_tree,
+            tree_creator_or_delegate: payer,
+            metadata: generate_metadata(payer)
+          }
+        end
+
+      assert {:ok, _transaction} = MplBubblegum.build_batch_mint(%{payer: payer, mints: Enum.take(mints, 2)})
+      assert {:error, {:invalid_parameter, _}} = MplBubblegum.build_batch_mint(%{payer: payer, mints: mints})
+    end
+  end
+
+  describe "decode_transaction/1" do
+    test "lists the instructions of a built transaction" do
+      {_, payer} = generate_keypair()
+      {_, merkle_tree} = generate_keypair()
+      {:ok, tree_config, _bump} = MplBubblegum.derive_tree_config_pda(merkle_tree)
+
+      {:ok, transaction} =
+        MplBubblegum.create_tree_config(%{
+          tree_config: tree_config,
+          merkle_tree: merkle_tree,
+          payer: payer,
+          tree_creator: payer,
+          max_depth: 14,
+          max_buffer_size: 64,
+          public: false
+        })
+
+      assert {:ok, decoded} = MplBubblegum.decode_transaction(transaction)
+      assert decoded.fee_payer == Pubkey.to_base58(payer)
+      assert decoded.num_signatures == 2
+      assert length(decoded.instructions) == 2
+      assert List.last(decoded.instructions).program_id == Pubkey.to_base58(MplBubblegum.bubblegum_program_id())
+    end
+
+    test "rejects bytes that are not a transaction" do
+      assert {:error, {:deserialization, _}} = MplBubblegum.decode_transaction(<<1, 2, 3>>)
+    end
+  end
+end